@@ -1,6 +1,15 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20250101_000000_init_schema;
+mod m20250201_000000_add_game_rule_set;
+mod m20250215_000000_seed_extra_ai_users;
+mod m20250301_000000_add_deal_seeds;
+mod m20250315_000000_add_deal_commitments;
+mod m20250401_000000_add_game_events;
+mod m20250415_000000_add_ai_difficulty;
+mod m20250501_000000_add_bots;
+mod m20250515_000000_add_game_version;
+mod m20250601_000000_add_idempotency_keys;
 
 pub struct Migrator;
 
@@ -9,6 +18,15 @@ impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
         vec![
             Box::new(m20250101_000000_init_schema::Migration),
+            Box::new(m20250201_000000_add_game_rule_set::Migration),
+            Box::new(m20250215_000000_seed_extra_ai_users::Migration),
+            Box::new(m20250301_000000_add_deal_seeds::Migration),
+            Box::new(m20250315_000000_add_deal_commitments::Migration),
+            Box::new(m20250401_000000_add_game_events::Migration),
+            Box::new(m20250415_000000_add_ai_difficulty::Migration),
+            Box::new(m20250501_000000_add_bots::Migration),
+            Box::new(m20250515_000000_add_game_version::Migration),
+            Box::new(m20250601_000000_add_idempotency_keys::Migration),
         ]
     }
 }
@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Rules every game was played with before rule sets were stored per game
+const STANDARD_RULE_SET: &str = r#"{"player_count":4,"round_schedule":[13,12,11,10,9,8,7,6,5,4,3,2,2,2,2,3,4,5,6,7,8,9,10,11,12,13],"min_bid":0,"max_bid":13}"#;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Games::Table)
+                    .add_column(
+                        ColumnDef::new(Games::RuleSet)
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust(format!("'{STANDARD_RULE_SET}'::jsonb"))),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Games::Table)
+                    .drop_column(Games::RuleSet)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    RuleSet,
+}
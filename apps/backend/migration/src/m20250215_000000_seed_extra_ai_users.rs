@@ -0,0 +1,56 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+use uuid::Uuid;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Insert 3 more AI users so a 7 seat table can be filled with one human
+        let now = chrono::Utc::now();
+        let ai_users = vec![
+            ("ai_user_4", "__ai+4@nommie.dev", "Bluffing Badger", now, now),
+            ("ai_user_5", "__ai+5@nommie.dev", "Counting Crow", now, now),
+            ("ai_user_6", "__ai+6@nommie.dev", "Patient Panda", now, now),
+        ];
+
+        for (external_id, email, name, created_at, updated_at) in ai_users {
+            manager
+                .get_connection()
+                .execute(
+                    Statement::from_sql_and_values(
+                        manager.get_database_backend(),
+                        r#"INSERT INTO users (id, external_id, email, name, is_ai, created_at, updated_at)
+                           VALUES ($1, $2, $3, $4, $5, $6, $7)
+                           ON CONFLICT (email) DO NOTHING"#,
+                        vec![
+                            Uuid::new_v4().into(),
+                            external_id.into(),
+                            email.into(),
+                            name.into(),
+                            true.into(),
+                            created_at.into(),
+                            updated_at.into(),
+                        ],
+                    ),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"DELETE FROM users
+                   WHERE email IN ('__ai+4@nommie.dev', '__ai+5@nommie.dev', '__ai+6@nommie.dev')"#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Pinned base seed for a game; rounds derive their seed from it when set
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Games::Table)
+                    .add_column(ColumnDef::new(Games::DealSeed).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Seed used to shuffle the deck for each round, so any deal can be replayed
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameRounds::Table)
+                    .add_column(ColumnDef::new(GameRounds::DealSeed).big_integer().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameRounds::Table)
+                    .drop_column(GameRounds::DealSeed)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Games::Table)
+                    .drop_column(Games::DealSeed)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    DealSeed,
+}
+
+#[derive(DeriveIden)]
+enum GameRounds {
+    Table,
+    DealSeed,
+}
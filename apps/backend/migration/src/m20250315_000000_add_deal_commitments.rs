@@ -0,0 +1,52 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Salt mixed into the published commitment, revealed once the round is scored
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameRounds::Table)
+                    .add_column(ColumnDef::new(GameRounds::DealSalt).string_len(64).null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // SHA-256 commitment of the deal seed and salt, published when the round is dealt
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameRounds::Table)
+                    .add_column(
+                        ColumnDef::new(GameRounds::DealCommitment)
+                            .string_len(64)
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameRounds::Table)
+                    .drop_column(GameRounds::DealCommitment)
+                    .drop_column(GameRounds::DealSalt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GameRounds {
+    Table,
+    DealSalt,
+    DealCommitment,
+}
@@ -0,0 +1,81 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Append-only log of everything that happened in a game, in order
+        manager
+            .create_table(
+                Table::create()
+                    .table(GameEvents::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GameEvents::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GameEvents::GameId).uuid().not_null())
+                    .col(ColumnDef::new(GameEvents::Sequence).integer().not_null())
+                    .col(
+                        ColumnDef::new(GameEvents::EventType)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(GameEvents::Payload).json().not_null())
+                    .col(
+                        ColumnDef::new(GameEvents::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_game_events_game_id")
+                            .from(GameEvents::Table, GameEvents::GameId)
+                            .to(Games::Table, Games::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Each game's events are numbered without gaps or repeats
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_game_events_game_id_sequence")
+                    .table(GameEvents::Table)
+                    .col(GameEvents::GameId)
+                    .col(GameEvents::Sequence)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GameEvents::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GameEvents {
+    Table,
+    Id,
+    GameId,
+    Sequence,
+    EventType,
+    Payload,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Id,
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Level an AI seat plays at; AI seats added before this default to the hardest
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GamePlayers::Table)
+                    .add_column(ColumnDef::new(GamePlayers::AiDifficulty).string_len(10).null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GamePlayers::Table)
+                    .drop_column(GamePlayers::AiDifficulty)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GamePlayers {
+    Table,
+    AiDifficulty,
+}
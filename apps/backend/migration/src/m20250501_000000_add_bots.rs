@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // External bots; each plays its seats through its own AI user
+        manager
+            .create_table(
+                Table::create()
+                    .table(Bots::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Bots::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Bots::UserId).uuid().not_null().unique_key())
+                    .col(ColumnDef::new(Bots::OwnerId).uuid().not_null())
+                    .col(ColumnDef::new(Bots::Name).string().not_null())
+                    // Where decisions are posted; bots without one connect over WebSocket
+                    .col(ColumnDef::new(Bots::CallbackUrl).string().null())
+                    .col(ColumnDef::new(Bots::TokenHash).string_len(64).not_null())
+                    .col(
+                        ColumnDef::new(Bots::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_bots_user_id")
+                            .from(Bots::Table, Bots::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_bots_owner_id")
+                            .from(Bots::Table, Bots::OwnerId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Bots::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Bots {
+    Table,
+    Id,
+    UserId,
+    OwnerId,
+    Name,
+    CallbackUrl,
+    TokenHash,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,80 @@
+//! Reference bot
+//!
+//! Serves the callback side of the bot protocol, always answering with the
+//! first legal action. Register it with `http://127.0.0.1:<port>/` as its
+//! callback URL. Run `nommie-bot --help` for the options.
+
+use std::env;
+use std::process::ExitCode;
+
+use actix_web::{post, web, App, HttpResponse, HttpServer};
+use backend::game_management::bot_protocol::{reference_reply, DecisionRequest};
+
+const USAGE: &str = "\
+Usage: nommie-bot [options]
+
+Options:
+  --port N   Port to serve decision requests on (default 9000)
+  --help     Show this message";
+
+const DEFAULT_PORT: u16 = 9000;
+
+#[post("/")]
+async fn decide(request: web::Json<DecisionRequest>) -> HttpResponse {
+    match reference_reply(&request) {
+        Some(reply) => HttpResponse::Ok().json(reply),
+        None => HttpResponse::UnprocessableEntity().body("No legal actions"),
+    }
+}
+
+#[actix_web::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    let port = match parse_args(&args) {
+        Ok(port) => port,
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let server = match HttpServer::new(|| App::new().service(decide)).bind(("127.0.0.1", port)) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Failed to bind port {port}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("Reference bot listening on http://127.0.0.1:{port}/");
+    match server.run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Server failed: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<u16, String> {
+    let mut port = DEFAULT_PORT;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--port" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {arg}"))?;
+                port = value
+                    .parse()
+                    .map_err(|_| format!("Invalid value for {arg}: {value}"))?;
+            }
+            _ => return Err(format!("Unknown option {arg}")),
+        }
+    }
+    Ok(port)
+}
@@ -0,0 +1,49 @@
+//! Training environment over stdin and stdout
+//!
+//! Reads one JSON command per line and answers each with one line of JSON, so
+//! training code in any language can drive the rules engine as a subprocess.
+//! No database is needed; run as many processes as there are environments.
+//! The commands are documented in `docs/gym.md`.
+
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+
+use backend::game_management::gym::Gym;
+
+fn main() -> ExitCode {
+    if std::env::args()
+        .skip(1)
+        .any(|arg| arg == "--help" || arg == "-h")
+    {
+        println!(
+            "Usage: nommie-gym\n\nReads JSON commands from stdin, one per line; see docs/gym.md."
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    let mut gym = Gym::default();
+    let stdin = io::stdin();
+    let mut stdout = io::stdout().lock();
+    for line in stdin.lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("Failed to read command: {e}");
+                return ExitCode::FAILURE;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let reply = gym.handle_line(&line);
+        if writeln!(stdout, "{reply}")
+            .and_then(|()| stdout.flush())
+            .is_err()
+        {
+            // The reader went away
+            break;
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,142 @@
+//! Headless bot-vs-bot simulation
+//!
+//! Plays complete games between AI levels in memory and reports how each
+//! level did. Run `nommie-sim --help` for the options.
+
+use std::env;
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+use backend::game_management::ai::{Difficulty, SearchBudget};
+use backend::game_management::rules::RuleSet;
+use backend::game_management::scoring::ScoringVariant;
+use backend::game_management::simulation::{run, SimConfig};
+
+const USAGE: &str = "\
+Usage: nommie-sim [options]
+
+Options:
+  --games N              Games to play (default 100)
+  --players LIST         Comma-separated level of each seat: easy, medium,
+                         hard or expert (default easy,medium,hard,expert)
+  --seed N               Seed for deals and AI choices (default 1)
+  --scoring VARIANT      standard, exact_bid, minus_difference or
+                         zero_bid_bonus (default standard)
+  --dealer-hook          Play with the dealer hook bidding rule
+  --threads N            Worker threads (default: one per core)
+  --expert-iterations N  Play-outs per expert decision (default 200); expert
+                         search has no time limit here, so runs repeat exactly
+  --json                 Print the report as JSON instead of a table
+  --help                 Show this message";
+
+/// Expert play-outs per decision unless `--expert-iterations` is given
+const DEFAULT_EXPERT_ITERATIONS: u32 = 200;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    let (config, json) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let report = match run(&config) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Simulation failed: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(output) => println!("{output}"),
+            Err(e) => {
+                eprintln!("Failed to serialize report: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        print!("{}", report.to_table());
+    }
+    ExitCode::SUCCESS
+}
+
+/// Build the run from the command line, and whether to print JSON
+fn parse_args(args: &[String]) -> Result<(SimConfig, bool), String> {
+    let mut games = 100;
+    let mut players = vec![
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+    let mut seed = 1;
+    let mut scoring = ScoringVariant::default();
+    let mut dealer_hook = false;
+    let mut threads = thread::available_parallelism().map_or(1, |cores| cores.get());
+    let mut expert_iterations = DEFAULT_EXPERT_ITERATIONS;
+    let mut json = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .map(String::as_str)
+                .ok_or_else(|| format!("Missing value for {arg}"))
+        };
+        match arg.as_str() {
+            "--games" => games = parse_number(arg, value()?)?,
+            "--players" => {
+                players = value()?
+                    .split(',')
+                    .map(|name| parse_name(arg, name.trim()))
+                    .collect::<Result<_, _>>()?
+            }
+            "--seed" => seed = parse_number(arg, value()?)?,
+            "--scoring" => scoring = parse_name(arg, value()?)?,
+            "--dealer-hook" => dealer_hook = true,
+            "--threads" => threads = parse_number(arg, value()?)?,
+            "--expert-iterations" => expert_iterations = parse_number(arg, value()?)?,
+            "--json" => json = true,
+            _ => return Err(format!("Unknown option {arg}")),
+        }
+    }
+
+    let mut rules = RuleSet::for_players(players.len())?;
+    rules.scoring = scoring;
+    rules.dealer_hook = dealer_hook;
+
+    let config = SimConfig {
+        rules,
+        players,
+        games,
+        seed,
+        threads,
+        expert_budget: SearchBudget {
+            iterations: expert_iterations,
+            time: Duration::MAX,
+        },
+    };
+    Ok((config, json))
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {option}: {value}"))
+}
+
+/// Parse a snake_case name the way the API accepts it
+fn parse_name<T: serde::de::DeserializeOwned>(option: &str, value: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| format!("Invalid value for {option}: {value}"))
+}
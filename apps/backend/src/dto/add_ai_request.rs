@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::dto::version_request::VersionRequest;
+use crate::game_management::ai::Difficulty;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AddAiRequest {
+    /// Level the AI seat plays at; defaults to hard
+    pub difficulty: Option<Difficulty>,
+    /// Registered bot to seat; it falls back to `difficulty` when it fails to answer
+    pub bot_id: Option<Uuid>,
+    #[serde(flatten)]
+    pub version: VersionRequest,
+}
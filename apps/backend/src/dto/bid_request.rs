@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 
+use crate::dto::version_request::VersionRequest;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BidRequest {
     pub bid: i32,
+    #[serde(flatten)]
+    pub version: VersionRequest,
 }
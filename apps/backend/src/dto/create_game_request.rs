@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_management::scoring::ScoringVariant;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateGameRequest {
+    /// Number of seats at the table (3 to 7); defaults to the standard 4
+    pub player_count: Option<usize>,
+    /// Scoring rule for the game; defaults to standard scoring
+    pub scoring: Option<ScoringVariant>,
+    /// Opt in to the dealer hook bidding rule; off by default
+    pub dealer_hook: Option<bool>,
+    /// Pin the deal seed so every round is dealt reproducibly; admins only
+    pub deal_seed: Option<i64>,
+}
@@ -2,6 +2,8 @@ use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::game_management::cards::Trump;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameSnapshot {
     pub game: GameInfo,
@@ -21,6 +23,7 @@ pub struct GameInfo {
     pub created_at: DateTime<FixedOffset>,
     pub updated_at: DateTime<FixedOffset>,
     pub started_at: Option<DateTime<FixedOffset>>,
+    pub version: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,16 +51,25 @@ pub struct RoundSnapshot {
     pub round_number: i32,
     pub phase: String,
     pub dealer_player_id: Option<Uuid>,
-    pub trump_suit: Option<String>,
+    pub trump_suit: Option<Trump>,
     pub cards_dealt: i32,
     pub bids: Vec<RoundBidSnapshot>,
     pub current_bidder_turn: Option<i32>,
+    pub forbidden_bid: Option<i32>, // Bid the current bidder may not make under the dealer hook rule
     pub current_trick: Option<TrickSnapshot>,
     pub completed_tricks: Vec<TrickSnapshot>,
     pub current_player_turn: Option<Uuid>,
+    pub deal_commitment: Option<String>, // SHA-256 of "{seed}:{salt}", published when dealt
+    pub deal_reveal: Option<DealRevealSnapshot>, // Seed and salt, revealed once the round is scored
     pub round_scores: Vec<RoundScoreSnapshot>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DealRevealSnapshot {
+    pub seed: String, // Decimal string so JavaScript clients keep full 64-bit precision
+    pub salt: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrickSnapshot {
     pub id: Uuid,
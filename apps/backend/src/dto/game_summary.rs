@@ -2,6 +2,9 @@ use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::dto::game_snapshot::DealRevealSnapshot;
+use crate::game_management::cards::Trump;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameSummary {
     pub game: GameSummaryInfo,
@@ -42,8 +45,10 @@ pub struct UserSummary {
 pub struct RoundSummary {
     pub round_number: i32,
     pub cards_dealt: i32,
-    pub trump_suit: Option<String>,
+    pub trump_suit: Option<Trump>,
     pub dealer_player_id: Option<Uuid>,
+    pub deal_commitment: Option<String>,
+    pub deal_reveal: Option<DealRevealSnapshot>,
     pub player_results: Vec<PlayerRoundResult>,
 }
 
@@ -60,7 +65,7 @@ pub struct PlayerRoundResult {
 pub struct FinalRoundSummary {
     pub round_number: i32,
     pub cards_dealt: i32,
-    pub trump_suit: Option<String>,
+    pub trump_suit: Option<Trump>,
     pub dealer_player_id: Option<Uuid>,
     pub bids: Vec<RoundBidSummary>,
     pub tricks_won: Vec<RoundScoreSummary>,
@@ -1,5 +1,10 @@
+pub mod add_ai_request;
 pub mod bid_request;
+pub mod create_game_request;
 pub mod game_snapshot;
 pub mod game_summary;
 pub mod play_request;
+pub mod register_bot_request;
+pub mod state_query;
 pub mod trump_request;
+pub mod version_request;
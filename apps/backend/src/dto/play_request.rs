@@ -1,6 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+use crate::dto::version_request::VersionRequest;
+use crate::game_management::cards::Card;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayRequest {
-    pub card: String,
+    pub card: Card,
+    #[serde(flatten)]
+    pub version: VersionRequest,
 }
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterBotRequest {
+    pub name: String,
+    /// Where decision requests are posted; omit to connect over WebSocket
+    pub callback_url: Option<String>,
+}
@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StateQuery {
+    /// Hold the response until the game moves past this version
+    pub wait_for_version: Option<i32>,
+}
@@ -1,6 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+use crate::dto::version_request::VersionRequest;
+use crate::game_management::cards::Trump;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrumpRequest {
-    pub trump_suit: String,
+    pub trump_suit: Trump,
+    #[serde(flatten)]
+    pub version: VersionRequest,
 }
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Version of the game an action was chosen at
+///
+/// Sent as `expected_version`, alone or flattened into an action's body. Once
+/// the game has moved past it the action is refused with `409 Conflict`, so a
+/// player never acts on a state they have not seen.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VersionRequest {
+    pub expected_version: Option<i32>,
+}
@@ -1,6 +1,8 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::game_management::ai::Difficulty;
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "game_players")]
 pub struct Model {
@@ -10,6 +12,21 @@ pub struct Model {
     pub user_id: Uuid,
     pub turn_order: Option<i32>,
     pub is_ready: bool,
+    /// Level the seat plays at; only set for AI seats
+    pub ai_difficulty: Option<AiDifficulty>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(10))")]
+pub enum AiDifficulty {
+    #[sea_orm(string_value = "easy")]
+    Easy,
+    #[sea_orm(string_value = "medium")]
+    Medium,
+    #[sea_orm(string_value = "hard")]
+    Hard,
+    #[sea_orm(string_value = "expert")]
+    Expert,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -41,3 +58,25 @@ impl Related<super::users::Entity> for Entity {
 }
 
 impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Difficulty> for AiDifficulty {
+    fn from(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => AiDifficulty::Easy,
+            Difficulty::Medium => AiDifficulty::Medium,
+            Difficulty::Hard => AiDifficulty::Hard,
+            Difficulty::Expert => AiDifficulty::Expert,
+        }
+    }
+}
+
+impl From<AiDifficulty> for Difficulty {
+    fn from(ai_difficulty: AiDifficulty) -> Self {
+        match ai_difficulty {
+            AiDifficulty::Easy => Difficulty::Easy,
+            AiDifficulty::Medium => Difficulty::Medium,
+            AiDifficulty::Hard => Difficulty::Hard,
+            AiDifficulty::Expert => Difficulty::Expert,
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::game_management::cards::{Suit, Trump};
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "game_rounds")]
 pub struct Model {
@@ -9,9 +11,27 @@ pub struct Model {
     pub game_id: Uuid,
     pub round_number: i32,
     pub dealer_player_id: Option<Uuid>,
-    pub trump_suit: Option<String>,
+    pub trump_suit: Option<TrumpSuit>,
     pub cards_dealt: i32,
     pub created_at: DateTimeWithTimeZone,
+    pub deal_seed: Option<i64>,
+    pub deal_salt: Option<String>,
+    pub deal_commitment: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(10))")]
+pub enum TrumpSuit {
+    #[sea_orm(string_value = "Spades")]
+    Spades,
+    #[sea_orm(string_value = "Hearts")]
+    Hearts,
+    #[sea_orm(string_value = "Diamonds")]
+    Diamonds,
+    #[sea_orm(string_value = "Clubs")]
+    Clubs,
+    #[sea_orm(string_value = "NoTrump")]
+    NoTrump,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -51,3 +71,27 @@ impl Related<super::round_bids::Entity> for Entity {
 }
 
 impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Trump> for TrumpSuit {
+    fn from(trump: Trump) -> Self {
+        match trump {
+            Trump::Suit(Suit::Spades) => TrumpSuit::Spades,
+            Trump::Suit(Suit::Hearts) => TrumpSuit::Hearts,
+            Trump::Suit(Suit::Diamonds) => TrumpSuit::Diamonds,
+            Trump::Suit(Suit::Clubs) => TrumpSuit::Clubs,
+            Trump::NoTrump => TrumpSuit::NoTrump,
+        }
+    }
+}
+
+impl From<TrumpSuit> for Trump {
+    fn from(trump_suit: TrumpSuit) -> Self {
+        match trump_suit {
+            TrumpSuit::Spades => Trump::Suit(Suit::Spades),
+            TrumpSuit::Hearts => Trump::Suit(Suit::Hearts),
+            TrumpSuit::Diamonds => Trump::Suit(Suit::Diamonds),
+            TrumpSuit::Clubs => Trump::Suit(Suit::Clubs),
+            TrumpSuit::NoTrump => Trump::NoTrump,
+        }
+    }
+}
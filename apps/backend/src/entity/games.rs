@@ -2,6 +2,8 @@ use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use crate::game_management::rules::RuleSet;
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "games")]
 pub struct Model {
@@ -14,6 +16,10 @@ pub struct Model {
     pub updated_at: DateTimeWithTimeZone,
     pub started_at: Option<DateTimeWithTimeZone>,
     pub completed_at: Option<DateTimeWithTimeZone>,
+    pub rule_set: Json,
+    pub deal_seed: Option<i64>,
+    /// Sequence of the game's latest event, so it goes up with every change
+    pub version: i32,
 }
 
 #[derive(Clone, Debug, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
@@ -54,6 +60,14 @@ impl Related<super::game_players::Entity> for Entity {
 
 impl ActiveModelBehavior for ActiveModel {}
 
+impl Model {
+    /// Decode the rule set this game is played with
+    pub fn rules(&self) -> Result<RuleSet, String> {
+        serde_json::from_value(self.rule_set.clone())
+            .map_err(|e| format!("Corrupt rule set in storage: {e}"))
+    }
+}
+
 impl fmt::Display for GameState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -1,6 +1,9 @@
+pub mod bots;
+pub mod game_events;
 pub mod game_players;
 pub mod game_rounds;
 pub mod games;
+pub mod idempotency_keys;
 pub mod round_bids;
 pub mod round_hands;
 pub mod round_scores;
@@ -0,0 +1,145 @@
+//! Greedy AI
+//!
+//! The middle built-in level. It bids its high cards, names its longest suit
+//! as trump and then tries to win every trick, whatever it bid.
+
+use rand::RngCore;
+
+use super::{wins_trick_so_far, SeatView, Strategy};
+use crate::game_management::bidding::avoid_hook_bid;
+use crate::game_management::cards::{Card, Rank, Suit, Trump};
+use crate::game_management::rules::is_trump_card;
+
+/// Bids its high cards and grabs every trick it can
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GreedyStrategy;
+
+impl Strategy for GreedyStrategy {
+    /// One trick for each ace and king held
+    fn choose_bid(&self, view: &SeatView, _rng: &mut dyn RngCore) -> i32 {
+        let high_cards = view
+            .hand
+            .iter()
+            .filter(|card| card.rank >= Rank::King)
+            .count() as i32;
+        let bid = high_cards.clamp(
+            view.rules.min_bid,
+            view.rules.max_bid_for_round(view.cards_dealt),
+        );
+        avoid_hook_bid(bid, view.forbidden_bid(), view.cards_dealt, &view.rules)
+    }
+
+    /// The longest suit, ties going to the suit with the higher top card
+    fn choose_trump(&self, view: &SeatView, _rng: &mut dyn RngCore) -> Trump {
+        let strength = |suit: Suit| {
+            let held = view.hand.iter().filter(|card| card.suit == suit);
+            (held.clone().count(), held.map(|card| card.rank).max())
+        };
+        Suit::ALL
+            .into_iter()
+            .max_by_key(|suit| strength(*suit))
+            .map_or(Trump::NoTrump, Trump::Suit)
+    }
+
+    /// Lead the strongest card, win as cheaply as possible, otherwise throw
+    /// the weakest card
+    fn choose_card(&self, view: &SeatView, _rng: &mut dyn RngCore) -> Card {
+        let trump = view.trump.unwrap_or(Trump::NoTrump);
+        let trick = view.current_trick();
+        let mut legal = view.legal_cards();
+        // Weakest first: plain cards before trumps, then by rank
+        legal.sort_by_key(|card| (is_trump_card(card, trump), card.rank));
+
+        let choice = if trick.is_empty() {
+            legal.last()
+        } else {
+            legal
+                .iter()
+                .find(|card| wins_trick_so_far(**card, trick, trump))
+                .or(legal.first())
+        };
+        choice.copied().unwrap_or(view.hand[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_management::engine::Trick;
+    use crate::game_management::rules::RuleSet;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use uuid::Uuid;
+
+    fn cards(notation: &[&str]) -> Vec<Card> {
+        notation.iter().map(|card| card.parse().unwrap()).collect()
+    }
+
+    fn view(hand: &[&str], trump: Option<Trump>, trick: &[&str]) -> SeatView {
+        let players: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let plays = cards(trick)
+            .into_iter()
+            .zip(players[1..].iter().copied())
+            .collect();
+        SeatView {
+            rules: RuleSet::standard(),
+            seat: 0,
+            players,
+            round_number: 1,
+            dealer: 3,
+            cards_dealt: 13,
+            hand: cards(hand),
+            bids: vec![None; 4],
+            trump,
+            tricks: vec![Trick {
+                trick_number: 1,
+                leader: 1,
+                plays,
+                winner: None,
+            }],
+            scores: vec![0; 4],
+        }
+    }
+
+    #[test]
+    fn test_greedy_bids_high_cards() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let hand = view(&["AS", "KH", "2D", "3C", "KC"], None, &[]);
+        assert_eq!(GreedyStrategy.choose_bid(&hand, &mut rng), 3);
+    }
+
+    #[test]
+    fn test_greedy_names_longest_suit() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let hand = view(&["AS", "2H", "3H", "4H", "KD", "QD"], None, &[]);
+        assert_eq!(
+            GreedyStrategy.choose_trump(&hand, &mut rng),
+            Trump::Suit(Suit::Hearts)
+        );
+    }
+
+    #[test]
+    fn test_greedy_wins_cheaply_or_throws_weakest() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let spades = Some(Trump::Suit(Suit::Spades));
+
+        let winning = view(&["AH", "QH", "9H", "2S"], spades, &["TH"]);
+        assert_eq!(
+            GreedyStrategy.choose_card(&winning, &mut rng),
+            cards(&["QH"])[0]
+        );
+
+        // Out of the suit led, a trump takes the trick
+        let ruffing = view(&["AD", "2S", "5S"], spades, &["TH", "JH"]);
+        assert_eq!(
+            GreedyStrategy.choose_card(&ruffing, &mut rng),
+            cards(&["2S"])[0]
+        );
+
+        let losing = view(&["9H", "3H"], spades, &["TH", "AH"]);
+        assert_eq!(
+            GreedyStrategy.choose_card(&losing, &mut rng),
+            cards(&["3H"])[0]
+        );
+    }
+}
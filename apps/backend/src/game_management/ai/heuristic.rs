@@ -0,0 +1,478 @@
+//! Heuristic AI
+//!
+//! The hard built-in level, one below the expert search. It estimates the
+//! tricks its hand will take to bid and pick trump, then plays to take exactly
+//! the tricks it bid.
+
+use std::cmp::Reverse;
+
+use rand::RngCore;
+use uuid::Uuid;
+
+use super::{wins_trick_so_far, SeatView, Strategy};
+use crate::game_management::bidding::avoid_hook_bid;
+use crate::game_management::cards::{Card, Rank, Suit, Trump};
+use crate::game_management::rules::{is_trump_card, RuleSet, DECK_SIZE};
+use crate::game_management::tricks::{get_lead_suit_from_trick, validate_follow_suit_rule};
+
+/// Tricks a card of an established long suit is worth
+const LONG_SUIT_VALUE: f64 = 0.5;
+
+/// Tricks a low trump is worth for each card short in a side suit
+const RUFF_VALUE: f64 = 0.7;
+
+/// How much a side-suit winner loses each time its suit has gone round
+const RUFF_RISK_PER_ROUND: f64 = 0.3;
+
+/// Estimate how many tricks a hand will take with a given trump
+///
+/// This function is PURE - it scores each card by the chance that no higher
+/// card of its suit was dealt to an opponent, discounts side-suit winners for
+/// the risk of being ruffed, and credits long trumps, ruffing chances and,
+/// without trumps, long suits headed by a top card.
+pub fn estimate_tricks(hand: &[Card], trump: Trump, cards_dealt: i32, player_count: usize) -> f64 {
+    let unseen = DECK_SIZE.saturating_sub(hand.len()) as f64;
+    let opponent_cards = (cards_dealt as f64 * (player_count - 1) as f64).min(unseen);
+    // Chance that any one card we cannot see was dealt to an opponent
+    let dealt_out = if unseen > 0.0 {
+        opponent_cards / unseen
+    } else {
+        0.0
+    };
+    // Cards of each suit a player holds on average
+    let fair_share = cards_dealt as f64 / Suit::ALL.len() as f64;
+
+    let trump_suit = trump.suit();
+    let mut estimate = 0.0;
+    let mut short_suit_gaps = 0.0;
+
+    for suit in Suit::ALL {
+        let mut held: Vec<Rank> = hand
+            .iter()
+            .filter(|card| card.suit == suit)
+            .map(|card| card.rank)
+            .collect();
+        held.sort_by_key(|rank| Reverse(*rank));
+        let length = held.len() as f64;
+        let is_trump = trump_suit == Some(suit);
+
+        for (times_led, rank) in held.iter().enumerate() {
+            let higher_unseen = Rank::ALL
+                .iter()
+                .filter(|higher| *higher > rank && !held.contains(higher))
+                .count();
+            let unbeaten = (1.0 - dealt_out).powi(higher_unseen as i32);
+            let ruff_safe = if trump_suit.is_some() && !is_trump {
+                (1.0 - RUFF_RISK_PER_ROUND * times_led as f64).max(0.0)
+            } else {
+                1.0
+            };
+            estimate += unbeaten * ruff_safe;
+        }
+
+        if is_trump {
+            // Trumps beyond a fair share win once the opponents' trumps are out
+            estimate += (length - fair_share).max(0.0) * LONG_SUIT_VALUE;
+        } else {
+            // A suit is only short enough to ruff a full card below a fair share
+            short_suit_gaps += (fair_share - 1.0 - length).max(0.0);
+            if trump == Trump::NoTrump && held.first().is_some_and(|top| *top >= Rank::King) {
+                estimate += (length - fair_share - 1.0).max(0.0) * LONG_SUIT_VALUE;
+            }
+        }
+    }
+
+    // Low trumps can ruff the suits we are short in
+    if let Some(suit) = trump_suit {
+        let low_trumps = hand
+            .iter()
+            .filter(|card| card.suit == suit && card.rank < Rank::Queen)
+            .count() as f64;
+        estimate += low_trumps.min(short_suit_gaps) * RUFF_VALUE;
+    }
+
+    estimate.min(hand.len() as f64)
+}
+
+/// Choose the trump a hand takes the most tricks with
+///
+/// This function is PURE - it picks the trump (or no trump) with the highest
+/// trick estimate; ties go to the earlier entry of `Trump::ALL`.
+pub fn choose_trump(hand: &[Card], cards_dealt: i32, player_count: usize) -> Trump {
+    let mut best = Trump::ALL[0];
+    let mut best_estimate = f64::MIN;
+    for trump in Trump::ALL {
+        let estimate = estimate_tricks(hand, trump, cards_dealt, player_count);
+        if estimate > best_estimate {
+            best = trump;
+            best_estimate = estimate;
+        }
+    }
+    best
+}
+
+/// Choose a bid for a hand
+///
+/// This function is PURE - it estimates tricks with the trump this hand would
+/// pick, or averaged over every trump when an earlier bid already beats it and
+/// the trump will be someone else's choice. The bids placed so far give the
+/// seat's distance from the dealer: when they and a fair share for each seat
+/// still to bid ask for more tricks than are dealt, the estimate is scaled
+/// down. The result stays within the round's bid range and off the dealer
+/// hook's forbidden bid.
+pub fn choose_bid(
+    hand: &[Card],
+    bids_placed: &[i32],
+    forbidden_bid: Option<i32>,
+    cards_dealt: i32,
+    rules: &RuleSet,
+) -> i32 {
+    let player_count = rules.player_count;
+    let own_trump = choose_trump(hand, cards_dealt, player_count);
+    let with_own_trump = estimate_tricks(hand, own_trump, cards_dealt, player_count);
+    let with_any_trump = Trump::ALL
+        .iter()
+        .map(|trump| estimate_tricks(hand, *trump, cards_dealt, player_count))
+        .sum::<f64>()
+        / Trump::ALL.len() as f64;
+
+    // Trump goes to the highest bid, ties to the earlier bidder
+    let highest_placed = bids_placed.iter().max().copied().unwrap_or(-1);
+    let mut estimate = if with_own_trump.round() as i32 > highest_placed {
+        with_own_trump
+    } else {
+        with_any_trump
+    };
+
+    let still_to_bid = player_count.saturating_sub(bids_placed.len() + 1);
+    let fair_share = cards_dealt as f64 / player_count as f64;
+    let demand =
+        bids_placed.iter().sum::<i32>() as f64 + still_to_bid as f64 * fair_share + estimate;
+    if demand > cards_dealt as f64 {
+        // Meet the table halfway: the other bids are only estimates too
+        estimate *= 0.5 + 0.5 * cards_dealt as f64 / demand;
+    }
+
+    let bid = (estimate.round() as i32).clamp(rules.min_bid, rules.max_bid_for_round(cards_dealt));
+    avoid_hook_bid(bid, forbidden_bid, cards_dealt, rules)
+}
+
+/// Choose a card to play
+///
+/// This function is PURE - it aims to take exactly the tricks bid. The AI
+/// wants the trick while it is short of its bid, or when the scoring rule pays
+/// more for one more trick; otherwise it tries to lose it. Wanting a trick, it
+/// wins as cheaply as it can when last to play and as safely as it can
+/// otherwise, and leads its strongest card. Avoiding one, it sheds the
+/// highest card that still loses and leads its weakest card. Only cards
+/// allowed by the follow-suit rule are considered.
+pub fn choose_card(
+    hand: &[Card],
+    trick: &[(Card, Uuid)],
+    trump: Trump,
+    bid: i32,
+    tricks_won: i32,
+    rules: &RuleSet,
+) -> Card {
+    let lead_suit = get_lead_suit_from_trick(trick);
+    let mut legal: Vec<Card> = hand
+        .iter()
+        .copied()
+        .filter(|card| lead_suit.is_none_or(|suit| validate_follow_suit_rule(card, suit, hand)))
+        .collect();
+    // Weakest first: plain cards before trumps, then by rank
+    legal.sort_by_key(|card| (is_trump_card(card, trump), card.rank));
+
+    let scoring = rules.scoring_rule();
+    let wants_trick = tricks_won < bid
+        || scoring.round_points(tricks_won + 1, bid) > scoring.round_points(tricks_won, bid);
+    let last_to_play = trick.len() + 1 == rules.player_count;
+
+    let (winners, losers): (Vec<Card>, Vec<Card>) = legal
+        .iter()
+        .partition(|card| wins_trick_so_far(**card, trick, trump));
+
+    let choice = match (trick.is_empty(), wants_trick) {
+        // Lead the strongest card, or the weakest to give the lead away
+        (true, true) => legal.iter().max_by_key(|card| card.rank).copied(),
+        (true, false) => legal.first().copied(),
+        (false, true) if last_to_play => winners.first().or(legal.first()).copied(),
+        (false, true) => winners.last().or(legal.first()).copied(),
+        (false, false) => match losers.last() {
+            Some(card) => Some(*card),
+            // Forced to win: last to play sheds the biggest card, otherwise
+            // leave room for a later player to overtake
+            None if last_to_play => winners.last().copied(),
+            None => winners.first().copied(),
+        },
+    };
+
+    choice.unwrap_or(hand[0])
+}
+
+/// Bids from hand strength and plays to make its bid exactly
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicStrategy;
+
+impl Strategy for HeuristicStrategy {
+    fn choose_bid(&self, view: &SeatView, _rng: &mut dyn RngCore) -> i32 {
+        choose_bid(
+            &view.hand,
+            &view.bids_in_order(),
+            view.forbidden_bid(),
+            view.cards_dealt,
+            &view.rules,
+        )
+    }
+
+    fn choose_trump(&self, view: &SeatView, _rng: &mut dyn RngCore) -> Trump {
+        choose_trump(&view.hand, view.cards_dealt, view.player_count())
+    }
+
+    fn choose_card(&self, view: &SeatView, _rng: &mut dyn RngCore) -> Card {
+        choose_card(
+            &view.hand,
+            view.current_trick(),
+            view.trump.unwrap_or(Trump::NoTrump),
+            view.bid().unwrap_or_default(),
+            view.tricks_won(view.seat),
+            &view.rules,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_management::engine::{Action, Event, GameEngine, Phase};
+    use crate::game_management::rules::{MAX_PLAYERS, MIN_PLAYERS};
+
+    fn cards(notation: &[&str]) -> Vec<Card> {
+        notation.iter().map(|card| card.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_estimate_tricks() {
+        // With every card dealt, only certain winners count
+        let hand = cards(&[
+            "AS", "KS", "AH", "KH", "AD", "KD", "AC", "KC", "2S", "2H", "2D", "2C", "3S",
+        ]);
+        let estimate = estimate_tricks(&hand, Trump::NoTrump, 13, 4);
+        assert!((8.0..=13.0).contains(&estimate));
+
+        let weak = cards(&[
+            "2S", "3S", "4S", "2H", "3H", "4H", "2D", "3D", "4D", "2C", "3C", "4C", "5C",
+        ]);
+        assert!(estimate_tricks(&weak, Trump::NoTrump, 13, 4) < 0.5);
+
+        // Never more tricks than cards held
+        for trump in Trump::ALL {
+            assert!(estimate_tricks(&hand, trump, 13, 4) <= 13.0);
+            assert!(estimate_tricks(&cards(&["AS"]), trump, 1, 7) <= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_long_trumps_add_tricks() {
+        let hand = cards(&["AS", "KS", "9S", "7S", "5S", "3S", "4H", "6D"]);
+        let in_spades = estimate_tricks(&hand, Trump::Suit(Suit::Spades), 8, 4);
+        let in_hearts = estimate_tricks(&hand, Trump::Suit(Suit::Hearts), 8, 4);
+        assert!(in_spades > in_hearts + 1.0);
+    }
+
+    #[test]
+    fn test_choose_trump() {
+        let hand = cards(&["AS", "KS", "9S", "7S", "5S", "3S", "4H", "6D"]);
+        assert_eq!(choose_trump(&hand, 8, 4), Trump::Suit(Suit::Spades));
+
+        let hand = cards(&["2H", "3H", "QD", "8D", "JC", "JH", "AC", "TC", "9C", "8C"]);
+        assert_eq!(choose_trump(&hand, 10, 4), Trump::Suit(Suit::Clubs));
+
+        // Balanced top cards and no long suit prefer no trump
+        let hand = cards(&["AS", "AH", "AD", "AC", "KS", "KH", "KD", "KC"]);
+        assert_eq!(choose_trump(&hand, 8, 4), Trump::NoTrump);
+    }
+
+    #[test]
+    fn test_choose_bid_follows_hand_strength() {
+        let rules = RuleSet::standard();
+        let strong = cards(&[
+            "AS", "KS", "QS", "JS", "TS", "AH", "KH", "AD", "KD", "AC", "KC", "QH", "QD",
+        ]);
+        let weak = cards(&[
+            "2S", "3S", "4S", "2H", "3H", "4H", "2D", "3D", "4D", "2C", "3C", "4C", "5C",
+        ]);
+        assert!(choose_bid(&strong, &[], None, 13, &rules) >= 9);
+        assert_eq!(choose_bid(&weak, &[], None, 13, &rules), 0);
+    }
+
+    #[test]
+    fn test_choose_bid_scales_down_against_high_bids() {
+        let rules = RuleSet::standard();
+        let hand = cards(&["AS", "KS", "7S", "AH", "4H", "9D", "3C"]);
+        let opening = choose_bid(&hand, &[], None, 7, &rules);
+        let after_high_bids = choose_bid(&hand, &[4, 3], None, 7, &rules);
+        assert!(after_high_bids <= opening);
+    }
+
+    #[test]
+    fn test_choose_bid_stays_legal() {
+        let mut rules = RuleSet::standard();
+        rules.dealer_hook = true;
+        let hand = cards(&["AS", "KS"]);
+        for bids in [vec![], vec![0], vec![0, 0], vec![0, 0, 0], vec![2, 2, 2]] {
+            for forbidden in [None, Some(0), Some(1), Some(2)] {
+                let bid = choose_bid(&hand, &bids, forbidden, 2, &rules);
+                assert!(rules.is_valid_bid_for_round(bid, 2));
+                assert_ne!(Some(bid), forbidden);
+            }
+        }
+    }
+
+    fn trick(notation: &[&str]) -> Vec<(Card, Uuid)> {
+        cards(notation)
+            .into_iter()
+            .map(|card| (card, Uuid::new_v4()))
+            .collect()
+    }
+
+    #[test]
+    fn test_choose_card_follows_suit() {
+        let rules = RuleSet::standard();
+        let hand = cards(&["AS", "2H", "KH", "3C"]);
+        for (bid, won) in [(0, 0), (2, 0), (1, 1)] {
+            let card = choose_card(&hand, &trick(&["9H"]), Trump::NoTrump, bid, won, &rules);
+            assert_eq!(card.suit, Suit::Hearts);
+        }
+    }
+
+    #[test]
+    fn test_choose_card_wins_cheaply_when_short_of_bid() {
+        let rules = RuleSet::standard();
+        let hand = cards(&["AH", "QH", "2H", "3C"]);
+        let spades = Trump::Suit(Suit::Spades);
+
+        // Last to play takes the trick with the lowest winner
+        let last = trick(&["9H", "JH", "4H"]);
+        assert_eq!(
+            choose_card(&hand, &last, spades, 2, 0, &rules),
+            cards(&["QH"])[0]
+        );
+
+        // With players still to come, play the strongest winner
+        let second = trick(&["9H"]);
+        assert_eq!(
+            choose_card(&hand, &second, spades, 2, 0, &rules),
+            cards(&["AH"])[0]
+        );
+
+        // Cannot win: throw the weakest card
+        let trumped = trick(&["9H", "2S"]);
+        assert_eq!(
+            choose_card(&hand, &trumped, spades, 2, 0, &rules),
+            cards(&["2H"])[0]
+        );
+    }
+
+    #[test]
+    fn test_choose_card_ducks_once_bid_is_made() {
+        let rules = RuleSet::standard();
+        let hand = cards(&["AH", "QH", "2H", "3C"]);
+        let spades = Trump::Suit(Suit::Spades);
+
+        // Shed the highest card that still loses
+        let led = trick(&["KH"]);
+        assert_eq!(
+            choose_card(&hand, &led, spades, 1, 1, &rules),
+            cards(&["QH"])[0]
+        );
+
+        // Lead the weakest card
+        assert_eq!(
+            choose_card(&hand, &[], spades, 1, 1, &rules),
+            cards(&["2H"])[0]
+        );
+
+        // Short of the bid, lead the strongest
+        assert_eq!(
+            choose_card(&hand, &[], spades, 2, 1, &rules),
+            cards(&["AH"])[0]
+        );
+    }
+
+    #[test]
+    fn test_choose_card_follows_scoring_rule_past_the_bid() {
+        // Standard scoring still pays a point per trick once the bid is lost
+        let rules = RuleSet::standard();
+        let hand = cards(&["AH", "2H"]);
+        let led = trick(&["KH"]);
+        assert_eq!(
+            choose_card(&hand, &led, Trump::NoTrump, 1, 2, &rules),
+            cards(&["AH"])[0]
+        );
+    }
+
+    #[test]
+    fn test_ai_plays_full_games_legally() {
+        let mut exact = 0;
+        let mut bids = 0;
+        for player_count in MIN_PLAYERS..=MAX_PLAYERS {
+            let mut rules = RuleSet::for_players(player_count).unwrap();
+            rules.dealer_hook = player_count % 2 == 0;
+            let player_ids: Vec<Uuid> = (0..player_count).map(|_| Uuid::new_v4()).collect();
+            let mut engine =
+                GameEngine::new(rules.clone(), &player_ids, player_count as i64).unwrap();
+            for player_id in &player_ids {
+                engine
+                    .apply(Action::Ready {
+                        player_id: *player_id,
+                    })
+                    .unwrap();
+            }
+
+            while engine.phase != Phase::Completed {
+                let seat = engine.current_turn().unwrap();
+                let player_id = player_ids[seat];
+                let round = engine.round.as_ref().unwrap();
+                let hand = &round.hands[seat];
+                let action = match engine.phase {
+                    Phase::Bidding => Action::Bid {
+                        player_id,
+                        bid: choose_bid(
+                            hand,
+                            &round.bids_in_order(),
+                            engine.forbidden_bid(),
+                            round.cards_dealt,
+                            &rules,
+                        ),
+                    },
+                    Phase::TrumpSelection => Action::ChooseTrump {
+                        player_id,
+                        trump: choose_trump(hand, round.cards_dealt, player_count),
+                    },
+                    _ => Action::Play {
+                        player_id,
+                        card: choose_card(
+                            hand,
+                            round.open_trick().map_or(&[], |trick| &trick.plays),
+                            round.trump.unwrap(),
+                            round.bids[seat].unwrap(),
+                            round.tricks_won(player_id),
+                            &rules,
+                        ),
+                    },
+                };
+
+                for event in engine.apply(action).unwrap() {
+                    if let Event::RoundScored { results, .. } = event {
+                        bids += results.len();
+                        exact += results.iter().filter(|r| r.bid == r.tricks_won).count();
+                    }
+                }
+            }
+        }
+
+        // Bidding from the hand and playing to the bid should hit it often
+        assert!(exact * 3 > bids, "{exact} exact bids out of {bids}");
+    }
+}
@@ -0,0 +1,624 @@
+//! Monte Carlo AI
+//!
+//! The expert built-in level: an information-set Monte Carlo tree search
+//! (ISMCTS). For every iteration the cards it cannot see are dealt out again
+//! at random, consistent with what the seat has seen: the cards already
+//! played, how many cards each seat still holds, and the suits a seat has
+//! shown void in by failing to follow. Each guess is played out to the end of
+//! the round with the pure trick rules and scored with the game's scoring
+//! rule, and the bid, trump or card with the best average score is chosen.
+
+use std::env;
+use std::time::{Duration, Instant};
+
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use uuid::Uuid;
+
+use super::{heuristic, SeatView, Strategy};
+use crate::game_management::bidding::{find_highest_bidder, forbidden_hook_bid};
+use crate::game_management::cards::{Card, Suit, Trump};
+use crate::game_management::scoring::EXACT_BID_BONUS;
+use crate::game_management::tricks::{
+    determine_trick_winner, get_lead_suit_from_trick, validate_follow_suit_rule,
+};
+
+/// Play-outs per decision when `AI_EXPERT_ITERATIONS` is not set
+const DEFAULT_ITERATIONS: u32 = 2000;
+
+/// Thinking time per decision when `AI_EXPERT_TIME_MS` is not set
+const DEFAULT_TIME_MS: u64 = 500;
+
+/// Weight of trying less-visited cards against replaying the best so far
+const EXPLORATION: f64 = 0.7;
+
+/// Attempts at dealing around known voids before the voids are ignored
+const DEAL_ATTEMPTS: usize = 20;
+
+/// How much search the expert AI may spend on one decision
+///
+/// The search stops at whichever limit is reached first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchBudget {
+    /// Most play-outs to run
+    pub iterations: u32,
+    /// Longest time to search for
+    pub time: Duration,
+}
+
+impl Default for SearchBudget {
+    fn default() -> Self {
+        SearchBudget {
+            iterations: DEFAULT_ITERATIONS,
+            time: Duration::from_millis(DEFAULT_TIME_MS),
+        }
+    }
+}
+
+impl SearchBudget {
+    /// Budget configured with `AI_EXPERT_ITERATIONS` and `AI_EXPERT_TIME_MS`
+    pub fn from_env() -> Self {
+        let iterations = env::var("AI_EXPERT_ITERATIONS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_ITERATIONS);
+        let millis = env::var("AI_EXPERT_TIME_MS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_TIME_MS);
+        SearchBudget {
+            iterations,
+            time: Duration::from_millis(millis),
+        }
+    }
+}
+
+/// Searches guesses at the hidden hands for the move with the best score
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IsmctsStrategy {
+    pub budget: SearchBudget,
+}
+
+impl IsmctsStrategy {
+    pub fn new(budget: SearchBudget) -> Self {
+        IsmctsStrategy { budget }
+    }
+}
+
+impl Strategy for IsmctsStrategy {
+    fn choose_bid(&self, view: &SeatView, rng: &mut dyn RngCore) -> i32 {
+        best_by_sampling(&view.legal_bids(), self.budget, rng, |bid, rng| {
+            play_out_bid(view, bid, rng)
+        })
+        .unwrap_or(view.rules.min_bid)
+    }
+
+    fn choose_trump(&self, view: &SeatView, rng: &mut dyn RngCore) -> Trump {
+        best_by_sampling(&Trump::ALL, self.budget, rng, |trump, rng| {
+            play_out_trump(view, trump, rng)
+        })
+        .unwrap_or(Trump::NoTrump)
+    }
+
+    fn choose_card(&self, view: &SeatView, rng: &mut dyn RngCore) -> Card {
+        search_card(view, self.budget, rng)
+    }
+}
+
+/// Suits each seat has shown void in this round
+///
+/// This function is PURE - a seat that played off the suit led to any trick
+/// holds no more cards of that suit.
+fn void_suits(view: &SeatView) -> Vec<Vec<Suit>> {
+    let mut voids = vec![Vec::new(); view.player_count()];
+    for trick in &view.tricks {
+        let Some(lead_suit) = get_lead_suit_from_trick(&trick.plays) else {
+            continue;
+        };
+        for (card, player_id) in &trick.plays[1..] {
+            let seat = view.players.iter().position(|id| id == player_id);
+            if let Some(seat) = seat.filter(|_| card.suit != lead_suit) {
+                if !voids[seat].contains(&lead_suit) {
+                    voids[seat].push(lead_suit);
+                }
+            }
+        }
+    }
+    voids
+}
+
+/// Deal the cards this seat cannot see to the other seats at random
+///
+/// Each seat gets as many cards as it still holds, never in a suit it has
+/// shown void in; cards left over were not dealt this round. If the voids
+/// cannot be honoured after a few attempts they are ignored.
+fn deal_unseen(view: &SeatView, rng: &mut dyn RngCore) -> Vec<Vec<Card>> {
+    let played: Vec<Card> = view
+        .tricks
+        .iter()
+        .flat_map(|trick| trick.plays.iter().map(|(card, _)| *card))
+        .collect();
+    let mut unseen: Vec<Card> = Card::full_deck()
+        .into_iter()
+        .filter(|card| !view.hand.contains(card) && !played.contains(card))
+        .collect();
+
+    let hand_sizes: Vec<usize> = (0..view.player_count())
+        .map(|seat| {
+            let plays = view
+                .tricks
+                .iter()
+                .flat_map(|trick| &trick.plays)
+                .filter(|(_, player_id)| *player_id == view.players[seat])
+                .count() as i32;
+            (view.cards_dealt - plays).max(0) as usize
+        })
+        .collect();
+    let voids = void_suits(view);
+
+    // Fill the seats with the fewest suits open to them first
+    let mut order: Vec<usize> = (0..view.player_count())
+        .filter(|seat| *seat != view.seat)
+        .collect();
+    order.sort_by_key(|seat| std::cmp::Reverse(voids[*seat].len()));
+
+    for attempt in 1..=DEAL_ATTEMPTS {
+        let respect_voids = attempt < DEAL_ATTEMPTS;
+        unseen.shuffle(rng);
+        let mut pool = unseen.clone();
+        let mut hands = vec![Vec::new(); view.player_count()];
+        hands[view.seat] = view.hand.clone();
+
+        let dealt = order.iter().all(|seat| {
+            while hands[*seat].len() < hand_sizes[*seat] {
+                let fits = pool
+                    .iter()
+                    .position(|card| !respect_voids || !voids[*seat].contains(&card.suit));
+                match fits {
+                    Some(index) => hands[*seat].push(pool.swap_remove(index)),
+                    None => return false,
+                }
+            }
+            true
+        });
+        if dealt {
+            return hands;
+        }
+    }
+
+    // Not reached: the last attempt ignores voids, and there are always
+    // enough unseen cards for the hands still held
+    vec![Vec::new(); view.player_count()]
+}
+
+/// The rest of a round played out on one guess at the hidden hands
+#[derive(Debug, Clone)]
+struct Playout<'a> {
+    view: &'a SeatView,
+    hands: Vec<Vec<Card>>,
+    bids: Vec<Option<i32>>,
+    trump: Trump,
+    /// Plays so far in the trick being played
+    trick: Vec<(Card, Uuid)>,
+    /// Seat leading the trick being played
+    leader: usize,
+    tricks_won: Vec<i32>,
+}
+
+impl<'a> Playout<'a> {
+    /// Continue the round as the view sees it, with the hidden hands guessed
+    fn from_view(view: &'a SeatView, hands: Vec<Vec<Card>>, trump: Trump) -> Self {
+        let player_count = view.player_count();
+        let leader = match view.tricks.last() {
+            Some(trick) if trick.winner.is_none() => trick.leader,
+            Some(trick) => view
+                .players
+                .iter()
+                .position(|id| Some(*id) == trick.winner)
+                .unwrap_or_default(),
+            None => (view.dealer + 1) % player_count,
+        };
+
+        Playout {
+            view,
+            hands,
+            bids: view.bids.clone(),
+            trump,
+            trick: view.current_trick().to_vec(),
+            leader,
+            tricks_won: (0..player_count)
+                .map(|seat| view.tricks_won(seat))
+                .collect(),
+        }
+    }
+
+    fn next_seat(&self) -> usize {
+        (self.leader + self.trick.len()) % self.view.player_count()
+    }
+
+    fn is_over(&self) -> bool {
+        self.hands.iter().all(Vec::is_empty)
+    }
+
+    fn legal_cards(&self, seat: usize) -> Vec<Card> {
+        let hand = &self.hands[seat];
+        match get_lead_suit_from_trick(&self.trick) {
+            Some(lead_suit) => hand
+                .iter()
+                .filter(|card| validate_follow_suit_rule(card, lead_suit, hand))
+                .copied()
+                .collect(),
+            None => hand.clone(),
+        }
+    }
+
+    /// Play a card for the seat due, settling the trick once everyone has played
+    fn play(&mut self, card: Card) {
+        let seat = self.next_seat();
+        self.hands[seat].retain(|held| *held != card);
+        self.trick.push((card, self.view.players[seat]));
+
+        if self.trick.len() == self.view.player_count() {
+            let winner = determine_trick_winner(&self.trick, self.trump)
+                .ok()
+                .and_then(|winner_id| self.view.players.iter().position(|id| *id == winner_id))
+                .unwrap_or(self.leader);
+            self.tricks_won[winner] += 1;
+            self.leader = winner;
+            self.trick.clear();
+        }
+    }
+
+    /// Finish the round, every seat playing as the heuristic AI would
+    fn play_to_end(&mut self) {
+        while !self.is_over() {
+            let seat = self.next_seat();
+            let card = heuristic::choose_card(
+                &self.hands[seat],
+                &self.trick,
+                self.trump,
+                self.bids[seat].unwrap_or_default(),
+                self.tricks_won[seat],
+                &self.view.rules,
+            );
+            self.play(card);
+        }
+    }
+
+    /// Points a seat scores for the round as played out
+    fn points(&self, seat: usize) -> f64 {
+        let bid = self.bids[seat].unwrap_or_default();
+        self.view
+            .rules
+            .scoring_rule()
+            .round_points(self.tricks_won[seat], bid) as f64
+    }
+}
+
+/// Run play-outs for each candidate in turn and keep the best on average
+///
+/// Returns None when there are no candidates. Ties go to the earlier
+/// candidate.
+fn best_by_sampling<T: Copy>(
+    candidates: &[T],
+    budget: SearchBudget,
+    rng: &mut dyn RngCore,
+    mut play_out: impl FnMut(T, &mut dyn RngCore) -> f64,
+) -> Option<T> {
+    if candidates.len() <= 1 {
+        return candidates.first().copied();
+    }
+
+    let started = Instant::now();
+    let mut totals = vec![0.0; candidates.len()];
+    let mut runs = vec![0u32; candidates.len()];
+    for iteration in 0..budget.iterations {
+        if started.elapsed() >= budget.time {
+            break;
+        }
+        let index = iteration as usize % candidates.len();
+        totals[index] += play_out(candidates[index], rng);
+        runs[index] += 1;
+    }
+
+    let average = |index: usize| match runs[index] {
+        0 => f64::MIN,
+        runs => totals[index] / runs as f64,
+    };
+    (0..candidates.len())
+        .rev()
+        .max_by(|a, b| average(*a).total_cmp(&average(*b)))
+        .map(|index| candidates[index])
+}
+
+/// Score a bid on one guess at the hidden hands
+///
+/// The seats still to bid and the trump chooser act as the heuristic AI
+/// would with the hands guessed for them.
+fn play_out_bid(view: &SeatView, bid: i32, rng: &mut dyn RngCore) -> f64 {
+    let hands = deal_unseen(view, rng);
+    let player_count = view.player_count();
+    let first_to_bid = (view.dealer + 1) % player_count;
+
+    let mut bids = view.bids.clone();
+    bids[view.seat] = Some(bid);
+    let mut in_order = Vec::new();
+    for offset in 0..player_count {
+        let seat = (first_to_bid + offset) % player_count;
+        let bid = match bids[seat] {
+            Some(bid) => bid,
+            None => {
+                let placed: Vec<i32> = in_order.iter().map(|(bid, _)| *bid).collect();
+                let forbidden = forbidden_hook_bid(&view.rules, view.cards_dealt, &placed);
+                heuristic::choose_bid(
+                    &hands[seat],
+                    &placed,
+                    forbidden,
+                    view.cards_dealt,
+                    &view.rules,
+                )
+            }
+        };
+        bids[seat] = Some(bid);
+        in_order.push((bid, view.players[seat]));
+    }
+
+    let (_, highest_bidder_id, _) = find_highest_bidder(&in_order);
+    let chooser = view
+        .players
+        .iter()
+        .position(|id| Some(*id) == highest_bidder_id)
+        .unwrap_or(view.seat);
+    let trump = heuristic::choose_trump(&hands[chooser], view.cards_dealt, player_count);
+
+    let mut playout = Playout::from_view(view, hands, trump);
+    playout.bids = bids;
+    playout.play_to_end();
+    playout.points(view.seat)
+}
+
+/// Score a trump on one guess at the hidden hands
+fn play_out_trump(view: &SeatView, trump: Trump, rng: &mut dyn RngCore) -> f64 {
+    let mut playout = Playout::from_view(view, deal_unseen(view, rng), trump);
+    playout.play_to_end();
+    playout.points(view.seat)
+}
+
+/// A card played at one point of the search tree
+#[derive(Debug, Clone)]
+struct Node {
+    card: Option<Card>,
+    /// Seat that played the card, whose score the node collects
+    seat: usize,
+    visits: f64,
+    total: f64,
+    /// Times the card was legal when the parent was visited
+    available: f64,
+    children: Vec<usize>,
+}
+
+impl Node {
+    fn upper_bound(&self) -> f64 {
+        self.total / self.visits + EXPLORATION * (self.available.ln() / self.visits).sqrt()
+    }
+}
+
+/// Choose a card by searching a tree of plays over many guessed deals
+///
+/// Every iteration guesses the hidden hands, walks down the tree through the
+/// cards legal in that guess, adds one new card, plays the rest of the round
+/// out heuristically and credits each seat on the path with its score. The
+/// card tried most often from the root is played.
+fn search_card(view: &SeatView, budget: SearchBudget, rng: &mut dyn RngCore) -> Card {
+    let legal = view.legal_cards();
+    if legal.len() <= 1 {
+        return legal.first().copied().unwrap_or(view.hand[0]);
+    }
+    let trump = view.trump.unwrap_or(Trump::NoTrump);
+    // Keep scores near the scale of the exploration weight
+    let scale = (view.cards_dealt + EXACT_BID_BONUS) as f64;
+
+    let started = Instant::now();
+    let mut tree = vec![Node {
+        card: None,
+        seat: view.seat,
+        visits: 0.0,
+        total: 0.0,
+        available: 0.0,
+        children: Vec::new(),
+    }];
+    for _ in 0..budget.iterations {
+        if started.elapsed() >= budget.time {
+            break;
+        }
+        let mut playout = Playout::from_view(view, deal_unseen(view, rng), trump);
+        let mut path = vec![0];
+        let mut node = 0;
+
+        while !playout.is_over() {
+            let seat = playout.next_seat();
+            let legal = playout.legal_cards(seat);
+            let children: Vec<usize> = tree[node]
+                .children
+                .iter()
+                .copied()
+                .filter(|child| tree[*child].card.is_some_and(|card| legal.contains(&card)))
+                .collect();
+            for child in &children {
+                tree[*child].available += 1.0;
+            }
+
+            let untried: Vec<Card> = legal
+                .iter()
+                .copied()
+                .filter(|card| {
+                    !children
+                        .iter()
+                        .any(|child| tree[*child].card == Some(*card))
+                })
+                .collect();
+            if let Some(card) = untried.choose(rng) {
+                let child = tree.len();
+                tree.push(Node {
+                    card: Some(*card),
+                    seat,
+                    visits: 0.0,
+                    total: 0.0,
+                    available: 1.0,
+                    children: Vec::new(),
+                });
+                tree[node].children.push(child);
+                playout.play(*card);
+                path.push(child);
+                break;
+            }
+
+            let Some(next) = children
+                .iter()
+                .copied()
+                .max_by(|a, b| tree[*a].upper_bound().total_cmp(&tree[*b].upper_bound()))
+            else {
+                break;
+            };
+            if let Some(card) = tree[next].card {
+                playout.play(card);
+            }
+            path.push(next);
+            node = next;
+        }
+
+        playout.play_to_end();
+        for index in path {
+            let node = &mut tree[index];
+            node.visits += 1.0;
+            node.total += playout.points(node.seat) / scale;
+        }
+    }
+
+    tree[0]
+        .children
+        .iter()
+        .map(|child| &tree[*child])
+        .max_by(|a, b| a.visits.total_cmp(&b.visits))
+        .and_then(|node| node.card)
+        .unwrap_or(legal[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_management::engine::Trick;
+    use crate::game_management::rules::RuleSet;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn cards(notation: &[&str]) -> Vec<Card> {
+        notation.iter().map(|card| card.parse().unwrap()).collect()
+    }
+
+    fn budget() -> SearchBudget {
+        SearchBudget {
+            iterations: 300,
+            time: Duration::from_secs(10),
+        }
+    }
+
+    /// Seat 0 of a table of `player_count`, with seat 0 as dealer
+    fn view(player_count: usize, cards_dealt: i32, hand: &[&str]) -> SeatView {
+        SeatView {
+            rules: RuleSet::for_players(player_count).unwrap(),
+            seat: 0,
+            players: (0..player_count).map(|_| Uuid::new_v4()).collect(),
+            round_number: 1,
+            dealer: 0,
+            cards_dealt,
+            hand: cards(hand),
+            bids: vec![None; player_count],
+            trump: None,
+            tricks: Vec::new(),
+            scores: vec![0; player_count],
+        }
+    }
+
+    #[test]
+    fn test_deal_unseen_respects_what_was_seen() {
+        let mut view = view(3, 3, &["AS", "KS"]);
+        let players = view.players.clone();
+        // Seat 1 led a heart, seat 2 could not follow and seat 0 won
+        view.tricks = vec![Trick {
+            trick_number: 1,
+            leader: 1,
+            plays: vec![
+                (cards(&["5H"])[0], players[1]),
+                (cards(&["2C"])[0], players[2]),
+                (cards(&["AH"])[0], players[0]),
+            ],
+            winner: Some(players[0]),
+        }];
+        assert_eq!(void_suits(&view), [vec![], vec![], vec![Suit::Hearts]]);
+
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..50 {
+            let hands = deal_unseen(&view, &mut rng);
+            assert_eq!(hands[0], view.hand);
+            assert_eq!(hands[1].len(), 2);
+            assert_eq!(hands[2].len(), 2);
+            for card in hands[1].iter().chain(&hands[2]) {
+                assert!(!cards(&["AS", "KS", "5H", "2C", "AH"]).contains(card));
+            }
+            assert!(hands[2].iter().all(|card| card.suit != Suit::Hearts));
+        }
+    }
+
+    #[test]
+    fn test_expert_bids_a_sure_thing() {
+        // Bidding first with the four top spades, the seat wins the bid,
+        // names spades and takes every trick
+        let mut view = view(4, 4, &["AS", "KS", "QS", "JS"]);
+        view.dealer = 3;
+        let mut rng = StdRng::seed_from_u64(1);
+        let expert = IsmctsStrategy::new(budget());
+        assert_eq!(expert.choose_bid(&view, &mut rng), 4);
+
+        view.bids = vec![Some(4), Some(0), Some(0), Some(0)];
+        assert_eq!(
+            expert.choose_trump(&view, &mut rng),
+            Trump::Suit(Suit::Spades)
+        );
+    }
+
+    #[test]
+    fn test_expert_ducks_once_bid_is_made() {
+        // Bid nothing and last to play: the ace would take an unwanted trick
+        let mut view = view(3, 2, &["AH", "2H"]);
+        let players = view.players.clone();
+        view.bids = vec![Some(0), Some(1), Some(1)];
+        view.trump = Some(Trump::Suit(Suit::Spades));
+        view.tricks = vec![Trick {
+            trick_number: 1,
+            leader: 1,
+            plays: vec![
+                (cards(&["5H"])[0], players[1]),
+                (cards(&["3H"])[0], players[2]),
+            ],
+            winner: None,
+        }];
+
+        let mut rng = StdRng::seed_from_u64(2);
+        let expert = IsmctsStrategy::new(budget());
+        assert_eq!(expert.choose_card(&view, &mut rng), cards(&["2H"])[0]);
+    }
+
+    #[test]
+    fn test_search_stops_at_the_time_limit() {
+        let view = view(4, 7, &["AS", "KS", "QS", "JS", "TS", "9S", "8S"]);
+        let expert = IsmctsStrategy::new(SearchBudget {
+            iterations: u32::MAX,
+            time: Duration::from_millis(50),
+        });
+        let mut rng = StdRng::seed_from_u64(3);
+        let started = Instant::now();
+        expert.choose_bid(&view, &mut rng);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,304 @@
+//! AI module
+//!
+//! AI seats make their decisions through a `Strategy`. A strategy only ever
+//! sees a `SeatView` of the round: its own hand and what the whole table can
+//! see. The built-in strategies are offered as difficulty levels, and each AI
+//! seat's level is recorded on its `game_players` row. Everything here is
+//! pure, leaving validation and persistence to the engine.
+
+pub mod greedy;
+pub mod heuristic;
+pub mod ismcts;
+pub mod random;
+
+use std::sync::OnceLock;
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::game_management::bidding::forbidden_hook_bid;
+use crate::game_management::cards::{Card, Trump};
+use crate::game_management::engine::{GameEngine, Trick};
+use crate::game_management::rules::RuleSet;
+use crate::game_management::tricks::{
+    determine_trick_winner, get_lead_suit_from_trick, validate_follow_suit_rule,
+};
+
+pub use greedy::GreedyStrategy;
+pub use heuristic::HeuristicStrategy;
+pub use ismcts::{IsmctsStrategy, SearchBudget};
+pub use random::RandomStrategy;
+
+/// What one seat can see of the current round
+///
+/// Holds the seat's own hand and public information only: the other hands
+/// and the deal seed are never copied in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeatView {
+    pub rules: RuleSet,
+    /// The seat that has to decide
+    pub seat: usize,
+    /// Player id of each seat
+    pub players: Vec<Uuid>,
+    pub round_number: i32,
+    /// Seat of the dealer
+    pub dealer: usize,
+    pub cards_dealt: i32,
+    /// Cards this seat still holds
+    pub hand: Vec<Card>,
+    /// Bids placed so far, by seat
+    pub bids: Vec<Option<i32>>,
+    pub trump: Option<Trump>,
+    /// Tricks played so far; the last one is open until it has a winner
+    pub tricks: Vec<Trick>,
+    /// Game scores from the rounds already scored, by seat
+    pub scores: Vec<i32>,
+}
+
+impl SeatView {
+    /// Redact a game to what one seat can see
+    ///
+    /// Returns None before the first deal or for a seat not at the table.
+    pub fn new(engine: &GameEngine, seat: usize) -> Option<Self> {
+        let round = engine.round.as_ref()?;
+        let hand = round.hands.get(seat)?.clone();
+
+        Some(SeatView {
+            rules: engine.rules.clone(),
+            seat,
+            players: engine.seats.iter().map(|seat| seat.player_id).collect(),
+            round_number: round.round_number,
+            dealer: round.dealer,
+            cards_dealt: round.cards_dealt,
+            hand,
+            bids: round.bids.clone(),
+            trump: round.trump,
+            tricks: round.tricks.clone(),
+            scores: engine.scores.clone(),
+        })
+    }
+
+    /// Player id of the seat deciding
+    pub fn player_id(&self) -> Uuid {
+        self.players[self.seat]
+    }
+
+    pub fn player_count(&self) -> usize {
+        self.players.len()
+    }
+
+    /// The bid this seat placed, once it has bid
+    pub fn bid(&self) -> Option<i32> {
+        self.bids[self.seat]
+    }
+
+    /// Bids placed so far, in bidding order
+    pub fn bids_in_order(&self) -> Vec<i32> {
+        let player_count = self.player_count();
+        let first_to_bid = (self.dealer + 1) % player_count;
+        (0..player_count)
+            .filter_map(|offset| self.bids[(first_to_bid + offset) % player_count])
+            .collect()
+    }
+
+    /// Bid the next bidder may not make under the dealer hook rule
+    pub fn forbidden_bid(&self) -> Option<i32> {
+        forbidden_hook_bid(&self.rules, self.cards_dealt, &self.bids_in_order())
+    }
+
+    /// Bids this seat may make
+    pub fn legal_bids(&self) -> Vec<i32> {
+        let forbidden = self.forbidden_bid();
+        (self.rules.min_bid..=self.rules.max_bid_for_round(self.cards_dealt))
+            .filter(|bid| Some(*bid) != forbidden)
+            .collect()
+    }
+
+    /// Plays so far in the trick still being played, as (card, player_id)
+    pub fn current_trick(&self) -> &[(Card, Uuid)] {
+        self.tricks
+            .last()
+            .filter(|trick| trick.winner.is_none())
+            .map_or(&[], |trick| &trick.plays)
+    }
+
+    /// Cards this seat may play into the current trick
+    pub fn legal_cards(&self) -> Vec<Card> {
+        match get_lead_suit_from_trick(self.current_trick()) {
+            Some(lead_suit) => self
+                .hand
+                .iter()
+                .filter(|card| validate_follow_suit_rule(card, lead_suit, &self.hand))
+                .copied()
+                .collect(),
+            None => self.hand.clone(),
+        }
+    }
+
+    /// Tricks won so far this round by a seat
+    pub fn tricks_won(&self, seat: usize) -> i32 {
+        let player_id = self.players[seat];
+        self.tricks
+            .iter()
+            .filter(|trick| trick.winner == Some(player_id))
+            .count() as i32
+    }
+}
+
+/// A way for an AI seat to make its decisions
+///
+/// Every choice must be legal for the view given, though the engine checks it
+/// again. Strategies that rely on chance draw from the `rng` passed in, so a
+/// seeded generator makes their play reproducible.
+pub trait Strategy: Send + Sync {
+    /// Bid to place, one of `view.legal_bids()`
+    fn choose_bid(&self, view: &SeatView, rng: &mut dyn RngCore) -> i32;
+
+    /// Trump for the round, once this seat has won the bidding
+    fn choose_trump(&self, view: &SeatView, rng: &mut dyn RngCore) -> Trump;
+
+    /// Card to play, one of `view.legal_cards()`
+    fn choose_card(&self, view: &SeatView, rng: &mut dyn RngCore) -> Card;
+}
+
+/// The built-in AI levels a seat can be given
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    /// Makes any legal move at random
+    Easy,
+    /// Bids its high cards and grabs every trick it can
+    Medium,
+    /// Bids from hand strength and plays to make its bid exactly
+    #[default]
+    Hard,
+    /// Searches guesses at the hidden hands for the best scoring move
+    Expert,
+}
+
+impl Difficulty {
+    /// Every built-in difficulty, easiest first
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+
+    /// The strategy playing at this level
+    ///
+    /// The expert search budget is read from the environment on first use.
+    pub fn strategy(self) -> &'static dyn Strategy {
+        static EXPERT: OnceLock<IsmctsStrategy> = OnceLock::new();
+        match self {
+            Difficulty::Easy => &RandomStrategy,
+            Difficulty::Medium => &GreedyStrategy,
+            Difficulty::Hard => &HeuristicStrategy,
+            Difficulty::Expert => {
+                EXPERT.get_or_init(|| IsmctsStrategy::new(SearchBudget::from_env()))
+            }
+        }
+    }
+}
+
+/// Check whether a card would win the trick as it stands
+///
+/// This function is PURE - it adds the card to the plays so far and asks the
+/// trick rules who is winning. Leading a card always wins the trick so far.
+fn wins_trick_so_far(card: Card, trick: &[(Card, Uuid)], trump: Trump) -> bool {
+    let mut plays = trick.to_vec();
+    plays.push((card, Uuid::nil()));
+    determine_trick_winner(&plays, trump) == Ok(Uuid::nil())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_management::engine::{Action, Phase};
+    use crate::game_management::rules::{MAX_PLAYERS, MIN_PLAYERS};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::time::Duration;
+
+    #[test]
+    fn test_difficulty_names() {
+        let names: Vec<String> = Difficulty::ALL
+            .iter()
+            .map(|difficulty| serde_json::to_string(difficulty).unwrap())
+            .collect();
+        assert_eq!(names, ["\"easy\"", "\"medium\"", "\"hard\"", "\"expert\""]);
+        assert_eq!(Difficulty::default(), Difficulty::Hard);
+    }
+
+    #[test]
+    fn test_seat_view_before_the_deal() {
+        let player_ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let engine = GameEngine::new(RuleSet::standard(), &player_ids, 1).unwrap();
+        assert_eq!(SeatView::new(&engine, 0), None);
+    }
+
+    #[test]
+    fn test_every_difficulty_plays_full_games_legally() {
+        // The expert on a small budget, so the games finish quickly
+        let expert = IsmctsStrategy::new(SearchBudget {
+            iterations: 10,
+            time: Duration::from_secs(1),
+        });
+        let strategies: [&dyn Strategy; 4] = [
+            &RandomStrategy,
+            &GreedyStrategy,
+            &HeuristicStrategy,
+            &expert,
+        ];
+        let mut rng = StdRng::seed_from_u64(7);
+        for player_count in MIN_PLAYERS..=MAX_PLAYERS {
+            let mut rules = RuleSet::for_players(player_count).unwrap();
+            rules.dealer_hook = player_count % 2 == 1;
+            let player_ids: Vec<Uuid> = (0..player_count).map(|_| Uuid::new_v4()).collect();
+            let mut engine =
+                GameEngine::new(rules.clone(), &player_ids, player_count as i64).unwrap();
+            for player_id in &player_ids {
+                engine
+                    .apply(Action::Ready {
+                        player_id: *player_id,
+                    })
+                    .unwrap();
+            }
+
+            while engine.phase != Phase::Completed {
+                let seat = engine.current_turn().unwrap();
+                let player_id = player_ids[seat];
+                let strategy = strategies[seat % strategies.len()];
+
+                // The view agrees with the engine on what is legal
+                let view = SeatView::new(&engine, seat).unwrap();
+                let round = engine.round.as_ref().unwrap();
+                assert_eq!(view.hand, round.hands[seat]);
+                assert_eq!(view.player_id(), player_id);
+                assert_eq!(view.legal_cards(), engine.legal_cards(seat));
+
+                let action = match engine.phase {
+                    Phase::Bidding => {
+                        assert_eq!(view.forbidden_bid(), engine.forbidden_bid());
+                        let bid = strategy.choose_bid(&view, &mut rng);
+                        assert!(view.legal_bids().contains(&bid));
+                        Action::Bid { player_id, bid }
+                    }
+                    Phase::TrumpSelection => Action::ChooseTrump {
+                        player_id,
+                        trump: strategy.choose_trump(&view, &mut rng),
+                    },
+                    _ => {
+                        assert_eq!(view.tricks_won(seat), round.tricks_won(player_id));
+                        Action::Play {
+                            player_id,
+                            card: strategy.choose_card(&view, &mut rng),
+                        }
+                    }
+                };
+                engine.apply(action).unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Random AI
+//!
+//! The easiest built-in level: every decision is a legal move picked at
+//! random.
+
+use rand::seq::SliceRandom;
+use rand::RngCore;
+
+use super::{SeatView, Strategy};
+use crate::game_management::cards::{Card, Trump};
+
+/// Makes any legal move at random
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomStrategy;
+
+impl Strategy for RandomStrategy {
+    fn choose_bid(&self, view: &SeatView, rng: &mut dyn RngCore) -> i32 {
+        view.legal_bids()
+            .choose(rng)
+            .copied()
+            .unwrap_or(view.rules.min_bid)
+    }
+
+    fn choose_trump(&self, _view: &SeatView, rng: &mut dyn RngCore) -> Trump {
+        Trump::ALL.choose(rng).copied().unwrap_or(Trump::NoTrump)
+    }
+
+    fn choose_card(&self, view: &SeatView, rng: &mut dyn RngCore) -> Card {
+        view.legal_cards()
+            .choose(rng)
+            .copied()
+            .unwrap_or(view.hand[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_management::engine::{Action, GameEngine};
+    use crate::game_management::rules::RuleSet;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_random_choices_repeat_with_the_same_seed() {
+        let player_ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let mut engine = GameEngine::new(RuleSet::standard(), &player_ids, 11).unwrap();
+        for player_id in &player_ids {
+            engine
+                .apply(Action::Ready {
+                    player_id: *player_id,
+                })
+                .unwrap();
+        }
+        let seat = engine.current_turn().unwrap();
+        let view = SeatView::new(&engine, seat).unwrap();
+
+        let choices = |seed: u64| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..20)
+                .map(|_| {
+                    (
+                        RandomStrategy.choose_bid(&view, &mut rng),
+                        RandomStrategy.choose_card(&view, &mut rng),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(choices(3), choices(3));
+
+        // Over many draws, more than one bid comes up
+        let bids: Vec<i32> = choices(3).iter().map(|(bid, _)| *bid).collect();
+        assert!(bids.iter().any(|bid| *bid != bids[0]));
+        assert!(bids.iter().all(|bid| view.legal_bids().contains(bid)));
+    }
+}
@@ -0,0 +1,360 @@
+//! AI turn driver
+//!
+//! After any change to a game, the driver checks whether the seat due to act
+//! belongs to an AI user and, if so, has it act after a short delay, using the
+//! strategy for the seat's difficulty. Seats taken by an external bot ask the
+//! bot instead, and fall back to that strategy when it fails to answer. It keeps
+//! going while AI seats follow one another and stops at the first human turn.
+//! A seat decides from an unlocked read of the game, taken at one point in
+//! time, so a slow bot or search never holds the game's lock. Its action then
+//! locks the game row and is only taken if the game is still at the version it
+//! decided on, so two drivers racing on the same game can never both act for
+//! one turn. A turn refused as stale is looked at again, and the seat is only
+//! asked to decide again once the game has moved past the version it answered
+//! for.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use rand::RngCore;
+use sea_orm::{
+    AccessMode, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, IsolationLevel,
+    QueryFilter, TransactionTrait,
+};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::entity::{bots, game_players, games, users};
+use crate::game_management::ai::{Difficulty, SeatView, Strategy};
+use crate::game_management::bot_protocol::{self, BotAction, Decision, DecisionRequest};
+use crate::game_management::engine::Phase;
+use crate::game_management::{bidding, live, orchestration, perform_ai_card_play};
+
+/// Delay before an AI acts when `AI_TURN_DELAY_MS` is not set
+const DEFAULT_TURN_DELAY_MS: u64 = 800;
+
+/// Games being driven, each with whether it changed while the driver looked
+static DRIVING: OnceLock<Mutex<HashMap<Uuid, bool>>> = OnceLock::new();
+
+fn driving() -> &'static Mutex<HashMap<Uuid, bool>> {
+    DRIVING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Pause before each AI action, so humans can follow the table
+///
+/// Configured with `AI_TURN_DELAY_MS`.
+pub fn turn_delay() -> Duration {
+    let millis = env::var("AI_TURN_DELAY_MS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_TURN_DELAY_MS);
+    Duration::from_millis(millis)
+}
+
+/// Drive a game's AI seats in the background
+///
+/// Call after any change to a game. If the game is already being driven, the
+/// running driver is told to look again instead of starting a second one.
+pub fn schedule(game_id: Uuid, db: DatabaseConnection) {
+    {
+        let mut driving = driving().lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(changed) = driving.get_mut(&game_id) {
+            *changed = true;
+            return;
+        }
+        driving.insert(game_id, false);
+    }
+
+    tokio::spawn(async move {
+        let mut guard = DriveGuard {
+            game_id,
+            active: true,
+        };
+        drive(&mut guard, &db).await;
+    });
+}
+
+/// Who makes an AI seat's decisions
+#[derive(Debug, Clone)]
+pub(crate) struct AiSeat {
+    /// Built-in level, also used when the seat's bot fails to answer
+    pub difficulty: Difficulty,
+    /// External bot playing the seat, if any
+    pub bot: Option<bots::Model>,
+}
+
+/// Have an AI seat make a decision
+///
+/// A bot is asked first, with the strategy for the seat's difficulty deciding
+/// whenever the bot cannot be reached, misses its deadline or answers with an
+/// illegal action.
+pub(crate) async fn choose(
+    game_id: Uuid,
+    seat: AiSeat,
+    decision: Decision,
+    view: SeatView,
+) -> Result<BotAction, String> {
+    if let Some(bot) = &seat.bot {
+        let request =
+            DecisionRequest::new(game_id, decision, &view, bot_protocol::decision_timeout());
+        match bot_protocol::ask(bot, &request).await {
+            Ok(action) => return Ok(action),
+            Err(e) => warn!(
+                "Bot {} failed to decide for game {game_id}, using the built-in AI: {e}",
+                bot.id
+            ),
+        }
+    }
+
+    decide(
+        seat.difficulty,
+        view,
+        move |strategy, view, rng| match decision {
+            Decision::Bid => BotAction::Bid {
+                bid: strategy.choose_bid(view, rng),
+            },
+            Decision::Trump => BotAction::Trump {
+                trump: strategy.choose_trump(view, rng),
+            },
+            Decision::Card => BotAction::Card {
+                card: strategy.choose_card(view, rng),
+            },
+        },
+    )
+    .await
+}
+
+/// Have a seat's strategy make a decision on the blocking thread pool
+///
+/// The expert level can search for its whole budget; running strategies on
+/// the blocking pool keeps the async workers free to serve requests.
+pub(crate) async fn decide<T, F>(
+    difficulty: Difficulty,
+    view: SeatView,
+    choose: F,
+) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&dyn Strategy, &SeatView, &mut dyn RngCore) -> T + Send + 'static,
+{
+    let decision = tokio::task::spawn_blocking(move || {
+        choose(difficulty.strategy(), &view, &mut rand::thread_rng())
+    });
+    match decision.await {
+        Ok(choice) => Ok(choice),
+        Err(e) => Err(format!("Failed to make AI decision: {e}")),
+    }
+}
+
+/// Resume every started game, so AI turns pending at shutdown are taken
+pub async fn resume_pending(db: &DatabaseConnection) -> Result<(), String> {
+    let started = match games::Entity::find()
+        .filter(games::Column::State.eq(games::GameState::Started))
+        .all(db)
+        .await
+    {
+        Ok(games) => games,
+        Err(e) => return Err(format!("Failed to fetch started games: {e}")),
+    };
+
+    for game in started {
+        schedule(game.id, db.clone());
+    }
+    Ok(())
+}
+
+/// Releases a game's driving entry, even if the driver panics
+struct DriveGuard {
+    game_id: Uuid,
+    active: bool,
+}
+
+impl Drop for DriveGuard {
+    fn drop(&mut self) {
+        if self.active {
+            let mut driving = driving().lock().unwrap_or_else(|e| e.into_inner());
+            driving.remove(&self.game_id);
+        }
+    }
+}
+
+async fn drive(guard: &mut DriveGuard, db: &DatabaseConnection) {
+    let game_id = guard.game_id;
+    // Decision refused as stale, kept until the game moves past its version
+    let mut answered = None;
+    loop {
+        let acted = match next_ai_turn(game_id, db).await {
+            Ok(Some(turn)) => {
+                tokio::time::sleep(turn_delay()).await;
+                match take_turn(game_id, turn, &mut answered, db).await {
+                    Ok(true) => {
+                        live::notify(game_id);
+                        true
+                    }
+                    // The game moved on while the seat was deciding
+                    Ok(false) => true,
+                    Err(e) => {
+                        warn!("AI turn failed for game {game_id}: {e}");
+                        false
+                    }
+                }
+            }
+            Ok(None) => false,
+            Err(e) => {
+                warn!("Failed to check AI turn for game {game_id}: {e}");
+                false
+            }
+        };
+        if acted {
+            continue;
+        }
+
+        // Stop, unless the game changed while we were looking
+        let mut driving = driving().lock().unwrap_or_else(|e| e.into_inner());
+        if driving.get(&game_id) == Some(&true) {
+            driving.insert(game_id, false);
+            continue;
+        }
+        driving.remove(&game_id);
+        guard.active = false;
+        return;
+    }
+}
+
+/// An AI seat due to act, as read without locking the game
+struct AiTurn {
+    player_id: Uuid,
+    seat: AiSeat,
+    decision: Decision,
+    view: SeatView,
+    /// Version of the game the seat decides at
+    version: i32,
+}
+
+/// Find the AI seat due to act in a game, if any, and who decides for it
+///
+/// Everything is read at one point in time, so the version the seat decides
+/// at is the one its view was built from.
+async fn next_ai_turn(game_id: Uuid, db: &DatabaseConnection) -> Result<Option<AiTurn>, String> {
+    let txn = match db
+        .begin_with_config(
+            Some(IsolationLevel::RepeatableRead),
+            Some(AccessMode::ReadOnly),
+        )
+        .await
+    {
+        Ok(txn) => txn,
+        Err(e) => return Err(format!("Failed to begin transaction: {e}")),
+    };
+    let turn = read_ai_turn(game_id, &txn).await?;
+    if let Err(e) = txn.commit().await {
+        return Err(format!("Failed to commit transaction: {e}"));
+    }
+    Ok(turn)
+}
+
+async fn read_ai_turn(
+    game_id: Uuid,
+    db: &(impl ConnectionTrait + std::marker::Send),
+) -> Result<Option<AiTurn>, String> {
+    let game = match games::Entity::find_by_id(game_id).one(db).await {
+        Ok(Some(game)) => game,
+        Ok(None) => return Ok(None),
+        Err(e) => return Err(format!("Failed to fetch game: {e}")),
+    };
+    if game.state != games::GameState::Started {
+        return Ok(None);
+    }
+
+    let engine = orchestration::load_engine(&game, db).await?;
+    let Some(player_id) = engine.current_player() else {
+        return Ok(None);
+    };
+    let decision = match engine.phase {
+        Phase::Bidding => Decision::Bid,
+        Phase::TrumpSelection => Decision::Trump,
+        Phase::Playing => Decision::Card,
+        Phase::Waiting | Phase::Completed => return Ok(None),
+    };
+
+    let player = match game_players::Entity::find_by_id(player_id).one(db).await {
+        Ok(Some(player)) => player,
+        Ok(None) => return Err("Player not found".to_string()),
+        Err(e) => return Err(format!("Failed to fetch player data: {e}")),
+    };
+    let is_ai = match users::Entity::find_by_id(player.user_id).one(db).await {
+        Ok(user) => user.is_some_and(|user| user.is_ai),
+        Err(e) => return Err(format!("Failed to fetch user: {e}")),
+    };
+    if !is_ai {
+        return Ok(None);
+    }
+
+    let bot = match bots::Entity::find()
+        .filter(bots::Column::UserId.eq(player.user_id))
+        .one(db)
+        .await
+    {
+        Ok(bot) => bot,
+        Err(e) => return Err(format!("Failed to fetch bot: {e}")),
+    };
+    let seat = AiSeat {
+        difficulty: player
+            .ai_difficulty
+            .map(Difficulty::from)
+            .unwrap_or_default(),
+        bot,
+    };
+    let seat_number = engine
+        .seat_of(player_id)
+        .ok_or("Player not found in game")?;
+    let view = SeatView::new(&engine, seat_number).ok_or("No current round found")?;
+    Ok(Some(AiTurn {
+        player_id,
+        seat,
+        decision,
+        view,
+        version: game.version,
+    }))
+}
+
+/// Have an AI seat decide on its turn, then take it
+///
+/// Returns false when the game moved on while the seat was deciding, keeping
+/// the decision in `answered`. It is taken again instead of asking the seat
+/// anew while the game is still read at the version it was made for.
+async fn take_turn(
+    game_id: Uuid,
+    turn: AiTurn,
+    answered: &mut Option<(i32, BotAction)>,
+    db: &DatabaseConnection,
+) -> Result<bool, String> {
+    let AiTurn {
+        player_id,
+        seat,
+        decision,
+        view,
+        version,
+    } = turn;
+    let action = match answered.take() {
+        Some((answered_at, action)) if answered_at == version => action,
+        _ => choose(game_id, seat, decision, view).await?,
+    };
+    let taken = match action {
+        BotAction::Bid { bid } => {
+            bidding::perform_ai_bid(game_id, player_id, bid, version, db).await?
+        }
+        BotAction::Trump { trump } => {
+            bidding::perform_ai_trump_selection(game_id, player_id, trump, version, db).await?
+        }
+        BotAction::Card { card } => {
+            perform_ai_card_play(game_id, player_id, card, version, db).await?
+        }
+    };
+    if !taken {
+        *answered = Some((version, action));
+    }
+    Ok(taken)
+}
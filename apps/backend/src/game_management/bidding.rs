@@ -3,49 +3,74 @@
 //! This module contains bidding logic, bid validation,
 //! bid processing mechanisms, and highest bidder resolution.
 
-use chrono::Utc;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use sea_orm::sea_query::LockType;
-use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
-    QueryFilter, QueryOrder, QuerySelect, Set,
-};
+use rand::{Rng, SeedableRng};
+use sea_orm::{DatabaseConnection, DatabaseTransaction, TransactionError, TransactionTrait};
 use uuid::Uuid;
 
-use crate::dto::bid_request::BidRequest;
-use crate::entity::{game_players, game_rounds, games, round_bids};
+use crate::game_management::cards::{Card, Trump};
+use crate::game_management::engine::{Action, RuleError};
+use crate::game_management::orchestration::{
+    apply_action, load_engine, lock_game_at, persist_events, player_id_for_user, ActionError,
+};
+use crate::game_management::rules::RuleSet;
 
 /// Create a standard 52-card deck and shuffle it
 ///
 /// This function is PURE - it has no side effects and is deterministic
-/// given the same random seed. It creates a deck with standard card
-/// representations (e.g., "AS", "KH", "2C") and shuffles them.
-pub fn create_shuffled_deck() -> Vec<String> {
-    let suits = vec!["H", "D", "C", "S"]; // Hearts, Diamonds, Clubs, Spades
-    let ranks = vec![
-        "2", "3", "4", "5", "6", "7", "8", "9", "T", "J", "Q", "K", "A",
-    ];
-
-    let mut deck = Vec::new();
-    for suit in &suits {
-        for rank in &ranks {
-            deck.push(format!("{rank}{suit}"));
-        }
-    }
+/// given the same random number generator state. It creates a deck of typed
+/// cards (e.g., "AS", "KH", "2C") and shuffles them with the given RNG.
+pub fn create_shuffled_deck(rng: &mut impl Rng) -> Vec<Card> {
+    let mut deck = Card::full_deck();
 
     // Shuffle the deck
-    let mut rng = rand::thread_rng();
-    deck.shuffle(&mut rng);
+    deck.shuffle(rng);
 
     deck
 }
 
-/// Validate bid value (0-13)
+/// Create the shuffled deck for a recorded deal seed
+///
+/// This function is PURE - the same seed always produces the same deck.
+pub fn deck_for_seed(seed: i64) -> Vec<Card> {
+    create_shuffled_deck(&mut StdRng::seed_from_u64(seed as u64))
+}
+
+/// Derive the deal seed for a round from a game's pinned base seed
+///
+/// This function is PURE - it gives every round of a pinned game its own,
+/// reproducible deal.
+pub fn round_deal_seed(base_seed: i64, round_number: i32) -> i64 {
+    base_seed.wrapping_add(round_number as i64)
+}
+
+/// Split a shuffled deck into one hand per seat
+///
+/// This function is PURE - seat N receives the Nth block of `cards_dealt`
+/// cards from the top of the deck. Returns an error if the deck is too small.
+pub fn deal_hands(
+    deck: &[Card],
+    player_count: usize,
+    cards_dealt: i32,
+) -> Result<Vec<Vec<Card>>, String> {
+    let hand_size = usize::try_from(cards_dealt).map_err(|_| "Invalid number of cards dealt")?;
+    if hand_size * player_count > deck.len() {
+        return Err("Not enough cards in deck".to_string());
+    }
+
+    Ok((0..player_count)
+        .map(|seat| deck[seat * hand_size..(seat + 1) * hand_size].to_vec())
+        .collect())
+}
+
+/// Validate bid value against the game's bid bounds for the current round
 ///
 /// This function is PURE - it validates bid values without any side effects.
+/// A bid can never exceed the number of cards dealt in the round.
 /// Returns true if the bid is valid, false otherwise.
-pub fn is_valid_bid(bid: i32) -> bool {
-    (0..=13).contains(&bid)
+pub fn is_valid_bid(bid: i32, cards_dealt: i32, rules: &RuleSet) -> bool {
+    rules.is_valid_bid_for_round(bid, cards_dealt)
 }
 
 /// Find the highest bidder from a list of bids
@@ -88,699 +113,234 @@ pub fn all_bids_submitted(bid_count: usize, player_count: usize) -> bool {
 /// Calculate the next player's turn for bidding
 ///
 /// This function is PURE - it calculates the next turn using modulo arithmetic.
-/// Returns the next turn index (0-3) with wraparound.
-pub fn get_next_bidding_turn(current_turn: i32) -> i32 {
-    (current_turn + 1) % 4
+/// Returns the next turn index (0 to player_count - 1) with wraparound.
+pub fn get_next_bidding_turn(current_turn: i32, player_count: usize) -> i32 {
+    (current_turn + 1) % player_count as i32
 }
 
-/// Validate that a bid can be submitted for the current game state
+/// Find the bid the dealer may not make under the dealer hook rule
 ///
-/// This function validates the game phase, turn order, and whether the player
-/// has already bid. Returns Ok(()) if valid, Err with message if invalid.
-pub(crate) async fn validate_bid(
-    game_id: Uuid,
-    user_id: Uuid,
-    bid_value: i32,
-    txn: &DatabaseTransaction,
-) -> Result<(), String> {
-    // Validate bid value (0-13)
-    if !is_valid_bid(bid_value) {
-        return Err("Bid must be between 0 and 13".to_string());
+/// This function is PURE - it checks whether the next bid would be the last
+/// of the round, which is the dealer's, and if so which bid would make the total equal the cards
+/// dealt. Returns None when the hook rule is off, the next bidder is not last,
+/// or the forbidden total is outside the allowed bid range.
+pub fn forbidden_hook_bid(rules: &RuleSet, cards_dealt: i32, existing_bids: &[i32]) -> Option<i32> {
+    if !rules.dealer_hook || existing_bids.len() + 1 != rules.player_count {
+        return None;
     }
 
-    // Lock the game row for update to prevent concurrent modifications
-    let game = match games::Entity::find_by_id(game_id)
-        .lock(LockType::Update)
-        .one(txn)
-        .await
-    {
-        Ok(Some(game)) => game,
-        Ok(None) => {
-            return Err("Game not found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch game: {e}"));
-        }
-    };
+    let forbidden = cards_dealt - existing_bids.iter().sum::<i32>();
+    is_valid_bid(forbidden, cards_dealt, rules).then_some(forbidden)
+}
 
-    // Validate that the game is in the Bidding phase
-    if game.phase != games::GamePhase::Bidding {
-        return Err("Game is not in bidding phase".to_string());
+/// Move a bid off the value forbidden by the dealer hook rule
+///
+/// This function is PURE - it returns the bid unchanged unless it is the
+/// forbidden value, in which case the nearest allowed bid is returned
+/// (one lower where possible, otherwise one higher).
+pub fn avoid_hook_bid(
+    bid: i32,
+    forbidden_bid: Option<i32>,
+    cards_dealt: i32,
+    rules: &RuleSet,
+) -> i32 {
+    if forbidden_bid != Some(bid) {
+        return bid;
+    }
+
+    if is_valid_bid(bid - 1, cards_dealt, rules) {
+        bid - 1
+    } else {
+        bid + 1
     }
+}
 
-    // Fetch the current player's game_player record
-    let current_player = match game_players::Entity::find()
-        .filter(game_players::Column::GameId.eq(game_id))
-        .filter(game_players::Column::UserId.eq(user_id))
-        .one(txn)
-        .await
-    {
-        Ok(Some(player)) => player,
-        Ok(None) => {
-            return Err("You are not a participant in this game".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch player data: {e}"));
-        }
-    };
+/// Reasons a bid submission can be rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BidError {
+    /// The dealer tried to make total bids equal the cards dealt
+    HookRule { forbidden_bid: i32 },
+    /// The bid is outside the range allowed for the current round
+    OutOfRange {
+        min_bid: i32,
+        max_bid: i32,
+        cards_dealt: i32,
+    },
+    /// Any other validation or storage failure
+    Rejected(String),
+}
 
-    // Check if this player has already bid in this round (idempotency check)
-    let current_round = match game_rounds::Entity::find()
-        .filter(game_rounds::Column::GameId.eq(game_id))
-        .order_by_desc(game_rounds::Column::RoundNumber)
-        .one(txn)
-        .await
-    {
-        Ok(Some(round)) => round,
-        Ok(None) => {
-            return Err("No current round found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch current round: {e}"));
+impl BidError {
+    /// Machine-readable error code for API responses
+    pub fn code(&self) -> &'static str {
+        match self {
+            BidError::HookRule { .. } => "HOOK_RULE_VIOLATION",
+            BidError::OutOfRange { .. } => "BID_OUT_OF_RANGE",
+            BidError::Rejected(_) => "BID_REJECTED",
         }
-    };
+    }
+}
 
-    let existing_bid = match round_bids::Entity::find()
-        .filter(round_bids::Column::RoundId.eq(current_round.id))
-        .filter(round_bids::Column::PlayerId.eq(current_player.id))
-        .one(txn)
-        .await
-    {
-        Ok(Some(_)) => true,
-        Ok(None) => false,
-        Err(e) => {
-            return Err(format!("Failed to check existing bid: {e}"));
+impl std::fmt::Display for BidError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BidError::HookRule { forbidden_bid } => write!(
+                f,
+                "Dealer hook rule: the dealer may not bid {forbidden_bid}"
+            ),
+            BidError::OutOfRange {
+                min_bid,
+                max_bid,
+                cards_dealt,
+            } => write!(
+                f,
+                "Bid must be between {min_bid} and {max_bid} when {cards_dealt} cards are dealt"
+            ),
+            BidError::Rejected(message) => write!(f, "{message}"),
         }
-    };
-
-    if existing_bid {
-        return Err("You have already submitted a bid for this round".to_string());
     }
+}
 
-    // Check if it's this player's turn to bid
-    let current_turn = game.current_turn.unwrap_or(0);
-    if current_player.turn_order.unwrap_or(-1) != current_turn {
-        return Err("It's not your turn to bid".to_string());
+impl std::error::Error for BidError {}
+
+impl From<String> for BidError {
+    fn from(message: String) -> Self {
+        BidError::Rejected(message)
     }
+}
 
-    Ok(())
+impl From<ActionError> for BidError {
+    fn from(error: ActionError) -> Self {
+        match error {
+            ActionError::Rule(RuleError::HookRule { forbidden_bid }) => {
+                BidError::HookRule { forbidden_bid }
+            }
+            ActionError::Rule(RuleError::BidOutOfRange {
+                min_bid,
+                max_bid,
+                cards_dealt,
+            }) => BidError::OutOfRange {
+                min_bid,
+                max_bid,
+                cards_dealt,
+            },
+            other => BidError::Rejected(other.to_string()),
+        }
+    }
 }
 
 /// Submit a bid within a transaction
 ///
-/// This function handles the complete bid submission process including
-/// validation, saving the bid, and potentially transitioning the game phase.
+/// The game engine checks the phase, turn order, bid range and dealer hook
+/// rule, and moves on to the next bidder or to trump selection.
 pub(crate) async fn submit_bid_transaction(
     game_id: Uuid,
     user_id: Uuid,
     bid_value: i32,
     txn: &DatabaseTransaction,
-) -> Result<(), String> {
-    // Validate the bid first
-    validate_bid(game_id, user_id, bid_value, txn).await?;
-
-    // Lock the current round row for update
-    let current_round = match game_rounds::Entity::find()
-        .filter(game_rounds::Column::GameId.eq(game_id))
-        .order_by_desc(game_rounds::Column::RoundNumber)
-        .lock(LockType::Update)
-        .one(txn)
-        .await
-    {
-        Ok(Some(round)) => round,
-        Ok(None) => {
-            return Err("No current round found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch current round: {e}"));
-        }
-    };
-
-    // Fetch the current player's game_player record
-    let current_player = match game_players::Entity::find()
-        .filter(game_players::Column::GameId.eq(game_id))
-        .filter(game_players::Column::UserId.eq(user_id))
-        .one(txn)
-        .await
-    {
-        Ok(Some(player)) => player,
-        Ok(None) => {
-            return Err("Failed to fetch player data".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch player data: {e}"));
-        }
-    };
-
-    // Save the bid to the round_bids table
-    let bid_id = Uuid::new_v4();
-    let round_bid = round_bids::ActiveModel {
-        id: Set(bid_id),
-        round_id: Set(current_round.id),
-        player_id: Set(current_player.id),
-        bid: Set(bid_value),
-    };
-
-    match round_bid.insert(txn).await {
-        Ok(_) => (),
-        Err(e) => {
-            return Err(format!("Failed to save bid: {e}"));
-        }
-    }
-
-    // Check if all players have bid in this round
-    let all_players = match game_players::Entity::find()
-        .filter(game_players::Column::GameId.eq(game_id))
-        .all(txn)
-        .await
-    {
-        Ok(players) => players,
-        Err(e) => {
-            return Err(format!("Failed to fetch all players: {e}"));
-        }
-    };
-
-    let round_bids = match round_bids::Entity::find()
-        .filter(round_bids::Column::RoundId.eq(current_round.id))
-        .all(txn)
-        .await
-    {
-        Ok(bids) => bids,
-        Err(e) => {
-            return Err(format!("Failed to fetch round bids: {e}"));
-        }
-    };
-
-    let all_bids_submitted = all_bids_submitted(round_bids.len(), all_players.len());
-
-    if all_bids_submitted {
-        // Transition the game to TrumpSelection phase
-        let game = match games::Entity::find_by_id(game_id)
-            .lock(LockType::Update)
-            .one(txn)
-            .await
-        {
-            Ok(Some(game)) => game,
-            Ok(None) => {
-                return Err("Game not found".to_string());
-            }
-            Err(e) => {
-                return Err(format!("Failed to fetch game: {e}"));
-            }
-        };
-
-        let game_update = games::ActiveModel {
-            id: Set(game.id),
-            state: Set(game.state),
-            phase: Set(games::GamePhase::TrumpSelection),
-            current_turn: Set(Some(0)), // Reset turn for trump selection
-            created_at: Set(game.created_at),
-            updated_at: Set(Utc::now().into()),
-            started_at: Set(game.started_at),
-            completed_at: Set(game.completed_at),
-        };
-
-        match game_update.update(txn).await {
-            Ok(_) => (),
-            Err(e) => {
-                return Err(format!("Failed to transition game phase: {e}"));
-            }
-        }
-    } else {
-        // Move to next player's turn
-        let game = match games::Entity::find_by_id(game_id)
-            .lock(LockType::Update)
-            .one(txn)
-            .await
-        {
-            Ok(Some(game)) => game,
-            Ok(None) => {
-                return Err("Game not found".to_string());
-            }
-            Err(e) => {
-                return Err(format!("Failed to fetch game: {e}"));
-            }
-        };
-
-        let current_turn = game.current_turn.unwrap_or(0);
-        let next_turn = get_next_bidding_turn(current_turn);
-
-        let game_update = games::ActiveModel {
-            id: Set(game.id),
-            state: Set(game.state),
-            phase: Set(game.phase),
-            current_turn: Set(Some(next_turn)),
-            created_at: Set(game.created_at),
-            updated_at: Set(Utc::now().into()),
-            started_at: Set(game.started_at),
-            completed_at: Set(game.completed_at),
-        };
-
-        match game_update.update(txn).await {
-            Ok(_) => (),
-            Err(e) => {
-                return Err(format!("Failed to update turn: {e}"));
-            }
-        }
-    }
+) -> Result<(), BidError> {
+    let player_id = player_id_for_user(game_id, user_id, txn).await?;
+
+    apply_action(
+        game_id,
+        Action::Bid {
+            player_id,
+            bid: bid_value,
+        },
+        txn,
+    )
+    .await?;
 
     Ok(())
 }
 
-/// Resolve the highest bidder for a round
-///
-/// This function finds the highest bidder from the round bids, handling ties
-/// by giving preference to the first bidder encountered.
-pub(crate) async fn resolve_highest_bidder(
-    round_id: Uuid,
-    db: &DatabaseConnection,
-) -> Result<Option<Uuid>, String> {
-    let round_bids = match round_bids::Entity::find()
-        .filter(round_bids::Column::RoundId.eq(round_id))
-        .all(db)
-        .await
-    {
-        Ok(bids) => bids,
-        Err(e) => {
-            return Err(format!("Failed to fetch round bids: {e}"));
-        }
-    };
-
-    let bids_with_players: Vec<(i32, Uuid)> = round_bids
-        .iter()
-        .map(|bid| (bid.bid, bid.player_id))
-        .collect();
-
-    let (_, highest_bidder_id, _) = find_highest_bidder(&bids_with_players);
-    Ok(highest_bidder_id)
-}
-
 /// Perform AI bidding action
 ///
-/// This function handles AI player bidding, including validation and
-/// potentially transitioning the game phase.
-#[allow(dead_code)]
+/// Places the bid the seat's bot or strategy chose at `version`, without
+/// holding the game's lock while it decided. The engine still rejects any bid
+/// forbidden by the dealer hook rule. Returns false, without bidding, when the
+/// game has moved on since.
 pub(crate) async fn perform_ai_bid(
     game_id: Uuid,
     player_id: Uuid,
-    bid_request: BidRequest,
+    bid: i32,
+    version: i32,
     db: &DatabaseConnection,
-) -> Result<(), String> {
-    // Validate bid value (0-13)
-    let bid_value = bid_request.bid;
-    if !is_valid_bid(bid_value) {
-        println!("[ERROR] perform_ai_bid: Invalid bid value: {bid_value}");
-        return Err("Bid must be between 0 and 13".to_string());
-    }
-
-    // Fetch the game
-    let game = match games::Entity::find_by_id(game_id).one(db).await {
-        Ok(Some(game)) => game,
-        Ok(None) => {
-            println!("[ERROR] perform_ai_bid: Game not found: {game_id}");
-            return Err("Game not found".to_string());
-        }
-        Err(e) => {
-            println!("[ERROR] perform_ai_bid: Failed to fetch game: {e}");
-            return Err(format!("Failed to fetch game: {e}"));
-        }
+) -> Result<bool, String> {
+    let txn = match db.begin().await {
+        Ok(txn) => txn,
+        Err(e) => return Err(format!("Failed to start transaction: {e}")),
     };
 
-    // Validate that the game is in the Bidding phase
-    if game.phase != games::GamePhase::Bidding {
-        return Err("Game is not in bidding phase".to_string());
-    }
-
-    // Find the current round for this game (latest round)
-    let current_round = match game_rounds::Entity::find()
-        .filter(game_rounds::Column::GameId.eq(game_id))
-        .order_by_desc(game_rounds::Column::RoundNumber)
-        .one(db)
-        .await
-    {
-        Ok(Some(round)) => round,
-        Ok(None) => {
-            println!("[ERROR] perform_ai_bid: No current round found for game: {game_id}",);
-            return Err("No current round found".to_string());
-        }
-        Err(e) => {
-            println!("[ERROR] perform_ai_bid: Failed to fetch current round: {e}",);
-            return Err(format!("Failed to fetch current round: {e}"));
-        }
-    };
-
-    // Check if this player has already bid in this round
-    let existing_bid = match round_bids::Entity::find()
-        .filter(round_bids::Column::RoundId.eq(current_round.id))
-        .filter(round_bids::Column::PlayerId.eq(player_id))
-        .one(db)
-        .await
-    {
-        Ok(Some(_)) => true,
-        Ok(None) => false,
-        Err(e) => {
-            println!("[ERROR] perform_ai_bid: Failed to check existing bid: {e}",);
-            return Err(format!("Failed to check existing bid: {e}"));
-        }
+    let Some(game) = lock_game_at(game_id, version, &txn).await? else {
+        return Ok(false);
     };
-
-    if existing_bid {
-        return Err("Player has already submitted a bid for this round".to_string());
-    }
-
-    // Check if it's this player's turn to bid
-    let current_turn = game.current_turn.unwrap_or(0);
-    let current_player = match game_players::Entity::find()
-        .filter(game_players::Column::GameId.eq(game_id))
-        .filter(game_players::Column::Id.eq(player_id))
-        .one(db)
-        .await
-    {
-        Ok(Some(player)) => player,
-        Ok(None) => {
-            println!("[ERROR] perform_ai_bid: Player not found: {player_id}");
-            return Err("Player not found".to_string());
-        }
+    let mut engine = load_engine(&game, &txn).await?;
+    let events = match engine.apply(Action::Bid { player_id, bid }) {
+        Ok(events) => events,
         Err(e) => {
-            println!("[ERROR] perform_ai_bid: Failed to fetch player data: {e}");
-            return Err(format!("Failed to fetch player data: {e}"));
+            println!("[ERROR] perform_ai_bid: Bid rejected for game {game_id}: {e}");
+            return Err(e.to_string());
         }
     };
+    persist_events(game, &engine, &events, &txn).await?;
 
-    if current_player.turn_order.unwrap_or(-1) != current_turn {
-        return Err("It's not this player's turn to bid".to_string());
+    match txn.commit().await {
+        Ok(_) => Ok(true),
+        Err(e) => Err(format!("Failed to commit bid: {e}")),
     }
-
-    // Save the bid to the round_bids table
-    let bid_id = Uuid::new_v4();
-    let round_bid = round_bids::ActiveModel {
-        id: Set(bid_id),
-        round_id: Set(current_round.id),
-        player_id: Set(player_id),
-        bid: Set(bid_value),
-    };
-
-    match round_bid.insert(db).await {
-        Ok(_) => (),
-        Err(e) => {
-            return Err(format!("Failed to save bid: {e}"));
-        }
-    }
-
-    // Check if all players have bid in this round
-    let all_players = match game_players::Entity::find()
-        .filter(game_players::Column::GameId.eq(game_id))
-        .all(db)
-        .await
-    {
-        Ok(players) => players,
-        Err(e) => {
-            println!("[ERROR] perform_ai_bid: Failed to fetch all players: {e}",);
-            return Err(format!("Failed to fetch all players: {e}"));
-        }
-    };
-
-    let round_bids = match round_bids::Entity::find()
-        .filter(round_bids::Column::RoundId.eq(current_round.id))
-        .all(db)
-        .await
-    {
-        Ok(bids) => bids,
-        Err(e) => {
-            println!("[ERROR] perform_ai_bid: Failed to fetch round bids: {e}",);
-            return Err(format!("Failed to fetch round bids: {e}"));
-        }
-    };
-
-    let all_bids_submitted = all_bids_submitted(round_bids.len(), all_players.len());
-
-    if all_bids_submitted {
-        // Transition the game to TrumpSelection phase using state module
-        if let Err(e) = crate::game_management::state::advance_phase(
-            &game,
-            games::GamePhase::TrumpSelection,
-            db,
-        )
-        .await
-        {
-            return Err(format!("Failed to transition game phase: {e}"));
-        }
-        if let Err(e) = crate::game_management::state::set_next_player(&game, 0, db).await {
-            return Err(format!("Failed to set next player: {e}"));
-        }
-    } else {
-        // Move to next player's turn using state module
-        let next_turn = get_next_bidding_turn(current_turn);
-        if let Err(e) = crate::game_management::state::set_next_player(&game, next_turn, db).await {
-            return Err(format!("Failed to update turn: {e}"));
-        }
-    }
-
-    Ok(())
 }
 
 /// Perform AI trump selection action
 ///
-/// This function handles AI player trump selection after winning the bidding.
-#[allow(dead_code)]
+/// Names the trump the seat's bot or strategy chose at `version` after
+/// winning the bidding. Returns false, without choosing, when the game has
+/// moved on since.
 pub(crate) async fn perform_ai_trump_selection(
     game_id: Uuid,
     player_id: Uuid,
-    trump_request: crate::dto::trump_request::TrumpRequest,
+    trump: Trump,
+    version: i32,
     db: &DatabaseConnection,
-) -> Result<(), String> {
-    // Validate trump suit
-    let trump_suit = &trump_request.trump_suit;
-    let valid_suits = ["Spades", "Hearts", "Diamonds", "Clubs", "NoTrump"];
-    if !valid_suits.contains(&trump_suit.as_str()) {
-        println!("[ERROR] perform_ai_trump_selection: Invalid trump suit: {trump_suit}",);
-        return Err(
-            "Invalid trump suit. Must be one of: Spades, Hearts, Diamonds, Clubs, NoTrump"
-                .to_string(),
-        );
-    }
-
-    // Fetch the game
-    let game = match games::Entity::find_by_id(game_id).one(db).await {
-        Ok(Some(game)) => game,
-        Ok(None) => {
-            return Err("Game not found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch game: {e}"));
-        }
-    };
-
-    // Validate that the game is in the TrumpSelection phase
-    if game.phase != games::GamePhase::TrumpSelection {
-        return Err("Game is not in trump selection phase".to_string());
-    }
-
-    // Fetch the current round for this game (latest round)
-    let current_round = match game_rounds::Entity::find()
-        .filter(game_rounds::Column::GameId.eq(game_id))
-        .order_by_desc(game_rounds::Column::RoundNumber)
-        .one(db)
-        .await
-    {
-        Ok(Some(round)) => round,
-        Ok(None) => {
-            return Err("No current round found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch current round: {e}"));
-        }
-    };
-
-    // Check if trump has already been selected for this round
-    if current_round.trump_suit.is_some() {
-        return Err("Trump has already been selected for this round".to_string());
+) -> Result<bool, String> {
+    let result = db
+        .transaction::<_, bool, ActionError>(|txn| {
+            Box::pin(async move {
+                let Some(game) = lock_game_at(game_id, version, txn).await? else {
+                    return Ok(false);
+                };
+                let mut engine = load_engine(&game, txn).await?;
+                let events = engine.apply(Action::ChooseTrump { player_id, trump })?;
+                persist_events(game, &engine, &events, txn).await?;
+                Ok(true)
+            })
+        })
+        .await;
+
+    match result {
+        Ok(chosen) => Ok(chosen),
+        Err(TransactionError::Transaction(e)) => Err(e.to_string()),
+        Err(e) => Err(format!("Failed to select trump: {e}")),
     }
-
-    // Fetch all bids for this round to determine the highest bidder
-    let round_bids = match round_bids::Entity::find()
-        .filter(round_bids::Column::RoundId.eq(current_round.id))
-        .all(db)
-        .await
-    {
-        Ok(bids) => bids,
-        Err(e) => {
-            return Err(format!("Failed to fetch round bids: {e}"));
-        }
-    };
-
-    // Find the highest bid and the player who bid first in case of ties
-    let bids_with_players: Vec<(i32, Uuid)> = round_bids
-        .iter()
-        .map(|bid| (bid.bid, bid.player_id))
-        .collect();
-
-    let (_, highest_bidder_id, _) = find_highest_bidder(&bids_with_players);
-
-    // Validate that the current player is the designated trump chooser
-    if player_id != highest_bidder_id.unwrap_or_default() {
-        return Err("Only the highest bidder can choose the trump suit".to_string());
-    }
-
-    // Update the round with the trump suit
-    let round_update = game_rounds::ActiveModel {
-        id: Set(current_round.id),
-        game_id: Set(current_round.game_id),
-        round_number: Set(current_round.round_number),
-        dealer_player_id: Set(current_round.dealer_player_id),
-        trump_suit: Set(Some(trump_suit.clone())),
-        cards_dealt: Set(current_round.cards_dealt),
-        created_at: Set(current_round.created_at),
-    };
-
-    match round_update.update(db).await {
-        Ok(_) => (),
-        Err(e) => {
-            return Err(format!("Failed to update round with trump suit: {e}"));
-        }
-    }
-
-    // Transition the game to Playing phase
-    let game_update = games::ActiveModel {
-        id: Set(game.id),
-        state: Set(game.state),
-        phase: Set(games::GamePhase::Playing),
-        current_turn: Set(Some(0)), // Reset turn for playing phase
-        created_at: Set(game.created_at),
-        updated_at: Set(Utc::now().into()),
-        started_at: Set(game.started_at),
-        completed_at: Set(game.completed_at),
-    };
-
-    match game_update.update(db).await {
-        Ok(_) => (),
-        Err(e) => {
-            return Err(format!("Failed to transition game to playing phase: {e}"));
-        }
-    }
-
-    Ok(())
 }
 
 /// Submit trump selection within a transaction
 ///
-/// This function handles trump selection after bidding is complete.
+/// Only the highest bidder may choose trump; play then opens with the seat
+/// after the dealer.
 pub(crate) async fn submit_trump_transaction(
     game_id: Uuid,
     user_id: Uuid,
-    trump_suit: String,
+    trump: Trump,
     txn: &DatabaseTransaction,
 ) -> Result<(), String> {
-    // Lock the game row for update to prevent concurrent modifications
-    let game = match games::Entity::find_by_id(game_id)
-        .lock(LockType::Update)
-        .one(txn)
+    let player_id = player_id_for_user(game_id, user_id, txn)
         .await
-    {
-        Ok(Some(game)) => game,
-        Ok(None) => {
-            return Err("Game not found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch game: {e}"));
-        }
-    };
-
-    // Validate that the game is in the TrumpSelection phase
-    if game.phase != games::GamePhase::TrumpSelection {
-        return Err("Game is not in trump selection phase".to_string());
-    }
+        .map_err(|e| e.to_string())?;
 
-    // Lock the current round row for update
-    let current_round = match game_rounds::Entity::find()
-        .filter(game_rounds::Column::GameId.eq(game_id))
-        .order_by_desc(game_rounds::Column::RoundNumber)
-        .lock(LockType::Update)
-        .one(txn)
+    apply_action(game_id, Action::ChooseTrump { player_id, trump }, txn)
         .await
-    {
-        Ok(Some(round)) => round,
-        Ok(None) => {
-            return Err("No current round found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch current round: {e}"));
-        }
-    };
-
-    // Check if trump has already been selected for this round (idempotency check)
-    if current_round.trump_suit.is_some() {
-        return Err("Trump has already been selected for this round".to_string());
-    }
-
-    // Fetch all bids for this round to determine the highest bidder
-    let round_bids = match round_bids::Entity::find()
-        .filter(round_bids::Column::RoundId.eq(current_round.id))
-        .all(txn)
-        .await
-    {
-        Ok(bids) => bids,
-        Err(e) => {
-            return Err(format!("Failed to fetch round bids: {e}"));
-        }
-    };
-
-    // Find the highest bid and the player who bid first in case of ties
-    let bids_with_players: Vec<(i32, Uuid)> = round_bids
-        .iter()
-        .map(|bid| (bid.bid, bid.player_id))
-        .collect();
-
-    let (_, highest_bidder_id, _) = find_highest_bidder(&bids_with_players);
-
-    // Fetch the current player's game_player record
-    let current_player = match game_players::Entity::find()
-        .filter(game_players::Column::GameId.eq(game_id))
-        .filter(game_players::Column::UserId.eq(user_id))
-        .one(txn)
-        .await
-    {
-        Ok(Some(player)) => player,
-        Ok(None) => {
-            return Err("You are not a participant in this game".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch player data: {e}"));
-        }
-    };
-
-    // Validate that the current player is the designated trump chooser
-    if current_player.id != highest_bidder_id.unwrap_or_default() {
-        return Err("Only the highest bidder can choose the trump suit".to_string());
-    }
-
-    // Update the round with the selected trump suit
-    let round_update = game_rounds::ActiveModel {
-        id: Set(current_round.id),
-        game_id: Set(current_round.game_id),
-        round_number: Set(current_round.round_number),
-        dealer_player_id: Set(current_round.dealer_player_id),
-        trump_suit: Set(Some(trump_suit)),
-        cards_dealt: Set(current_round.cards_dealt),
-        created_at: Set(current_round.created_at),
-    };
-
-    match round_update.update(txn).await {
-        Ok(_) => (),
-        Err(e) => {
-            return Err(format!("Failed to update round with trump suit: {e}"));
-        }
-    }
-
-    // Transition the game to Playing phase using state module
-    if let Err(e) =
-        crate::game_management::state::advance_phase(&game, games::GamePhase::Playing, txn).await
-    {
-        return Err(format!("Failed to transition game phase: {e}"));
-    }
-    if let Err(e) = crate::game_management::state::set_next_player(&game, 0, txn).await {
-        return Err(format!("Failed to set next player: {e}"));
-    }
+        .map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -788,23 +348,21 @@ pub(crate) async fn submit_trump_transaction(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::entity::games;
+    use crate::game_management::cards::{Rank, Suit};
+    use crate::game_management::rules::{MAX_PLAYERS, MIN_PLAYERS};
 
     #[test]
     fn test_create_shuffled_deck() {
-        let deck = create_shuffled_deck();
+        let deck = create_shuffled_deck(&mut rand::thread_rng());
 
         // Should have exactly 52 cards
         assert_eq!(deck.len(), 52);
 
         // Should contain all expected cards
-        let suits = vec!["H", "D", "C", "S"];
-        let ranks = vec![
-            "2", "3", "4", "5", "6", "7", "8", "9", "T", "J", "Q", "K", "A",
-        ];
-
-        for suit in &suits {
-            for rank in &ranks {
-                let expected_card = format!("{rank}{suit}");
+        for suit in Suit::ALL {
+            for rank in Rank::ALL {
+                let expected_card = Card::new(rank, suit);
                 assert!(
                     deck.contains(&expected_card),
                     "Deck missing card: {expected_card}"
@@ -813,13 +371,90 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_seeded_decks_are_reproducible() {
+        let first = create_shuffled_deck(&mut StdRng::seed_from_u64(42));
+        let second = create_shuffled_deck(&mut StdRng::seed_from_u64(42));
+        assert_eq!(first, second);
+        assert_eq!(deck_for_seed(42), first);
+        assert_ne!(deck_for_seed(43), first);
+
+        // Negative seeds from the database map onto the full u64 range
+        assert_eq!(deck_for_seed(-1), deck_for_seed(-1));
+        assert_ne!(deck_for_seed(-1), deck_for_seed(1));
+
+        // Each round of a pinned game gets its own deal
+        assert_ne!(round_deal_seed(42, 1), round_deal_seed(42, 2));
+        assert_eq!(round_deal_seed(i64::MAX, 1), i64::MIN);
+    }
+
+    #[test]
+    fn test_deal_hands() {
+        let deck = deck_for_seed(7);
+        let hands = deal_hands(&deck, 4, 13).unwrap();
+        assert_eq!(hands.len(), 4);
+        assert!(hands.iter().all(|hand| hand.len() == 13));
+        assert_eq!(hands[0], deck[..13]);
+        assert_eq!(hands[3], deck[39..]);
+
+        let hands = deal_hands(&deck, 7, 2).unwrap();
+        assert_eq!(hands[6], deck[12..14]);
+
+        assert!(deal_hands(&deck, 5, 11).is_err());
+        assert!(deal_hands(&deck, 4, -1).is_err());
+    }
+
     #[test]
     fn test_is_valid_bid() {
-        assert!(is_valid_bid(0));
-        assert!(is_valid_bid(7));
-        assert!(is_valid_bid(13));
-        assert!(!is_valid_bid(-1));
-        assert!(!is_valid_bid(14));
+        let rules = RuleSet::standard();
+        assert!(is_valid_bid(0, 13, &rules));
+        assert!(is_valid_bid(7, 13, &rules));
+        assert!(is_valid_bid(13, 13, &rules));
+        assert!(!is_valid_bid(-1, 13, &rules));
+        assert!(!is_valid_bid(14, 13, &rules));
+
+        // Nobody can bid more tricks than cards dealt
+        assert!(is_valid_bid(2, 2, &rules));
+        assert!(!is_valid_bid(3, 2, &rules));
+        assert!(!is_valid_bid(9, 2, &rules));
+
+        let capped = RuleSet {
+            max_bid: 5,
+            ..RuleSet::standard()
+        };
+        assert!(is_valid_bid(5, 13, &capped));
+        assert!(!is_valid_bid(6, 13, &capped));
+    }
+
+    #[test]
+    fn test_bid_bounds_for_every_round() {
+        for player_count in MIN_PLAYERS..=MAX_PLAYERS {
+            let rules = RuleSet::for_players(player_count).unwrap();
+
+            for round_number in 1..=rules.total_rounds() {
+                let cards_dealt = rules.cards_dealt(round_number).unwrap();
+
+                for bid in -3..=rules.max_bid + 3 {
+                    let in_range = (0..=cards_dealt).contains(&bid);
+                    assert_eq!(
+                        is_valid_bid(bid, cards_dealt, &rules),
+                        in_range,
+                        "{player_count} players, round {round_number}, bid {bid}"
+                    );
+                }
+
+                let error = BidError::OutOfRange {
+                    min_bid: rules.min_bid,
+                    max_bid: rules.max_bid_for_round(cards_dealt),
+                    cards_dealt,
+                };
+                assert_eq!(error.code(), "BID_OUT_OF_RANGE");
+                assert_eq!(
+                    error.to_string(),
+                    format!("Bid must be between 0 and {cards_dealt} when {cards_dealt} cards are dealt")
+                );
+            }
+        }
     }
 
     #[test]
@@ -868,10 +503,11 @@ mod tests {
 
     #[test]
     fn test_get_next_bidding_turn() {
-        assert_eq!(get_next_bidding_turn(0), 1);
-        assert_eq!(get_next_bidding_turn(1), 2);
-        assert_eq!(get_next_bidding_turn(2), 3);
-        assert_eq!(get_next_bidding_turn(3), 0); // Wraparound
+        assert_eq!(get_next_bidding_turn(0, 4), 1);
+        assert_eq!(get_next_bidding_turn(1, 4), 2);
+        assert_eq!(get_next_bidding_turn(2, 4), 3);
+        assert_eq!(get_next_bidding_turn(3, 4), 0); // Wraparound
+        assert_eq!(get_next_bidding_turn(2, 3), 0); // Three player table
     }
 
     /// Test that game phase advances correctly after all bids are submitted
@@ -907,4 +543,47 @@ mod tests {
         // Test that the phase constants are different (ensuring transition is meaningful)
         assert_ne!(games::GamePhase::Bidding, games::GamePhase::TrumpSelection);
     }
+
+    #[test]
+    fn test_forbidden_hook_bid() {
+        let hook = RuleSet {
+            dealer_hook: true,
+            ..RuleSet::standard()
+        };
+
+        // Only the last of the four bidders is restricted
+        assert_eq!(forbidden_hook_bid(&hook, 13, &[3, 3, 3]), Some(4));
+        assert_eq!(forbidden_hook_bid(&hook, 13, &[3, 3]), None);
+        assert_eq!(forbidden_hook_bid(&hook, 2, &[0, 0, 0]), Some(2));
+
+        // No restriction when the total is already past the cards dealt
+        assert_eq!(forbidden_hook_bid(&hook, 2, &[1, 1, 1]), None);
+
+        // The rule is opt-in
+        assert_eq!(
+            forbidden_hook_bid(&RuleSet::standard(), 13, &[3, 3, 3]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_avoid_hook_bid() {
+        let rules = RuleSet::standard();
+        assert_eq!(avoid_hook_bid(4, Some(4), 13, &rules), 3);
+        assert_eq!(avoid_hook_bid(0, Some(0), 13, &rules), 1);
+        assert_eq!(avoid_hook_bid(0, Some(0), 1, &rules), 1);
+        assert_eq!(avoid_hook_bid(5, Some(4), 13, &rules), 5);
+        assert_eq!(avoid_hook_bid(4, None, 13, &rules), 4);
+    }
+
+    #[test]
+    fn test_bid_error_codes() {
+        let hook = BidError::HookRule { forbidden_bid: 4 };
+        assert_eq!(hook.code(), "HOOK_RULE_VIOLATION");
+        assert!(hook.to_string().contains('4'));
+
+        let rejected = BidError::from("It's not your turn to bid".to_string());
+        assert_eq!(rejected.code(), "BID_REJECTED");
+        assert_eq!(rejected.to_string(), "It's not your turn to bid");
+    }
 }
@@ -0,0 +1,585 @@
+//! External bot protocol
+//!
+//! User-written bots take AI seats and make their own decisions. When it is a
+//! bot's turn it is sent a `DecisionRequest` holding what its seat can see and
+//! every legal action, and it answers with a `BotReply` naming one of them
+//! before the deadline. Bots registered with a callback URL get each request
+//! as an HTTP POST and answer in the response body; the others keep a
+//! WebSocket open at `/bots/connect` and answer with a reply message. The
+//! wire format is documented in `docs/bot-protocol.md`.
+//!
+//! Callback URLs must point at public addresses, so a bot cannot be used to
+//! make the server reach its own network. Hosts listed in `BOT_CALLBACK_HOSTS`
+//! are trusted wherever they point, which is how a bot on the same machine is
+//! registered.
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use rand::RngCore;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::{redirect, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
+
+use crate::entity::bots;
+use crate::game_management::ai::SeatView;
+use crate::game_management::cards::{Card, Trump};
+use crate::game_management::engine::Phase;
+
+/// Time a bot gets to decide when `BOT_DECISION_TIMEOUT_MS` is not set
+const DEFAULT_DECISION_TIMEOUT_MS: u64 = 5000;
+
+/// Time a bot gets to answer a decision request
+///
+/// Configured with `BOT_DECISION_TIMEOUT_MS`.
+pub fn decision_timeout() -> Duration {
+    let millis = env::var("BOT_DECISION_TIMEOUT_MS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_DECISION_TIMEOUT_MS);
+    Duration::from_millis(millis)
+}
+
+/// Whether a bot may be sent requests at an address
+///
+/// This function is PURE - loopback, private, link-local, shared and other
+/// non-routable addresses are refused.
+pub fn public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [first, second, ..] = v4.octets();
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_documentation()
+                || v4.is_multicast()
+                || first == 0
+                || (first == 100 && second & 0xc0 == 64))
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => public_address(IpAddr::V4(v4)),
+            None => {
+                let first = v6.segments()[0];
+                !(v6.is_loopback()
+                    || v6.is_unspecified()
+                    || v6.is_multicast()
+                    || first & 0xfe00 == 0xfc00
+                    || first & 0xffc0 == 0xfe80)
+            }
+        },
+    }
+}
+
+/// Whether a host is trusted wherever it points
+///
+/// Configured with `BOT_CALLBACK_HOSTS`, a comma-separated list of hosts.
+fn trusted_host(host: &str) -> bool {
+    env::var("BOT_CALLBACK_HOSTS").is_ok_and(|hosts| {
+        hosts
+            .split(',')
+            .any(|trusted| trusted.trim().eq_ignore_ascii_case(host))
+    })
+}
+
+/// Check that a callback URL may be sent decision requests
+///
+/// The URL must be http or https and its host must resolve only to public
+/// addresses, unless it is trusted.
+pub async fn check_callback_url(url: &str) -> Result<(), String> {
+    let url = Url::parse(url).map_err(|e| format!("Invalid callback URL: {e}"))?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err("Callback URL must be http or https".to_string());
+    }
+    let Some(host) = url.host_str() else {
+        return Err("Callback URL has no host".to_string());
+    };
+    if trusted_host(host) {
+        return Ok(());
+    }
+
+    let port = url.port_or_known_default().unwrap_or(80);
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let addrs = match tokio::net::lookup_host((host, port)).await {
+        Ok(addrs) => addrs.collect::<Vec<_>>(),
+        Err(e) => return Err(format!("Failed to resolve callback host: {e}")),
+    };
+    if addrs.is_empty() || !addrs.iter().all(|addr| public_address(addr.ip())) {
+        return Err("Callback URL must point at a public address".to_string());
+    }
+    Ok(())
+}
+
+/// Resolver for callback requests that refuses non-public addresses
+///
+/// Checked again when connecting, so a host cannot resolve to a public address
+/// when registered and to a private one later.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if !trusted_host(&host) && !addrs.iter().all(|addr| public_address(addr.ip())) {
+                return Err(format!("{host} does not resolve to a public address").into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// The kind of decision a bot is asked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Decision {
+    Bid,
+    Trump,
+    Card,
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Decision::Bid => write!(f, "bid"),
+            Decision::Trump => write!(f, "trump"),
+            Decision::Card => write!(f, "card"),
+        }
+    }
+}
+
+impl Decision {
+    /// The decision due in a phase, if anyone acts in it
+    pub fn for_phase(phase: Phase) -> Option<Self> {
+        match phase {
+            Phase::Bidding => Some(Decision::Bid),
+            Phase::TrumpSelection => Some(Decision::Trump),
+            Phase::Playing => Some(Decision::Card),
+            Phase::Waiting | Phase::Completed => None,
+        }
+    }
+}
+
+/// A move a bot can make
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BotAction {
+    Bid { bid: i32 },
+    Trump { trump: Trump },
+    Card { card: Card },
+}
+
+impl BotAction {
+    /// The decision this action answers
+    pub fn decision(&self) -> Decision {
+        match self {
+            BotAction::Bid { .. } => Decision::Bid,
+            BotAction::Trump { .. } => Decision::Trump,
+            BotAction::Card { .. } => Decision::Card,
+        }
+    }
+}
+
+/// Every action a seat may take for a decision
+///
+/// This function is PURE - it lists the seat's legal bids, every trump, or
+/// the cards it may play into the current trick.
+pub fn legal_actions(view: &SeatView, decision: Decision) -> Vec<BotAction> {
+    match decision {
+        Decision::Bid => view
+            .legal_bids()
+            .into_iter()
+            .map(|bid| BotAction::Bid { bid })
+            .collect(),
+        Decision::Trump => Trump::ALL
+            .into_iter()
+            .map(|trump| BotAction::Trump { trump })
+            .collect(),
+        Decision::Card => view
+            .legal_cards()
+            .into_iter()
+            .map(|card| BotAction::Card { card })
+            .collect(),
+    }
+}
+
+/// A card played into the current trick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrickPlay {
+    pub player_id: Uuid,
+    pub card: Card,
+}
+
+/// Everything a bot is told when it has to decide
+///
+/// Built from the seat's `SeatView`, so it never holds the other hands.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionRequest {
+    /// Echoed back in the reply
+    pub request_id: Uuid,
+    pub game_id: Uuid,
+    pub decision: Decision,
+    /// The bot's seat
+    pub seat: usize,
+    /// Player id of each seat
+    pub players: Vec<Uuid>,
+    pub round_number: i32,
+    /// Seat of the dealer
+    pub dealer: usize,
+    pub cards_dealt: i32,
+    /// Cards the bot still holds
+    pub hand: Vec<Card>,
+    /// Bids placed so far, by seat
+    pub bids: Vec<Option<i32>>,
+    pub trump: Option<Trump>,
+    /// Plays so far in the trick being played
+    pub trick: Vec<TrickPlay>,
+    /// Tricks won so far this round, by seat
+    pub tricks_won: Vec<i32>,
+    /// Game scores from the rounds already scored, by seat
+    pub scores: Vec<i32>,
+    /// The reply must name one of these
+    pub legal_actions: Vec<BotAction>,
+    /// Milliseconds the bot has to reply
+    pub deadline_ms: u64,
+}
+
+impl DecisionRequest {
+    /// Describe a decision to a bot
+    ///
+    /// This function is PURE - it copies the seat's view and lists the legal
+    /// actions for the decision, under a fresh request id.
+    pub fn new(game_id: Uuid, decision: Decision, view: &SeatView, deadline: Duration) -> Self {
+        DecisionRequest {
+            request_id: Uuid::new_v4(),
+            game_id,
+            decision,
+            seat: view.seat,
+            players: view.players.clone(),
+            round_number: view.round_number,
+            dealer: view.dealer,
+            cards_dealt: view.cards_dealt,
+            hand: view.hand.clone(),
+            bids: view.bids.clone(),
+            trump: view.trump,
+            trick: view
+                .current_trick()
+                .iter()
+                .map(|(card, player_id)| TrickPlay {
+                    player_id: *player_id,
+                    card: *card,
+                })
+                .collect(),
+            tricks_won: (0..view.player_count())
+                .map(|seat| view.tricks_won(seat))
+                .collect(),
+            scores: view.scores.clone(),
+            legal_actions: legal_actions(view, decision),
+            deadline_ms: deadline.as_millis() as u64,
+        }
+    }
+}
+
+/// A bot's answer to a decision request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BotReply {
+    pub request_id: Uuid,
+    pub action: BotAction,
+}
+
+/// The reference bot's answer: the first legal action
+///
+/// This function is PURE - it is the simplest bot that follows the protocol,
+/// served by the `nommie-bot` binary and used in tests.
+pub fn reference_reply(request: &DecisionRequest) -> Option<BotReply> {
+    request.legal_actions.first().map(|action| BotReply {
+        request_id: request.request_id,
+        action: *action,
+    })
+}
+
+/// Generate the secret a bot authenticates with
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Hash a bot token for storage
+///
+/// This function is PURE - only the SHA-256 of a token is ever stored.
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Ask a bot for its decision
+///
+/// Fails if the bot cannot be reached, misses the deadline or names an action
+/// that is not legal, leaving the caller to decide for it.
+pub async fn ask(bot: &bots::Model, request: &DecisionRequest) -> Result<BotAction, String> {
+    let deadline = Duration::from_millis(request.deadline_ms);
+    let reply = match &bot.callback_url {
+        Some(url) => post_request(url, request, deadline).await?,
+        None => send_over_socket(bot.id, request, deadline).await?,
+    };
+
+    if reply.request_id != request.request_id {
+        return Err("Bot replied to a different request".to_string());
+    }
+    if !request.legal_actions.contains(&reply.action) {
+        return Err(format!("Bot chose an illegal action: {:?}", reply.action));
+    }
+    Ok(reply.action)
+}
+
+async fn post_request(
+    url: &str,
+    request: &DecisionRequest,
+    deadline: Duration,
+) -> Result<BotReply, String> {
+    // Addresses written into the URL are not resolved, so check them here
+    check_callback_url(url).await?;
+
+    // Redirects are not followed, as they could lead anywhere
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    let client = CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .redirect(redirect::Policy::none())
+            .dns_resolver(PublicResolver)
+            .build()
+            .unwrap_or_default()
+    });
+
+    let response = match client
+        .post(url)
+        .timeout(deadline)
+        .json(request)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+    {
+        Ok(response) => response,
+        Err(e) if e.is_timeout() => return Err("Bot did not reply in time".to_string()),
+        Err(e) => return Err(format!("Failed to reach bot: {e}")),
+    };
+    match response.json().await {
+        Ok(reply) => Ok(reply),
+        Err(e) if e.is_timeout() => Err("Bot did not reply in time".to_string()),
+        Err(e) => Err(format!("Failed to read bot reply: {e}")),
+    }
+}
+
+/// Open WebSocket connections, by bot, and the requests awaiting a reply
+#[derive(Default)]
+struct Sockets {
+    connections: HashMap<Uuid, (Uuid, mpsc::UnboundedSender<String>)>,
+    pending: HashMap<Uuid, oneshot::Sender<BotReply>>,
+}
+
+static SOCKETS: OnceLock<Mutex<Sockets>> = OnceLock::new();
+
+fn sockets() -> &'static Mutex<Sockets> {
+    SOCKETS.get_or_init(|| Mutex::new(Sockets::default()))
+}
+
+/// Register a bot's WebSocket connection
+///
+/// Returns the connection's id and the requests to send down it. A newer
+/// connection replaces an older one, whose receiver then closes.
+pub fn connect(bot_id: Uuid) -> (Uuid, mpsc::UnboundedReceiver<String>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let connection_id = Uuid::new_v4();
+    let mut sockets = sockets().lock().unwrap_or_else(|e| e.into_inner());
+    sockets.connections.insert(bot_id, (connection_id, sender));
+    (connection_id, receiver)
+}
+
+/// Forget a bot's WebSocket connection, unless a newer one replaced it
+pub fn disconnect(bot_id: Uuid, connection_id: Uuid) {
+    let mut sockets = sockets().lock().unwrap_or_else(|e| e.into_inner());
+    if sockets
+        .connections
+        .get(&bot_id)
+        .is_some_and(|(id, _)| *id == connection_id)
+    {
+        sockets.connections.remove(&bot_id);
+    }
+}
+
+/// Hand a reply received over a WebSocket to the request awaiting it
+pub fn receive_reply(message: &str) -> Result<(), String> {
+    let reply: BotReply = match serde_json::from_str(message) {
+        Ok(reply) => reply,
+        Err(e) => return Err(format!("Failed to parse bot reply: {e}")),
+    };
+    let waiting = {
+        let mut sockets = sockets().lock().unwrap_or_else(|e| e.into_inner());
+        sockets.pending.remove(&reply.request_id)
+    };
+    match waiting {
+        Some(waiting) => waiting
+            .send(reply)
+            .map_err(|_| "Reply arrived after the deadline".to_string()),
+        None => Err(format!(
+            "No request {} is awaiting a reply",
+            reply.request_id
+        )),
+    }
+}
+
+async fn send_over_socket(
+    bot_id: Uuid,
+    request: &DecisionRequest,
+    deadline: Duration,
+) -> Result<BotReply, String> {
+    let message = match serde_json::to_string(request) {
+        Ok(message) => message,
+        Err(e) => return Err(format!("Failed to serialize decision request: {e}")),
+    };
+
+    let (sender, receiver) = oneshot::channel();
+    {
+        let mut sockets = sockets().lock().unwrap_or_else(|e| e.into_inner());
+        let Some((_, connection)) = sockets.connections.get(&bot_id) else {
+            return Err("Bot is not connected".to_string());
+        };
+        if connection.send(message).is_err() {
+            return Err("Bot is not connected".to_string());
+        }
+        sockets.pending.insert(request.request_id, sender);
+    }
+
+    let reply = tokio::time::timeout(deadline, receiver).await;
+    sockets()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .pending
+        .remove(&request.request_id);
+    match reply {
+        Ok(Ok(reply)) => Ok(reply),
+        Ok(Err(_)) => Err("Bot disconnected".to_string()),
+        Err(_) => Err("Bot did not reply in time".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_management::engine::{Action, GameEngine};
+    use crate::game_management::rules::RuleSet;
+
+    fn bidding_view() -> SeatView {
+        let player_ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let mut engine = GameEngine::new(RuleSet::standard(), &player_ids, 3).unwrap();
+        for player_id in &player_ids {
+            engine
+                .apply(Action::Ready {
+                    player_id: *player_id,
+                })
+                .unwrap();
+        }
+        let seat = engine.current_turn().unwrap();
+        SeatView::new(&engine, seat).unwrap()
+    }
+
+    #[test]
+    fn test_request_lists_legal_actions() {
+        let view = bidding_view();
+        let request = DecisionRequest::new(
+            Uuid::new_v4(),
+            Decision::Bid,
+            &view,
+            Duration::from_millis(250),
+        );
+        let bids: Vec<BotAction> = view
+            .legal_bids()
+            .into_iter()
+            .map(|bid| BotAction::Bid { bid })
+            .collect();
+        assert_eq!(request.legal_actions, bids);
+        assert_eq!(request.hand, view.hand);
+        assert_eq!(request.deadline_ms, 250);
+        assert!(request.trick.is_empty());
+
+        let trumps = DecisionRequest::new(Uuid::new_v4(), Decision::Trump, &view, Duration::ZERO);
+        assert_eq!(trumps.legal_actions.len(), Trump::ALL.len());
+    }
+
+    #[test]
+    fn test_wire_format() {
+        let reply = BotReply {
+            request_id: Uuid::nil(),
+            action: BotAction::Card {
+                card: "TH".parse().unwrap(),
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(reply).unwrap(),
+            serde_json::json!({
+                "request_id": Uuid::nil(),
+                "action": { "type": "card", "card": "TH" }
+            })
+        );
+
+        let view = bidding_view();
+        let request = DecisionRequest::new(Uuid::nil(), Decision::Bid, &view, Duration::ZERO);
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["decision"], "bid");
+        assert_eq!(
+            json["legal_actions"][0],
+            serde_json::json!({ "type": "bid", "bid": 0 })
+        );
+        let parsed: DecisionRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, request);
+    }
+
+    #[test]
+    fn test_reference_reply_is_legal() {
+        let view = bidding_view();
+        let request = DecisionRequest::new(Uuid::new_v4(), Decision::Card, &view, Duration::ZERO);
+        let reply = reference_reply(&request).unwrap();
+        assert_eq!(reply.request_id, request.request_id);
+        assert!(request.legal_actions.contains(&reply.action));
+    }
+
+    #[test]
+    fn test_only_public_addresses_are_called() {
+        for ip in ["93.184.216.34", "2606:4700::1111"] {
+            assert!(public_address(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!public_address(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn test_tokens_are_stored_hashed() {
+        let token = generate_token();
+        assert_eq!(token.len(), 64);
+        assert_ne!(generate_token(), token);
+        assert_eq!(hash_token(&token), hash_token(&token));
+        assert_ne!(hash_token(&token), token);
+    }
+}
@@ -0,0 +1,410 @@
+//! Cards module
+//!
+//! This module contains the strongly typed card domain model (`Card`,
+//! `Rank`, `Suit`, `Trump`) and its string notation ("AS", "TD", "NoTrump").
+//! It depends only on std and serde.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A card suit, written as a single letter in card notation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Suit {
+    Spades,
+    Hearts,
+    Diamonds,
+    Clubs,
+}
+
+impl Suit {
+    /// All suits, in the order used when building a fresh deck
+    pub const ALL: [Suit; 4] = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
+
+    /// Single-letter notation for this suit
+    pub fn as_char(self) -> char {
+        match self {
+            Suit::Spades => 'S',
+            Suit::Hearts => 'H',
+            Suit::Diamonds => 'D',
+            Suit::Clubs => 'C',
+        }
+    }
+
+    /// Parse a suit from its single-letter notation
+    pub fn from_char(c: char) -> Option<Suit> {
+        match c {
+            'S' => Some(Suit::Spades),
+            'H' => Some(Suit::Hearts),
+            'D' => Some(Suit::Diamonds),
+            'C' => Some(Suit::Clubs),
+            _ => None,
+        }
+    }
+
+    /// Full name of this suit, as used by the trump selection API
+    pub fn name(self) -> &'static str {
+        match self {
+            Suit::Spades => "Spades",
+            Suit::Hearts => "Hearts",
+            Suit::Diamonds => "Diamonds",
+            Suit::Clubs => "Clubs",
+        }
+    }
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
+
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Suit::from_char(c).ok_or(ParseCardError::InvalidSuit(c)),
+            _ => Err(ParseCardError::InvalidLength(s.to_string())),
+        }
+    }
+}
+
+/// A card rank, ordered from lowest (Two) to highest (Ace)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Rank {
+    /// All ranks, from lowest to highest
+    pub const ALL: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+
+    /// Numeric value for comparison (2=2, 3=3, ..., A=14)
+    pub fn value(self) -> i32 {
+        self as i32 + 2
+    }
+
+    /// Single-character notation for this rank
+    pub fn as_char(self) -> char {
+        match self {
+            Rank::Two => '2',
+            Rank::Three => '3',
+            Rank::Four => '4',
+            Rank::Five => '5',
+            Rank::Six => '6',
+            Rank::Seven => '7',
+            Rank::Eight => '8',
+            Rank::Nine => '9',
+            Rank::Ten => 'T',
+            Rank::Jack => 'J',
+            Rank::Queen => 'Q',
+            Rank::King => 'K',
+            Rank::Ace => 'A',
+        }
+    }
+
+    /// Parse a rank from its single-character notation
+    pub fn from_char(c: char) -> Option<Rank> {
+        Rank::ALL.into_iter().find(|rank| rank.as_char() == c)
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
+
+/// A playing card, written as rank followed by suit (e.g. "AS", "TD", "2C")
+///
+/// Serializes to and from the same two-character notation, so any value that
+/// deserializes into a `Card` is guaranteed to be a real card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Card {
+    pub rank: Rank,
+    pub suit: Suit,
+}
+
+impl Card {
+    pub fn new(rank: Rank, suit: Suit) -> Self {
+        Self { rank, suit }
+    }
+
+    /// Build an ordered (unshuffled) standard 52-card deck
+    pub fn full_deck() -> Vec<Card> {
+        Suit::ALL
+            .into_iter()
+            .flat_map(|suit| Rank::ALL.into_iter().map(move |rank| Card::new(rank, suit)))
+            .collect()
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.rank, self.suit)
+    }
+}
+
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let (rank, suit) = match (chars.next(), chars.next(), chars.next()) {
+            (Some(rank), Some(suit), None) => (rank, suit),
+            _ => return Err(ParseCardError::InvalidLength(s.to_string())),
+        };
+
+        let rank = Rank::from_char(rank).ok_or(ParseCardError::InvalidRank(rank))?;
+        let suit = Suit::from_char(suit).ok_or(ParseCardError::InvalidSuit(suit))?;
+
+        Ok(Card { rank, suit })
+    }
+}
+
+impl TryFrom<String> for Card {
+    type Error = ParseCardError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Card> for String {
+    fn from(card: Card) -> Self {
+        card.to_string()
+    }
+}
+
+/// Error returned when a string is not valid card notation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    InvalidLength(String),
+    InvalidRank(char),
+    InvalidSuit(char),
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseCardError::InvalidLength(s) => write!(
+                f,
+                "Invalid card format '{s}'. Use format like '5S', 'AH', 'KD'"
+            ),
+            ParseCardError::InvalidRank(c) => write!(f, "Invalid card rank '{c}'"),
+            ParseCardError::InvalidSuit(c) => write!(f, "Invalid card suit '{c}'"),
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+/// The trump for a round: either one suit, or no trump at all
+///
+/// Serializes to and from the names used by the trump selection API
+/// ("Spades", "Hearts", "Diamonds", "Clubs", "NoTrump").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Trump {
+    Suit(Suit),
+    NoTrump,
+}
+
+impl Trump {
+    /// Every trump a round can be played with
+    pub const ALL: [Trump; 5] = [
+        Trump::Suit(Suit::Spades),
+        Trump::Suit(Suit::Hearts),
+        Trump::Suit(Suit::Diamonds),
+        Trump::Suit(Suit::Clubs),
+        Trump::NoTrump,
+    ];
+
+    /// The trump suit, or None when playing no trump
+    pub fn suit(self) -> Option<Suit> {
+        match self {
+            Trump::Suit(suit) => Some(suit),
+            Trump::NoTrump => None,
+        }
+    }
+
+    /// API name for this trump
+    pub fn name(self) -> &'static str {
+        match self {
+            Trump::Suit(suit) => suit.name(),
+            Trump::NoTrump => "NoTrump",
+        }
+    }
+}
+
+impl fmt::Display for Trump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Trump {
+    type Err = ParseTrumpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Trump::ALL
+            .into_iter()
+            .find(|trump| trump.name() == s)
+            .ok_or_else(|| ParseTrumpError(s.to_string()))
+    }
+}
+
+impl TryFrom<String> for Trump {
+    type Error = ParseTrumpError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Trump> for String {
+    fn from(trump: Trump) -> Self {
+        trump.name().to_string()
+    }
+}
+
+/// Error returned when a string is not a valid trump name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTrumpError(pub String);
+
+impl fmt::Display for ParseTrumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid trump suit '{}'. Must be one of: Spades, Hearts, Diamonds, Clubs, NoTrump",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseTrumpError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_round_trips_notation() {
+        for notation in ["AS", "TD", "2C", "KH", "9S"] {
+            let card: Card = notation.parse().unwrap();
+            assert_eq!(card.to_string(), notation);
+        }
+    }
+
+    #[test]
+    fn test_card_parse_fields() {
+        let card: Card = "TD".parse().unwrap();
+        assert_eq!(card.rank, Rank::Ten);
+        assert_eq!(card.suit, Suit::Diamonds);
+    }
+
+    #[test]
+    fn test_card_parse_rejects_malformed() {
+        assert_eq!(
+            "A".parse::<Card>(),
+            Err(ParseCardError::InvalidLength("A".to_string()))
+        );
+        assert_eq!(
+            "ASS".parse::<Card>(),
+            Err(ParseCardError::InvalidLength("ASS".to_string()))
+        );
+        assert_eq!("1S".parse::<Card>(), Err(ParseCardError::InvalidRank('1')));
+        assert_eq!("AX".parse::<Card>(), Err(ParseCardError::InvalidSuit('X')));
+        assert_eq!("as".parse::<Card>(), Err(ParseCardError::InvalidRank('a')));
+    }
+
+    #[test]
+    fn test_rank_ordering_and_values() {
+        assert!(Rank::Ace > Rank::King);
+        assert!(Rank::Three > Rank::Two);
+        assert_eq!(Rank::Two.value(), 2);
+        assert_eq!(Rank::Ten.value(), 10);
+        assert_eq!(Rank::Ace.value(), 14);
+    }
+
+    #[test]
+    fn test_card_serde_uses_notation() {
+        let card = Card::new(Rank::Queen, Suit::Hearts);
+        assert_eq!(serde_json::to_string(&card).unwrap(), "\"QH\"");
+
+        let parsed: Card = serde_json::from_str("\"7C\"").unwrap();
+        assert_eq!(parsed, Card::new(Rank::Seven, Suit::Clubs));
+
+        assert!(serde_json::from_str::<Card>("\"ZZ\"").is_err());
+    }
+
+    #[test]
+    fn test_full_deck_has_52_unique_cards() {
+        let deck = Card::full_deck();
+        assert_eq!(deck.len(), 52);
+
+        let unique: std::collections::HashSet<_> = deck.iter().collect();
+        assert_eq!(unique.len(), 52);
+    }
+
+    #[test]
+    fn test_trump_round_trips_api_names() {
+        for trump in Trump::ALL {
+            let parsed: Trump = trump.to_string().parse().unwrap();
+            assert_eq!(parsed, trump);
+        }
+
+        assert_eq!("Spades".parse::<Trump>(), Ok(Trump::Suit(Suit::Spades)));
+        assert_eq!("NoTrump".parse::<Trump>(), Ok(Trump::NoTrump));
+        assert!("hearts".parse::<Trump>().is_err());
+        assert!("H".parse::<Trump>().is_err());
+    }
+
+    #[test]
+    fn test_trump_serde_uses_api_names() {
+        assert_eq!(
+            serde_json::to_string(&Trump::Suit(Suit::Clubs)).unwrap(),
+            "\"Clubs\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Trump>("\"NoTrump\"").unwrap(),
+            Trump::NoTrump
+        );
+        assert!(serde_json::from_str::<Trump>("\"Stars\"").is_err());
+    }
+
+    #[test]
+    fn test_trump_suit() {
+        assert_eq!(Trump::Suit(Suit::Hearts).suit(), Some(Suit::Hearts));
+        assert_eq!(Trump::NoTrump.suit(), None);
+    }
+}
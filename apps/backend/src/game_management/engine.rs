@@ -0,0 +1,1215 @@
+//! Game engine module
+//!
+//! This module contains the in-memory game engine. A `GameEngine` holds the
+//! whole state of one game - seats, hands, bids, trump, tricks and scores -
+//! and advances it one `Action` at a time, returning the `Event`s that
+//! happened. It depends only on domain types and std, so complete games can
+//! be played without a database. `orchestration` loads an engine from the
+//! database, applies an action and persists the resulting events.
+//!
+//! Turn order follows the table: the dealer rotates every round, the player
+//! after the dealer bids first and leads the first trick, the highest bidder
+//! (first in bidding order on a tie) chooses trump, and each trick winner
+//! leads the next trick.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::game_management::bidding::{
+    deal_hands, deck_for_seed, find_highest_bidder, forbidden_hook_bid, is_valid_bid,
+    round_deal_seed, BidError,
+};
+use crate::game_management::cards::{Card, Trump};
+use crate::game_management::rules::RuleSet;
+use crate::game_management::tricks::{
+    apply_play_logic, determine_trick_winner, get_lead_suit_from_trick, validate_follow_suit_rule,
+    ApplyPlayError, TrickState,
+};
+
+/// Where a game is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Waiting,
+    Bidding,
+    TrumpSelection,
+    Playing,
+    Completed,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Waiting => write!(f, "waiting"),
+            Phase::Bidding => write!(f, "bidding"),
+            Phase::TrumpSelection => write!(f, "trump selection"),
+            Phase::Playing => write!(f, "playing"),
+            Phase::Completed => write!(f, "completed"),
+        }
+    }
+}
+
+/// A seat at the table; seats are kept in turn order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Seat {
+    pub player_id: Uuid,
+    pub is_ready: bool,
+}
+
+/// A trick in the current round
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trick {
+    pub trick_number: i32,
+    /// Seat that leads the trick
+    pub leader: usize,
+    /// Plays in order as (card, player_id)
+    pub plays: Vec<(Card, Uuid)>,
+    pub winner: Option<Uuid>,
+}
+
+/// The round currently being played
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Round {
+    pub round_number: i32,
+    /// Seat of the dealer
+    pub dealer: usize,
+    pub cards_dealt: i32,
+    pub deal_seed: i64,
+    /// Cards still held, by seat
+    pub hands: Vec<Vec<Card>>,
+    /// Bids placed so far, by seat
+    pub bids: Vec<Option<i32>>,
+    pub trump: Option<Trump>,
+    /// Tricks played so far; the last one is open until it has a winner
+    pub tricks: Vec<Trick>,
+}
+
+impl Round {
+    /// Seat that bids first and leads the first trick (the one after the dealer)
+    pub fn first_to_act(&self) -> usize {
+        (self.dealer + 1) % self.bids.len()
+    }
+
+    /// Bids placed so far, in bidding order
+    pub fn bids_in_order(&self) -> Vec<i32> {
+        let player_count = self.bids.len();
+        (0..player_count)
+            .filter_map(|offset| self.bids[(self.first_to_act() + offset) % player_count])
+            .collect()
+    }
+
+    /// The trick still being played, if any
+    pub fn open_trick(&self) -> Option<&Trick> {
+        self.tricks.last().filter(|trick| trick.winner.is_none())
+    }
+
+    /// Tricks won so far by a player
+    pub fn tricks_won(&self, player_id: Uuid) -> i32 {
+        self.tricks
+            .iter()
+            .filter(|trick| trick.winner == Some(player_id))
+            .count() as i32
+    }
+}
+
+/// Something a player does at the table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Action {
+    Ready { player_id: Uuid },
+    Bid { player_id: Uuid, bid: i32 },
+    ChooseTrump { player_id: Uuid, trump: Trump },
+    Play { player_id: Uuid, card: Card },
+}
+
+/// One player's outcome for a scored round
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundResult {
+    pub player_id: Uuid,
+    pub bid: i32,
+    pub tricks_won: i32,
+    pub points: i32,
+}
+
+/// Something that happened as the result of an action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    PlayerJoined {
+        player_id: Uuid,
+        user_id: Uuid,
+    },
+    PlayerReady {
+        player_id: Uuid,
+    },
+    GameStarted,
+    RoundDealt {
+        round_number: i32,
+        dealer_id: Uuid,
+        cards_dealt: i32,
+        deal_seed: i64,
+        /// Hands by seat as (player_id, cards)
+        hands: Vec<(Uuid, Vec<Card>)>,
+    },
+    BidPlaced {
+        player_id: Uuid,
+        bid: i32,
+    },
+    TrumpChosen {
+        player_id: Uuid,
+        trump: Trump,
+    },
+    CardPlayed {
+        player_id: Uuid,
+        card: Card,
+        trick_number: i32,
+        play_order: i32,
+    },
+    TrickWon {
+        trick_number: i32,
+        winner_id: Uuid,
+    },
+    RoundScored {
+        round_number: i32,
+        results: Vec<RoundResult>,
+    },
+    GameCompleted {
+        /// Final totals by seat as (player_id, score)
+        final_scores: Vec<(Uuid, i32)>,
+    },
+}
+
+/// Reasons the engine rejects an action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleError {
+    /// The player does not have a seat at this table
+    UnknownPlayer,
+    /// The player has already marked themselves ready
+    AlreadyReady,
+    /// The action does not belong to the current phase
+    WrongPhase { expected: Phase },
+    /// Another seat is due to act
+    NotYourTurn { phase: Phase },
+    /// The bid is outside the range allowed for the current round
+    BidOutOfRange {
+        min_bid: i32,
+        max_bid: i32,
+        cards_dealt: i32,
+    },
+    /// The dealer tried to make total bids equal the cards dealt
+    HookRule { forbidden_bid: i32 },
+    /// The card cannot be played from this hand
+    IllegalPlay(ApplyPlayError),
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::UnknownPlayer => write!(f, "You are not a participant in this game"),
+            RuleError::AlreadyReady => write!(f, "Player is already ready"),
+            RuleError::WrongPhase {
+                expected: Phase::Waiting,
+            } => write!(f, "Game is not in waiting state"),
+            RuleError::WrongPhase { expected } => write!(f, "Game is not in {expected} phase"),
+            RuleError::NotYourTurn {
+                phase: Phase::TrumpSelection,
+            } => write!(f, "Only the highest bidder can choose trump"),
+            RuleError::NotYourTurn {
+                phase: Phase::Playing,
+            } => write!(f, "It's not your turn to play"),
+            RuleError::NotYourTurn { .. } => write!(f, "It's not your turn to bid"),
+            RuleError::BidOutOfRange {
+                min_bid,
+                max_bid,
+                cards_dealt,
+            } => BidError::OutOfRange {
+                min_bid: *min_bid,
+                max_bid: *max_bid,
+                cards_dealt: *cards_dealt,
+            }
+            .fmt(f),
+            RuleError::HookRule { forbidden_bid } => BidError::HookRule {
+                forbidden_bid: *forbidden_bid,
+            }
+            .fmt(f),
+            RuleError::IllegalPlay(ApplyPlayError::CardNotInHand) => {
+                write!(f, "You don't have that card in your hand")
+            }
+            RuleError::IllegalPlay(ApplyPlayError::FollowSuitViolation) => {
+                write!(f, "You must follow suit if possible")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// The complete state of one game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameEngine {
+    pub rules: RuleSet,
+    pub seats: Vec<Seat>,
+    pub phase: Phase,
+    pub round: Option<Round>,
+    /// Running totals, by seat
+    pub scores: Vec<i32>,
+    /// Base seed each round's deal seed is derived from
+    pub seed: i64,
+}
+
+impl GameEngine {
+    /// Seat players (in turn order) at a table that is waiting to start
+    pub fn new(rules: RuleSet, player_ids: &[Uuid], seed: i64) -> Result<Self, String> {
+        rules.validate()?;
+        if player_ids.len() > rules.player_count {
+            return Err(format!(
+                "Too many players for a table of {}",
+                rules.player_count
+            ));
+        }
+
+        Ok(Self {
+            seats: player_ids
+                .iter()
+                .map(|&player_id| Seat {
+                    player_id,
+                    is_ready: false,
+                })
+                .collect(),
+            scores: vec![0; player_ids.len()],
+            phase: Phase::Waiting,
+            round: None,
+            rules,
+            seed,
+        })
+    }
+
+    /// Seat index of a player
+    pub fn seat_of(&self, player_id: Uuid) -> Option<usize> {
+        self.seats
+            .iter()
+            .position(|seat| seat.player_id == player_id)
+    }
+
+    /// Seat that must act next, if the game is waiting on one
+    pub fn current_turn(&self) -> Option<usize> {
+        let round = self.round.as_ref()?;
+        let player_count = self.seats.len();
+
+        match self.phase {
+            Phase::Bidding => {
+                let placed = round.bids.iter().flatten().count();
+                Some((round.first_to_act() + placed) % player_count)
+            }
+            Phase::TrumpSelection => self.trump_chooser(),
+            Phase::Playing => round
+                .open_trick()
+                .map(|trick| (trick.leader + trick.plays.len()) % player_count),
+            Phase::Waiting | Phase::Completed => None,
+        }
+    }
+
+    /// Player that must act next, if the game is waiting on one
+    pub fn current_player(&self) -> Option<Uuid> {
+        self.current_turn().map(|seat| self.seats[seat].player_id)
+    }
+
+    /// Seat of the highest bidder once every bid is in
+    pub fn trump_chooser(&self) -> Option<usize> {
+        let round = self.round.as_ref()?;
+        if round.bids.iter().any(Option::is_none) {
+            return None;
+        }
+
+        let player_count = self.seats.len();
+        let bids: Vec<(i32, Uuid)> = (0..player_count)
+            .map(|offset| (round.first_to_act() + offset) % player_count)
+            .map(|seat| {
+                (
+                    round.bids[seat].unwrap_or_default(),
+                    self.seats[seat].player_id,
+                )
+            })
+            .collect();
+
+        let (_, highest_bidder_id, _) = find_highest_bidder(&bids);
+        highest_bidder_id.and_then(|player_id| self.seat_of(player_id))
+    }
+
+    /// Bid the next bidder may not make under the dealer hook rule
+    pub fn forbidden_bid(&self) -> Option<i32> {
+        if self.phase != Phase::Bidding {
+            return None;
+        }
+        let round = self.round.as_ref()?;
+        forbidden_hook_bid(&self.rules, round.cards_dealt, &round.bids_in_order())
+    }
+
+    /// Cards a seat may legally play into the open trick
+    pub fn legal_cards(&self, seat: usize) -> Vec<Card> {
+        let Some(round) = &self.round else {
+            return Vec::new();
+        };
+        let hand = &round.hands[seat];
+
+        match round
+            .open_trick()
+            .and_then(|trick| get_lead_suit_from_trick(&trick.plays))
+        {
+            Some(lead_suit) => hand
+                .iter()
+                .filter(|card| validate_follow_suit_rule(card, lead_suit, hand))
+                .copied()
+                .collect(),
+            None => hand.clone(),
+        }
+    }
+
+    /// Apply an action, returning the events it caused
+    ///
+    /// The engine is left unchanged when the action is rejected.
+    pub fn apply(&mut self, action: Action) -> Result<Vec<Event>, RuleError> {
+        match action {
+            Action::Ready { player_id } => self.ready(player_id),
+            Action::Bid { player_id, bid } => self.bid(player_id, bid),
+            Action::ChooseTrump { player_id, trump } => self.choose_trump(player_id, trump),
+            Action::Play { player_id, card } => self.play(player_id, card),
+        }
+    }
+
+    /// Start the game once every seat is filled and ready
+    ///
+    /// Returns no events if the table is not ready yet.
+    pub fn start_if_ready(&mut self) -> Vec<Event> {
+        let table_full = self.seats.len() == self.rules.player_count;
+        let all_ready = self.seats.iter().all(|seat| seat.is_ready);
+        if self.phase != Phase::Waiting || !table_full || !all_ready {
+            return Vec::new();
+        }
+
+        let mut events = Vec::new();
+        self.emit(&mut events, Event::GameStarted);
+        let dealt = self.deal_event(1);
+        self.emit(&mut events, dealt);
+        events
+    }
+
+    /// Rebuild an engine by applying logged events in order
+    pub fn replay<'a>(
+        rules: RuleSet,
+        seed: i64,
+        events: impl IntoIterator<Item = &'a Event>,
+    ) -> Result<Self, String> {
+        let mut engine = Self::new(rules, &[], seed)?;
+        for event in events {
+            engine.apply_event(event);
+        }
+        Ok(engine)
+    }
+
+    /// Update the state for an event that has already happened
+    ///
+    /// Events are not validated: every change to the engine state goes through
+    /// here, both when an action is applied and when a log is replayed.
+    pub fn apply_event(&mut self, event: &Event) {
+        match event {
+            Event::PlayerJoined { player_id, .. } => {
+                self.seats.push(Seat {
+                    player_id: *player_id,
+                    is_ready: false,
+                });
+                self.scores.push(0);
+            }
+            Event::PlayerReady { player_id } => {
+                if let Some(seat) = self.seat_of(*player_id) {
+                    self.seats[seat].is_ready = true;
+                }
+            }
+            Event::GameStarted => {
+                self.scores = vec![0; self.seats.len()];
+            }
+            Event::RoundDealt {
+                round_number,
+                dealer_id,
+                cards_dealt,
+                deal_seed,
+                hands,
+            } => {
+                let mut seat_hands = vec![Vec::new(); self.seats.len()];
+                for (player_id, hand) in hands {
+                    if let Some(seat) = self.seat_of(*player_id) {
+                        seat_hands[seat] = hand.clone();
+                    }
+                }
+
+                self.round = Some(Round {
+                    round_number: *round_number,
+                    dealer: self.seat_of(*dealer_id).unwrap_or_default(),
+                    cards_dealt: *cards_dealt,
+                    deal_seed: *deal_seed,
+                    hands: seat_hands,
+                    bids: vec![None; self.seats.len()],
+                    trump: None,
+                    tricks: Vec::new(),
+                });
+                self.phase = Phase::Bidding;
+            }
+            Event::BidPlaced { player_id, bid } => {
+                let seat = self.seat_of(*player_id);
+                if let (Some(round), Some(seat)) = (self.round.as_mut(), seat) {
+                    round.bids[seat] = Some(*bid);
+                    if round.bids.iter().all(Option::is_some) {
+                        self.phase = Phase::TrumpSelection;
+                    }
+                }
+            }
+            Event::TrumpChosen { trump, .. } => {
+                if let Some(round) = self.round.as_mut() {
+                    round.trump = Some(*trump);
+                    round.tricks.push(Trick {
+                        trick_number: 1,
+                        leader: round.first_to_act(),
+                        plays: Vec::new(),
+                        winner: None,
+                    });
+                    self.phase = Phase::Playing;
+                }
+            }
+            Event::CardPlayed {
+                player_id, card, ..
+            } => {
+                let seat = self.seat_of(*player_id);
+                if let (Some(round), Some(seat)) = (self.round.as_mut(), seat) {
+                    round.hands[seat].retain(|held| held != card);
+                    if let Some(trick) = round.tricks.last_mut() {
+                        trick.plays.push((*card, *player_id));
+                    }
+                }
+            }
+            Event::TrickWon {
+                trick_number,
+                winner_id,
+            } => {
+                let leader = self.seat_of(*winner_id).unwrap_or_default();
+                if let Some(round) = self.round.as_mut() {
+                    if let Some(trick) = round.tricks.last_mut() {
+                        trick.winner = Some(*winner_id);
+                    }
+                    if *trick_number < round.cards_dealt {
+                        round.tricks.push(Trick {
+                            trick_number: trick_number + 1,
+                            leader,
+                            plays: Vec::new(),
+                            winner: None,
+                        });
+                    }
+                }
+            }
+            Event::RoundScored { results, .. } => {
+                for result in results {
+                    if let Some(seat) = self.seat_of(result.player_id) {
+                        self.scores[seat] += result.points;
+                    }
+                }
+            }
+            Event::GameCompleted { .. } => {
+                self.phase = Phase::Completed;
+            }
+        }
+    }
+
+    /// Apply an event and add it to the events caused by the current action
+    fn emit(&mut self, events: &mut Vec<Event>, event: Event) {
+        self.apply_event(&event);
+        events.push(event);
+    }
+
+    /// Seat of a player acting in the given phase, checking the phase first
+    fn acting_seat(&self, player_id: Uuid, phase: Phase) -> Result<usize, RuleError> {
+        if self.phase != phase {
+            return Err(RuleError::WrongPhase { expected: phase });
+        }
+        let seat = self.seat_of(player_id).ok_or(RuleError::UnknownPlayer)?;
+        if phase != Phase::Waiting && self.current_turn() != Some(seat) {
+            return Err(RuleError::NotYourTurn { phase });
+        }
+        Ok(seat)
+    }
+
+    fn ready(&mut self, player_id: Uuid) -> Result<Vec<Event>, RuleError> {
+        let seat = self.acting_seat(player_id, Phase::Waiting)?;
+        if self.seats[seat].is_ready {
+            return Err(RuleError::AlreadyReady);
+        }
+
+        let mut events = Vec::new();
+        self.emit(&mut events, Event::PlayerReady { player_id });
+        events.extend(self.start_if_ready());
+        Ok(events)
+    }
+
+    fn bid(&mut self, player_id: Uuid, bid: i32) -> Result<Vec<Event>, RuleError> {
+        self.acting_seat(player_id, Phase::Bidding)?;
+        let Some(round) = &self.round else {
+            return Err(RuleError::WrongPhase {
+                expected: Phase::Bidding,
+            });
+        };
+
+        if !is_valid_bid(bid, round.cards_dealt, &self.rules) {
+            return Err(RuleError::BidOutOfRange {
+                min_bid: self.rules.min_bid,
+                max_bid: self.rules.max_bid_for_round(round.cards_dealt),
+                cards_dealt: round.cards_dealt,
+            });
+        }
+        if self.forbidden_bid() == Some(bid) {
+            return Err(RuleError::HookRule { forbidden_bid: bid });
+        }
+
+        let mut events = Vec::new();
+        self.emit(&mut events, Event::BidPlaced { player_id, bid });
+        Ok(events)
+    }
+
+    fn choose_trump(&mut self, player_id: Uuid, trump: Trump) -> Result<Vec<Event>, RuleError> {
+        self.acting_seat(player_id, Phase::TrumpSelection)?;
+
+        let mut events = Vec::new();
+        self.emit(&mut events, Event::TrumpChosen { player_id, trump });
+        Ok(events)
+    }
+
+    fn play(&mut self, player_id: Uuid, card: Card) -> Result<Vec<Event>, RuleError> {
+        let seat = self.acting_seat(player_id, Phase::Playing)?;
+        let Some(round) = &self.round else {
+            return Err(RuleError::WrongPhase {
+                expected: Phase::Playing,
+            });
+        };
+        let (Some(trump), Some(trick)) = (round.trump, round.open_trick()) else {
+            return Err(RuleError::WrongPhase {
+                expected: Phase::Playing,
+            });
+        };
+
+        apply_play_logic(
+            &card,
+            &TrickState::new(trick.plays.clone()),
+            &round.hands[seat],
+        )
+        .map_err(RuleError::IllegalPlay)?;
+
+        let trick_number = trick.trick_number;
+        let play_order = trick.plays.len() as i32;
+        let mut events = Vec::new();
+        self.emit(
+            &mut events,
+            Event::CardPlayed {
+                player_id,
+                card,
+                trick_number,
+                play_order,
+            },
+        );
+
+        let Some(plays) = self
+            .round
+            .as_ref()
+            .and_then(Round::open_trick)
+            .map(|trick| trick.plays.clone())
+            .filter(|plays| plays.len() == self.seats.len())
+        else {
+            return Ok(events);
+        };
+
+        // Every seat has played: settle the trick
+        let winner_id =
+            determine_trick_winner(&plays, trump).expect("a complete trick always has plays");
+        self.emit(
+            &mut events,
+            Event::TrickWon {
+                trick_number,
+                winner_id,
+            },
+        );
+
+        if self.round.as_ref().and_then(Round::open_trick).is_none() {
+            self.finish_round(&mut events);
+        }
+
+        Ok(events)
+    }
+
+    /// Score the finished round, then deal the next one or end the game
+    fn finish_round(&mut self, events: &mut Vec<Event>) {
+        let Some(round) = &self.round else {
+            return;
+        };
+        let scoring_rule = self.rules.scoring_rule();
+        let round_number = round.round_number;
+
+        let results: Vec<RoundResult> = self
+            .seats
+            .iter()
+            .enumerate()
+            .map(|(seat, Seat { player_id, .. })| {
+                let bid = round.bids[seat].unwrap_or_default();
+                let tricks_won = round.tricks_won(*player_id);
+                RoundResult {
+                    player_id: *player_id,
+                    bid,
+                    tricks_won,
+                    points: scoring_rule.round_points(tricks_won, bid),
+                }
+            })
+            .collect();
+
+        self.emit(
+            events,
+            Event::RoundScored {
+                round_number,
+                results,
+            },
+        );
+
+        if self.rules.is_game_complete(round_number) {
+            let final_scores = self
+                .seats
+                .iter()
+                .map(|seat| seat.player_id)
+                .zip(self.scores.iter().copied())
+                .collect();
+            self.emit(events, Event::GameCompleted { final_scores });
+        } else {
+            let dealt = self.deal_event(round_number + 1);
+            self.emit(events, dealt);
+        }
+    }
+
+    /// Deal a round from its derived seed
+    fn deal_event(&self, round_number: i32) -> Event {
+        let cards_dealt = self
+            .rules
+            .cards_dealt(round_number)
+            .expect("rounds are only dealt from the schedule");
+        let dealer = self.rules.dealer_index_for_round(round_number);
+        let deal_seed = round_deal_seed(self.seed, round_number);
+        let hands = deal_hands(&deck_for_seed(deal_seed), self.seats.len(), cards_dealt)
+            .expect("validated rule sets always fit in the deck");
+
+        Event::RoundDealt {
+            round_number,
+            dealer_id: self.seats[dealer].player_id,
+            cards_dealt,
+            deal_seed,
+            hands: self
+                .seats
+                .iter()
+                .map(|seat| seat.player_id)
+                .zip(hands)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_management::rules::{MAX_PLAYERS, MIN_PLAYERS};
+    use crate::game_management::scoring::ScoringVariant;
+
+    fn new_game(rules: RuleSet, seed: i64) -> GameEngine {
+        let player_ids: Vec<Uuid> = (0..rules.player_count).map(|_| Uuid::new_v4()).collect();
+        GameEngine::new(rules, &player_ids, seed).unwrap()
+    }
+
+    fn started_game(rules: RuleSet, seed: i64) -> GameEngine {
+        let mut engine = new_game(rules, seed);
+        for seat in 0..engine.seats.len() {
+            let player_id = engine.seats[seat].player_id;
+            engine.apply(Action::Ready { player_id }).unwrap();
+        }
+        assert_eq!(engine.phase, Phase::Bidding);
+        engine
+    }
+
+    /// Pick the next action for whoever is due to act, always making a legal move
+    fn next_action(engine: &GameEngine) -> Action {
+        let seat = engine.current_turn().unwrap();
+        let player_id = engine.seats[seat].player_id;
+        let round = engine.round.as_ref().unwrap();
+
+        match engine.phase {
+            Phase::Bidding => {
+                let bid = if engine.forbidden_bid() == Some(1) {
+                    0
+                } else {
+                    1
+                };
+                Action::Bid { player_id, bid }
+            }
+            Phase::TrumpSelection => Action::ChooseTrump {
+                player_id,
+                trump: Trump::ALL[round.round_number as usize % Trump::ALL.len()],
+            },
+            Phase::Playing => Action::Play {
+                player_id,
+                card: engine.legal_cards(seat)[0],
+            },
+            Phase::Waiting | Phase::Completed => unreachable!("no one acts"),
+        }
+    }
+
+    fn play_to_completion(engine: &mut GameEngine) -> Vec<Event> {
+        let mut events = Vec::new();
+        while engine.phase != Phase::Completed {
+            let action = next_action(engine);
+            events.extend(engine.apply(action).unwrap());
+        }
+        events
+    }
+
+    #[test]
+    fn test_full_game_at_every_table_size() {
+        for player_count in MIN_PLAYERS..=MAX_PLAYERS {
+            let rules = RuleSet::for_players(player_count).unwrap();
+            let mut rules_with_hook = rules.clone();
+            rules_with_hook.dealer_hook = true;
+
+            for rules in [rules, rules_with_hook] {
+                let mut engine = started_game(rules.clone(), player_count as i64);
+                let events = play_to_completion(&mut engine);
+
+                let mut totals = vec![0; player_count];
+                let mut rounds_scored = 0;
+                let mut tricks_won = 0;
+                for event in &events {
+                    if let Event::RoundScored { results, .. } = event {
+                        rounds_scored += 1;
+                        for (total, result) in totals.iter_mut().zip(results) {
+                            *total += result.points;
+                            tricks_won += result.tricks_won;
+                        }
+                    }
+                }
+
+                assert_eq!(rounds_scored, rules.total_rounds());
+                assert_eq!(tricks_won, rules.round_schedule.iter().sum::<i32>());
+                assert_eq!(engine.scores, totals);
+                assert_eq!(engine.current_turn(), None);
+                assert!(engine
+                    .round
+                    .as_ref()
+                    .unwrap()
+                    .hands
+                    .iter()
+                    .all(Vec::is_empty));
+
+                let Some(Event::GameCompleted { final_scores }) = events.last() else {
+                    panic!("game should end with GameCompleted");
+                };
+                let final_totals: Vec<i32> = final_scores.iter().map(|(_, score)| *score).collect();
+                assert_eq!(final_totals, totals);
+            }
+        }
+    }
+
+    #[test]
+    fn test_standard_game_plays_26_rounds() {
+        let mut engine = started_game(RuleSet::standard(), 42);
+        let mut dealt = vec![engine.round.as_ref().unwrap().cards_dealt];
+        let events = play_to_completion(&mut engine);
+
+        dealt.extend(events.iter().filter_map(|event| match event {
+            Event::RoundDealt { cards_dealt, .. } => Some(*cards_dealt),
+            _ => None,
+        }));
+        assert_eq!(dealt, RuleSet::standard().round_schedule);
+        assert_eq!(dealt.len(), 26);
+    }
+
+    #[test]
+    fn test_game_starts_when_every_seat_is_ready() {
+        let mut engine = new_game(RuleSet::standard(), 1);
+        let last = engine.seats[3].player_id;
+
+        for seat in 0..3 {
+            let player_id = engine.seats[seat].player_id;
+            let events = engine.apply(Action::Ready { player_id }).unwrap();
+            assert_eq!(events, vec![Event::PlayerReady { player_id }]);
+        }
+        assert_eq!(engine.phase, Phase::Waiting);
+
+        let first = engine.seats[0].player_id;
+        assert_eq!(
+            engine.apply(Action::Ready { player_id: first }),
+            Err(RuleError::AlreadyReady)
+        );
+
+        let events = engine.apply(Action::Ready { player_id: last }).unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1], Event::GameStarted);
+        assert!(matches!(
+            events[2],
+            Event::RoundDealt {
+                round_number: 1,
+                cards_dealt: 13,
+                ..
+            }
+        ));
+        assert_eq!(engine.phase, Phase::Bidding);
+    }
+
+    #[test]
+    fn test_partial_table_does_not_start() {
+        let rules = RuleSet::standard();
+        let player_ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let mut engine = GameEngine::new(rules, &player_ids, 1).unwrap();
+
+        for player_id in player_ids {
+            engine.apply(Action::Ready { player_id }).unwrap();
+        }
+        assert_eq!(engine.phase, Phase::Waiting);
+        assert!(engine.start_if_ready().is_empty());
+    }
+
+    #[test]
+    fn test_deal_matches_round_seed() {
+        let engine = started_game(RuleSet::standard(), 7);
+        let round = engine.round.as_ref().unwrap();
+
+        assert_eq!(round.deal_seed, round_deal_seed(7, 1));
+        let expected = deal_hands(&deck_for_seed(round.deal_seed), 4, 13).unwrap();
+        assert_eq!(round.hands, expected);
+    }
+
+    #[test]
+    fn test_dealer_rotates_and_player_after_dealer_acts_first() {
+        let mut engine = started_game(RuleSet::standard(), 3);
+
+        for round_number in 1..=5 {
+            let round = engine.round.as_ref().unwrap();
+            assert_eq!(round.round_number, round_number);
+            assert_eq!(round.dealer, (round_number as usize - 1) % 4);
+            assert_eq!(engine.current_turn(), Some((round.dealer + 1) % 4));
+
+            // Finish the round
+            while engine.round.as_ref().unwrap().round_number == round_number {
+                let action = next_action(&engine);
+                engine.apply(action).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_hook_binds_the_dealer_in_later_rounds() {
+        let mut rules = RuleSet::standard();
+        rules.dealer_hook = true;
+        let mut engine = started_game(rules, 17);
+        while engine.round.as_ref().unwrap().round_number < 3 {
+            let action = next_action(&engine);
+            engine.apply(action).unwrap();
+        }
+
+        // Everyone else bids freely; the dealer bids last and is held to it
+        let dealer = engine.round.as_ref().unwrap().dealer;
+        assert_eq!(dealer, 2);
+        for _ in 0..3 {
+            assert_ne!(engine.current_turn(), Some(dealer));
+            assert_eq!(engine.forbidden_bid(), None);
+            let action = next_action(&engine);
+            engine.apply(action).unwrap();
+        }
+        assert_eq!(engine.current_turn(), Some(dealer));
+        assert_eq!(engine.forbidden_bid(), Some(8));
+        assert_eq!(
+            engine.apply(Action::Bid {
+                player_id: engine.seats[dealer].player_id,
+                bid: 8,
+            }),
+            Err(RuleError::HookRule { forbidden_bid: 8 })
+        );
+    }
+
+    #[test]
+    fn test_first_trick_led_by_player_after_dealer() {
+        let mut engine = started_game(RuleSet::standard(), 5);
+        while engine.phase != Phase::Playing {
+            let action = next_action(&engine);
+            engine.apply(action).unwrap();
+        }
+
+        let round = engine.round.as_ref().unwrap();
+        assert_eq!(round.open_trick().unwrap().leader, 1);
+        assert_eq!(engine.current_turn(), Some(1));
+    }
+
+    #[test]
+    fn test_trick_winner_leads_next_trick() {
+        let mut engine = started_game(RuleSet::standard(), 11);
+        while engine.phase != Phase::Playing {
+            let action = next_action(&engine);
+            engine.apply(action).unwrap();
+        }
+
+        let mut winner = None;
+        for _ in 0..4 {
+            let action = next_action(&engine);
+            for event in engine.apply(action).unwrap() {
+                if let Event::TrickWon { winner_id, .. } = event {
+                    winner = Some(winner_id);
+                }
+            }
+        }
+
+        let winner = winner.expect("a full trick has a winner");
+        assert_eq!(engine.current_player(), Some(winner));
+        assert_eq!(
+            engine
+                .round
+                .as_ref()
+                .unwrap()
+                .open_trick()
+                .unwrap()
+                .trick_number,
+            2
+        );
+    }
+
+    #[test]
+    fn test_trump_chooser_tie_goes_to_first_bidder() {
+        let mut engine = started_game(RuleSet::standard(), 9);
+
+        // Seat 1 bids first; seats 2 and 0 tie with seat 1 on the highest bid
+        for bid in [4, 2, 3, 4] {
+            let player_id = engine.current_player().unwrap();
+            engine.apply(Action::Bid { player_id, bid }).unwrap();
+        }
+
+        assert_eq!(engine.phase, Phase::TrumpSelection);
+        assert_eq!(engine.trump_chooser(), Some(1));
+
+        let not_chooser = engine.seats[0].player_id;
+        assert_eq!(
+            engine.apply(Action::ChooseTrump {
+                player_id: not_chooser,
+                trump: Trump::NoTrump,
+            }),
+            Err(RuleError::NotYourTurn {
+                phase: Phase::TrumpSelection
+            })
+        );
+    }
+
+    #[test]
+    fn test_bid_errors() {
+        let mut rules = RuleSet::standard();
+        rules.dealer_hook = true;
+        let mut engine = started_game(rules, 13);
+
+        let out_of_turn = engine.seats[0].player_id;
+        assert_eq!(
+            engine.apply(Action::Bid {
+                player_id: out_of_turn,
+                bid: 1,
+            }),
+            Err(RuleError::NotYourTurn {
+                phase: Phase::Bidding
+            })
+        );
+
+        let bidder = engine.current_player().unwrap();
+        let error = engine
+            .apply(Action::Bid {
+                player_id: bidder,
+                bid: 14,
+            })
+            .unwrap_err();
+        assert_eq!(
+            error,
+            RuleError::BidOutOfRange {
+                min_bid: 0,
+                max_bid: 13,
+                cards_dealt: 13,
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Bid must be between 0 and 13 when 13 cards are dealt"
+        );
+
+        assert_eq!(
+            engine.apply(Action::Bid {
+                player_id: Uuid::new_v4(),
+                bid: 1,
+            }),
+            Err(RuleError::UnknownPlayer)
+        );
+
+        for bid in [3, 3, 3] {
+            let player_id = engine.current_player().unwrap();
+            engine.apply(Action::Bid { player_id, bid }).unwrap();
+        }
+
+        // The dealer bids last and may not make the total 13
+        let dealer = engine.current_player().unwrap();
+        assert_eq!(dealer, engine.seats[0].player_id);
+        assert_eq!(engine.forbidden_bid(), Some(4));
+        let before = engine.clone();
+        assert_eq!(
+            engine.apply(Action::Bid {
+                player_id: dealer,
+                bid: 4,
+            }),
+            Err(RuleError::HookRule { forbidden_bid: 4 })
+        );
+        assert_eq!(engine, before);
+
+        engine
+            .apply(Action::Bid {
+                player_id: dealer,
+                bid: 5,
+            })
+            .unwrap();
+        assert_eq!(engine.phase, Phase::TrumpSelection);
+    }
+
+    #[test]
+    fn test_wrong_phase_errors() {
+        let mut engine = new_game(RuleSet::standard(), 17);
+        let player_id = engine.seats[0].player_id;
+
+        let error = engine.apply(Action::Bid { player_id, bid: 1 }).unwrap_err();
+        assert_eq!(
+            error,
+            RuleError::WrongPhase {
+                expected: Phase::Bidding
+            }
+        );
+        assert_eq!(error.to_string(), "Game is not in bidding phase");
+
+        let mut engine = started_game(RuleSet::standard(), 17);
+        let player_id = engine.current_player().unwrap();
+        let error = engine.apply(Action::Ready { player_id }).unwrap_err();
+        assert_eq!(error.to_string(), "Game is not in waiting state");
+
+        let card = engine.round.as_ref().unwrap().hands[1][0];
+        let error = engine.apply(Action::Play { player_id, card }).unwrap_err();
+        assert_eq!(error.to_string(), "Game is not in playing phase");
+    }
+
+    #[test]
+    fn test_illegal_plays_are_rejected() {
+        let mut engine = started_game(RuleSet::standard(), 19);
+        while engine.phase != Phase::Playing {
+            let action = next_action(&engine);
+            engine.apply(action).unwrap();
+        }
+
+        // A card from someone else's hand
+        let leader = engine.current_turn().unwrap();
+        let player_id = engine.seats[leader].player_id;
+        let round = engine.round.as_ref().unwrap();
+        let foreign_card = round.hands[(leader + 1) % 4][0];
+        let error = engine
+            .apply(Action::Play {
+                player_id,
+                card: foreign_card,
+            })
+            .unwrap_err();
+        assert_eq!(error, RuleError::IllegalPlay(ApplyPlayError::CardNotInHand));
+        assert_eq!(error.to_string(), "You don't have that card in your hand");
+
+        // Lead, then look for a follower holding both the lead suit and another suit
+        let action = next_action(&engine);
+        engine.apply(action).unwrap();
+        let follower = engine.current_turn().unwrap();
+        let lead_suit = engine.round.as_ref().unwrap().tricks[0].plays[0].0.suit;
+        let hand = engine.round.as_ref().unwrap().hands[follower].clone();
+
+        if let (true, Some(off_suit)) = (
+            hand.iter().any(|card| card.suit == lead_suit),
+            hand.iter().find(|card| card.suit != lead_suit),
+        ) {
+            let error = engine
+                .apply(Action::Play {
+                    player_id: engine.seats[follower].player_id,
+                    card: *off_suit,
+                })
+                .unwrap_err();
+            assert_eq!(
+                error,
+                RuleError::IllegalPlay(ApplyPlayError::FollowSuitViolation)
+            );
+            assert_eq!(error.to_string(), "You must follow suit if possible");
+            assert!(engine
+                .legal_cards(follower)
+                .iter()
+                .all(|card| card.suit == lead_suit));
+        }
+    }
+
+    #[test]
+    fn test_scores_follow_scoring_variant() {
+        let mut rules = RuleSet::standard();
+        rules.scoring = ScoringVariant::ExactBid;
+        let mut engine = started_game(rules.clone(), 23);
+        let events = play_to_completion(&mut engine);
+
+        for event in events {
+            if let Event::RoundScored { results, .. } = event {
+                for result in results {
+                    assert_eq!(
+                        result.points,
+                        rules
+                            .scoring_rule()
+                            .round_points(result.tricks_won, result.bid)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_engine_round_trips_through_serde() {
+        let mut engine = started_game(RuleSet::standard(), 29);
+        for _ in 0..6 {
+            let action = next_action(&engine);
+            engine.apply(action).unwrap();
+        }
+
+        let json = serde_json::to_string(&engine).unwrap();
+        let restored: GameEngine = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, engine);
+    }
+
+    #[test]
+    fn test_replay_rebuilds_engine_at_every_step() {
+        let rules = RuleSet::for_players(3).unwrap();
+        let mut engine = new_game(rules.clone(), 31);
+        let mut log: Vec<Event> = engine
+            .seats
+            .iter()
+            .map(|seat| Event::PlayerJoined {
+                player_id: seat.player_id,
+                user_id: Uuid::new_v4(),
+            })
+            .collect();
+        assert_eq!(GameEngine::replay(rules.clone(), 31, &log).unwrap(), engine);
+
+        for seat in 0..engine.seats.len() {
+            let player_id = engine.seats[seat].player_id;
+            log.extend(engine.apply(Action::Ready { player_id }).unwrap());
+            assert_eq!(GameEngine::replay(rules.clone(), 31, &log).unwrap(), engine);
+        }
+
+        while engine.phase != Phase::Completed {
+            let action = next_action(&engine);
+            log.extend(engine.apply(action).unwrap());
+            assert_eq!(GameEngine::replay(rules.clone(), 31, &log).unwrap(), engine);
+        }
+    }
+}
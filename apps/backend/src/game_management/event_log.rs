@@ -0,0 +1,323 @@
+//! Event log module
+//!
+//! Every event a game produces is appended to `game_events`, in the same
+//! transaction as the action that caused it. The log is never updated or
+//! deleted from, so replaying it through the engine shows exactly how a game
+//! reached its state. This is how disputed games are audited.
+
+use chrono::{DateTime, FixedOffset, Utc};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::dto::game_snapshot::{
+    DealRevealSnapshot, GameInfo, GameSnapshot, PlayerSnapshot, RoundBidSnapshot,
+    RoundScoreSnapshot, RoundSnapshot, TrickPlaySnapshot, TrickSnapshot, UserSnapshot,
+};
+use crate::entity::{game_events, games, users};
+use crate::game_management::engine::{Event, GameEngine, Phase, RoundResult, Trick};
+use crate::game_management::fairness::deal_commitment;
+use crate::game_management::orchestration::stored_phase;
+use crate::game_management::rules::{turn_order_from_index, RuleSet};
+
+/// An event as written to the log
+///
+/// Alongside the engine event, the log keeps the storage details the event
+/// produced, so a replay can rebuild the same snapshot the live tables give.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoggedEvent {
+    #[serde(flatten)]
+    pub event: Event,
+    /// Salt of the published deal commitment (round_dealt only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deal_salt: Option<String>,
+    /// Id of the stored round (round_dealt only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub round_id: Option<Uuid>,
+    /// Id of the stored trick (first card_played of each trick only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trick_id: Option<Uuid>,
+}
+
+impl From<Event> for LoggedEvent {
+    fn from(event: Event) -> Self {
+        Self {
+            event,
+            deal_salt: None,
+            round_id: None,
+            trick_id: None,
+        }
+    }
+}
+
+/// Append events to the end of a game's log
+///
+/// The game's version is moved on to the last event appended.
+pub(crate) async fn append_events(
+    game_id: Uuid,
+    entries: &[LoggedEvent],
+    db: &(impl ConnectionTrait + std::marker::Send),
+) -> Result<(), String> {
+    let last_sequence = last_sequence(game_id, db).await?;
+
+    let now: DateTime<FixedOffset> = Utc::now().into();
+    for (sequence, entry) in (last_sequence + 1..).zip(entries) {
+        let payload = match serde_json::to_value(entry) {
+            Ok(payload) => payload,
+            Err(e) => return Err(format!("Failed to encode game event: {e}")),
+        };
+        let event_type = payload["type"].as_str().unwrap_or_default().to_string();
+
+        let row = game_events::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            game_id: Set(game_id),
+            sequence: Set(sequence),
+            event_type: Set(event_type),
+            payload: Set(payload),
+            created_at: Set(now),
+        };
+        if let Err(e) = row.insert(db).await {
+            return Err(format!("Failed to record game event: {e}"));
+        }
+    }
+
+    // The game's version follows its log
+    let version = last_sequence + entries.len() as i32;
+    if let Err(e) = games::Entity::update_many()
+        .col_expr(games::Column::Version, Expr::value(version))
+        .filter(games::Column::Id.eq(game_id))
+        .exec(db)
+        .await
+    {
+        return Err(format!("Failed to update game version: {e}"));
+    }
+
+    Ok(())
+}
+
+/// Sequence number of a game's latest event, or 0 for an empty log
+pub(crate) async fn last_sequence(
+    game_id: Uuid,
+    db: &(impl ConnectionTrait + std::marker::Send),
+) -> Result<i32, String> {
+    match game_events::Entity::find()
+        .filter(game_events::Column::GameId.eq(game_id))
+        .order_by_desc(game_events::Column::Sequence)
+        .one(db)
+        .await
+    {
+        Ok(last) => Ok(last.map(|entry| entry.sequence).unwrap_or(0)),
+        Err(e) => Err(format!("Failed to fetch game events: {e}")),
+    }
+}
+
+/// Load the entries of a game's log after a sequence number, in order
+pub(crate) async fn load_events_after(
+    game_id: Uuid,
+    after: i32,
+    db: &(impl ConnectionTrait + std::marker::Send),
+) -> Result<Vec<game_events::Model>, String> {
+    match game_events::Entity::find()
+        .filter(game_events::Column::GameId.eq(game_id))
+        .filter(game_events::Column::Sequence.gt(after))
+        .order_by_asc(game_events::Column::Sequence)
+        .all(db)
+        .await
+    {
+        Ok(entries) => Ok(entries),
+        Err(e) => Err(format!("Failed to fetch game events: {e}")),
+    }
+}
+
+/// Load a game's log in order
+pub(crate) async fn load_events(
+    game_id: Uuid,
+    db: &(impl ConnectionTrait + std::marker::Send),
+) -> Result<Vec<game_events::Model>, String> {
+    match game_events::Entity::find()
+        .filter(game_events::Column::GameId.eq(game_id))
+        .order_by_asc(game_events::Column::Sequence)
+        .all(db)
+        .await
+    {
+        Ok(entries) => Ok(entries),
+        Err(e) => Err(format!("Failed to fetch game events: {e}")),
+    }
+}
+
+/// Rebuild a game's snapshot from its event log
+///
+/// This function is PURE - it replays the log through the engine and builds
+/// the same snapshot `build_game_snapshot` reads from the live tables. The
+/// users are only needed for display details (name, email, AI flag).
+pub fn replay_snapshot(
+    game_id: Uuid,
+    rules: &RuleSet,
+    log: &[game_events::Model],
+    users: &[users::Model],
+) -> Result<GameSnapshot, String> {
+    let mut engine = GameEngine::new(rules.clone(), &[], 0)?;
+    let mut phase = "bidding".to_string();
+    let mut user_ids = Vec::new();
+    let mut started_at = None;
+    let mut round_id = None;
+    let mut deal_salt = None;
+    let mut trick_ids = Vec::new();
+    let mut bids = Vec::new();
+    let mut round_results: Option<Vec<RoundResult>> = None;
+
+    for entry in log {
+        let logged: LoggedEvent = serde_json::from_value(entry.payload.clone())
+            .map_err(|e| format!("Corrupt event {} in log: {e}", entry.sequence))?;
+
+        match &logged.event {
+            Event::PlayerJoined { user_id, .. } => user_ids.push(*user_id),
+            Event::GameStarted => started_at = Some(entry.created_at),
+            Event::RoundDealt { .. } => {
+                round_id = logged.round_id;
+                deal_salt = logged.deal_salt.clone();
+                trick_ids.clear();
+                bids.clear();
+                round_results = None;
+            }
+            Event::BidPlaced { player_id, bid } => bids.push(RoundBidSnapshot {
+                player_id: *player_id,
+                bid: *bid,
+            }),
+            Event::CardPlayed { .. } => trick_ids.extend(logged.trick_id),
+            Event::RoundScored { results, .. } => round_results = Some(results.clone()),
+            _ => {}
+        }
+
+        engine.apply_event(&logged.event);
+        if let Some(stored) = stored_phase(engine.phase) {
+            phase = stored.to_string();
+        }
+    }
+
+    let current_turn = engine.current_turn().map(turn_order_from_index);
+    let hands = engine.round.as_ref().map(|round| &round.hands);
+
+    let players = engine
+        .seats
+        .iter()
+        .enumerate()
+        .zip(&user_ids)
+        .filter_map(|((seat, seat_state), user_id)| {
+            let user = users.iter().find(|user| user.id == *user_id)?;
+            Some(PlayerSnapshot {
+                id: seat_state.player_id,
+                user_id: user.id,
+                turn_order: Some(turn_order_from_index(seat)),
+                is_ready: seat_state.is_ready,
+                is_ai: user.is_ai,
+                total_score: engine.scores[seat],
+                hand: hands.map(|hands| hands[seat].iter().map(ToString::to_string).collect()),
+                user: UserSnapshot {
+                    id: user.id,
+                    email: user.email.clone(),
+                    name: user.name.clone(),
+                },
+            })
+        })
+        .collect();
+
+    let current_round = engine.round.as_ref().map(|round| {
+        let trick_snapshot = |trick: &Trick| TrickSnapshot {
+            id: trick_ids
+                .get(trick.trick_number as usize - 1)
+                .copied()
+                .unwrap_or_default(),
+            trick_number: trick.trick_number,
+            winner_player_id: trick.winner,
+            plays: (0..)
+                .zip(&trick.plays)
+                .map(|(play_order, (card, player_id))| TrickPlaySnapshot {
+                    player_id: *player_id,
+                    card: card.to_string(),
+                    play_order,
+                })
+                .collect(),
+        };
+
+        RoundSnapshot {
+            id: round_id.unwrap_or_default(),
+            round_number: round.round_number,
+            phase: phase.clone(),
+            dealer_player_id: Some(engine.seats[round.dealer].player_id),
+            trump_suit: round.trump,
+            cards_dealt: round.cards_dealt,
+            bids: bids.clone(),
+            current_bidder_turn: current_turn,
+            forbidden_bid: engine.forbidden_bid(),
+            // Trick rows are stored with their first play
+            current_trick: round
+                .open_trick()
+                .filter(|trick| !trick.plays.is_empty())
+                .map(trick_snapshot),
+            completed_tricks: round
+                .tricks
+                .iter()
+                .filter(|trick| trick.winner.is_some())
+                .map(trick_snapshot)
+                .collect(),
+            current_player_turn: match engine.phase {
+                Phase::Bidding | Phase::Playing => engine.current_player(),
+                _ => None,
+            },
+            deal_commitment: deal_salt
+                .as_ref()
+                .map(|salt| deal_commitment(round.deal_seed, salt)),
+            deal_reveal: deal_salt
+                .as_ref()
+                .filter(|_| round_results.is_some())
+                .map(|salt| DealRevealSnapshot {
+                    seed: round.deal_seed.to_string(),
+                    salt: salt.clone(),
+                }),
+            round_scores: round_results
+                .iter()
+                .flatten()
+                .map(|result| RoundScoreSnapshot {
+                    player_id: result.player_id,
+                    tricks_won: result.tricks_won,
+                    bid: result.bid,
+                    points: result.points,
+                })
+                .collect(),
+        }
+    });
+
+    let state = match engine.phase {
+        Phase::Waiting => "waiting",
+        Phase::Completed => "completed",
+        _ => "started",
+    };
+
+    Ok(GameSnapshot {
+        game: GameInfo {
+            id: game_id,
+            state: state.to_string(),
+            phase,
+            current_turn,
+            created_at: log
+                .first()
+                .map(|entry| entry.created_at)
+                .unwrap_or_default(),
+            updated_at: log.last().map(|entry| entry.created_at).unwrap_or_default(),
+            started_at,
+            version: log.last().map(|entry| entry.sequence).unwrap_or(0),
+        },
+        player_count: engine.seats.len(),
+        max_players: rules.player_count,
+        trump_chooser_id: match engine.phase {
+            Phase::TrumpSelection => engine.current_player(),
+            _ => None,
+        },
+        players,
+        current_round,
+    })
+}
@@ -0,0 +1,127 @@
+//! Fairness module
+//!
+//! This module contains the commit-reveal scheme that lets players check the
+//! server did not stack the deck. When a round is dealt the server publishes
+//! `SHA-256("{seed}:{salt}")` as a lowercase hex commitment. Once the round
+//! is scored the seed and salt are revealed, and anyone can rebuild the deck
+//! from them and compare it against the hands that were dealt.
+
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use crate::game_management::bidding::{deal_hands, deck_for_seed};
+use crate::game_management::cards::Card;
+
+/// Number of random bytes in a deal salt
+pub const SALT_BYTES: usize = 16;
+
+/// Generate a fresh random salt for a deal commitment
+///
+/// Returns the salt as lowercase hex.
+pub fn generate_salt(rng: &mut impl Rng) -> String {
+    let bytes: [u8; SALT_BYTES] = rng.gen();
+    hex::encode(bytes)
+}
+
+/// Build the published commitment for a deal seed and salt
+///
+/// This function is PURE - it hashes `"{seed}:{salt}"` with SHA-256 and
+/// returns the digest as lowercase hex.
+pub fn deal_commitment(seed: i64, salt: &str) -> String {
+    hex::encode(Sha256::digest(format!("{seed}:{salt}")))
+}
+
+/// Check a revealed deal against its commitment and the hands dealt
+///
+/// This function is PURE - it confirms the seed and salt hash to the
+/// published commitment, then rebuilds the deck from the seed and checks that
+/// every seat (in turn order) was dealt exactly the cards the deck gives it.
+/// Card order within a hand is ignored.
+pub fn verify_deal(
+    commitment: &str,
+    seed: i64,
+    salt: &str,
+    cards_dealt: i32,
+    dealt_hands: &[Vec<Card>],
+) -> Result<(), String> {
+    if deal_commitment(seed, salt) != commitment.to_ascii_lowercase() {
+        return Err("Revealed seed and salt do not match the published commitment".to_string());
+    }
+
+    let deck = deck_for_seed(seed);
+    let expected_hands = deal_hands(&deck, dealt_hands.len(), cards_dealt)?;
+
+    for (seat, (expected, dealt)) in expected_hands.iter().zip(dealt_hands).enumerate() {
+        let mut expected = expected.clone();
+        let mut dealt = dealt.clone();
+        expected.sort_by_key(|card| card.to_string());
+        dealt.sort_by_key(|card| card.to_string());
+
+        if expected != dealt {
+            return Err(format!(
+                "Hand dealt to seat {seat} does not match the revealed seed"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_deal_commitment() {
+        // Known SHA-256 digests so clients can check the format independently
+        assert_eq!(
+            deal_commitment(42, "abc"),
+            "4c611c47136d0d52ef7b1d6508b6d77b5d245237732f0e539c3b8a614d0586e5"
+        );
+        assert_eq!(
+            deal_commitment(-7, "00ff"),
+            "e9e09752e88ac554466264ebb6239d66f546283ff6bd98c543ff44c05d943a29"
+        );
+        assert_ne!(deal_commitment(42, "abc"), deal_commitment(43, "abc"));
+        assert_ne!(deal_commitment(42, "abc"), deal_commitment(42, "abd"));
+    }
+
+    #[test]
+    fn test_generate_salt() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let salt = generate_salt(&mut rng);
+        assert_eq!(salt.len(), SALT_BYTES * 2);
+        assert!(salt.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(generate_salt(&mut rng), salt);
+    }
+
+    #[test]
+    fn test_verify_deal() {
+        let seed = 99;
+        let salt = "5a17";
+        let commitment = deal_commitment(seed, salt);
+        let mut hands = deal_hands(&deck_for_seed(seed), 4, 5).unwrap();
+
+        // Card order within a hand does not matter
+        hands[2].reverse();
+        assert_eq!(verify_deal(&commitment, seed, salt, 5, &hands), Ok(()));
+        assert_eq!(
+            verify_deal(&commitment.to_uppercase(), seed, salt, 5, &hands),
+            Ok(())
+        );
+
+        // A different seed or salt breaks the commitment
+        assert!(verify_deal(&commitment, seed + 1, salt, 5, &hands).is_err());
+        assert!(verify_deal(&commitment, seed, "5a18", 5, &hands).is_err());
+
+        // A swapped card is caught
+        let stray = deck_for_seed(seed)[51];
+        hands[1][0] = stray;
+        assert_eq!(
+            verify_deal(&commitment, seed, salt, 5, &hands),
+            Err("Hand dealt to seat 1 does not match the revealed seed".to_string())
+        );
+    }
+}
@@ -0,0 +1,501 @@
+//! Gym module
+//!
+//! A training environment over the rules engine for bots learning offline.
+//! An `Environment` plays one game in memory: `reset` deals a new game from a
+//! seed, `observe` shows a seat what it can see, and `step` applies the move
+//! of the seat due to act and reports the points each seat scored. Nothing
+//! here touches the database, so any number of environments can run side by
+//! side. The `nommie-gym` binary serves it as line-delimited JSON.
+//!
+//! Actions use the same format as the `bot_protocol`, so a bot trained here
+//! can play live games unchanged.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::game_management::ai::SeatView;
+use crate::game_management::bot_protocol::{legal_actions, BotAction, Decision};
+use crate::game_management::cards::{Card, Trump};
+use crate::game_management::engine::{Action, Event, GameEngine, Phase, RuleError};
+use crate::game_management::rules::{RuleSet, STANDARD_PLAYER_COUNT};
+use crate::game_management::scoring::ScoringVariant;
+use crate::game_management::tricks::ApplyPlayError;
+
+/// Rules for the games an environment deals
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GymConfig {
+    /// Number of seats at the table (3 to 7); defaults to the standard 4
+    pub player_count: Option<usize>,
+    /// Scoring rule; defaults to standard scoring
+    pub scoring: Option<ScoringVariant>,
+    /// Play with the dealer hook bidding rule; off by default
+    pub dealer_hook: Option<bool>,
+}
+
+impl GymConfig {
+    /// The rule set these options describe
+    pub fn rules(&self) -> Result<RuleSet, String> {
+        let rules = RuleSet::for_players(self.player_count.unwrap_or(STANDARD_PLAYER_COUNT))?;
+        Ok(RuleSet {
+            scoring: self.scoring.unwrap_or_default(),
+            dealer_hook: self.dealer_hook.unwrap_or_default(),
+            ..rules
+        })
+    }
+}
+
+/// Why a command failed
+///
+/// Serialized with a `kind` tag, so training code can tell a bad move from a
+/// bad request. Illegal plays carry the engine's `ApplyPlayError` reason.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GymError {
+    /// The line is not a valid command
+    InvalidCommand { message: String },
+    /// The config cannot be played
+    InvalidConfig { message: String },
+    /// No game has been dealt yet; call `reset` first
+    NotReset,
+    /// The seat is not at the table
+    UnknownSeat { seat: usize },
+    /// The game is over; call `reset` to deal another
+    GameOver,
+    /// The action answers a different decision from the one due
+    WrongDecision { expected: Decision },
+    /// The bid is outside the range allowed this round
+    BidOutOfRange {
+        min_bid: i32,
+        max_bid: i32,
+        cards_dealt: i32,
+    },
+    /// The bid is forbidden by the dealer hook rule
+    HookRule { forbidden_bid: i32 },
+    /// The card cannot be played
+    IllegalPlay { reason: ApplyPlayError },
+    /// The engine rejected the action for another reason
+    Rejected { message: String },
+}
+
+impl fmt::Display for GymError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GymError::InvalidCommand { message } => write!(f, "Invalid command: {message}"),
+            GymError::InvalidConfig { message } => write!(f, "Invalid config: {message}"),
+            GymError::NotReset => write!(f, "No game has been dealt; call reset first"),
+            GymError::UnknownSeat { seat } => write!(f, "Seat {seat} is not at the table"),
+            GymError::GameOver => write!(f, "The game is over; call reset to deal another"),
+            GymError::WrongDecision { expected } => write!(f, "Expected a {expected} action"),
+            GymError::BidOutOfRange {
+                min_bid,
+                max_bid,
+                cards_dealt,
+            } => RuleError::BidOutOfRange {
+                min_bid: *min_bid,
+                max_bid: *max_bid,
+                cards_dealt: *cards_dealt,
+            }
+            .fmt(f),
+            GymError::HookRule { forbidden_bid } => RuleError::HookRule {
+                forbidden_bid: *forbidden_bid,
+            }
+            .fmt(f),
+            GymError::IllegalPlay { reason } => RuleError::IllegalPlay(*reason).fmt(f),
+            GymError::Rejected { message } => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for GymError {}
+
+impl From<RuleError> for GymError {
+    fn from(error: RuleError) -> Self {
+        match error {
+            RuleError::BidOutOfRange {
+                min_bid,
+                max_bid,
+                cards_dealt,
+            } => GymError::BidOutOfRange {
+                min_bid,
+                max_bid,
+                cards_dealt,
+            },
+            RuleError::HookRule { forbidden_bid } => GymError::HookRule { forbidden_bid },
+            RuleError::IllegalPlay(reason) => GymError::IllegalPlay { reason },
+            other => GymError::Rejected {
+                message: other.to_string(),
+            },
+        }
+    }
+}
+
+/// A card played into the current trick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SeatPlay {
+    pub seat: usize,
+    pub card: Card,
+}
+
+/// What one seat can see of the game
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Observation {
+    pub seat: usize,
+    pub phase: Phase,
+    pub round_number: i32,
+    /// Seat of the dealer
+    pub dealer: usize,
+    pub cards_dealt: i32,
+    /// Cards the seat still holds
+    pub hand: Vec<Card>,
+    /// Bids placed so far, by seat
+    pub bids: Vec<Option<i32>>,
+    pub trump: Option<Trump>,
+    /// Plays so far in the trick being played
+    pub trick: Vec<SeatPlay>,
+    /// Tricks won so far this round, by seat
+    pub tricks_won: Vec<i32>,
+    /// Game scores from the rounds already scored, by seat
+    pub scores: Vec<i32>,
+    /// Seat due to act, if any
+    pub to_act: Option<usize>,
+    /// Actions this seat may take; empty unless it is due to act
+    pub legal_actions: Vec<BotAction>,
+}
+
+/// What a step did
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StepResult {
+    /// Seat whose action was applied
+    pub seat: usize,
+    /// Points scored by each seat in a round the step finished, else zeros
+    pub rewards: Vec<i32>,
+    /// Whether the game is over
+    pub done: bool,
+    /// Seat due to act next, if any
+    pub to_act: Option<usize>,
+    /// Actions the next seat may take
+    pub legal_actions: Vec<BotAction>,
+}
+
+/// One game played in memory
+#[derive(Debug, Clone)]
+pub struct Environment {
+    engine: GameEngine,
+}
+
+impl Environment {
+    /// Deal a new game with every seat ready
+    ///
+    /// The same seed and config always deal the same cards.
+    pub fn reset(seed: i64, config: &GymConfig) -> Result<Self, GymError> {
+        let invalid = |message: String| GymError::InvalidConfig { message };
+        let rules = config.rules().map_err(invalid)?;
+        let player_ids: Vec<Uuid> = (0..rules.player_count)
+            .map(|seat| Uuid::from_u128(seat as u128 + 1))
+            .collect();
+        let mut engine = GameEngine::new(rules, &player_ids, seed).map_err(invalid)?;
+        for player_id in player_ids {
+            engine.apply(Action::Ready { player_id })?;
+        }
+        Ok(Environment { engine })
+    }
+
+    /// Seat due to act, if the game is not over
+    pub fn to_act(&self) -> Option<usize> {
+        self.engine.current_turn()
+    }
+
+    /// Show a seat what it can see
+    pub fn observe(&self, seat: usize) -> Result<Observation, GymError> {
+        let view = SeatView::new(&self.engine, seat).ok_or(GymError::UnknownSeat { seat })?;
+        let to_act = self.to_act();
+
+        Ok(Observation {
+            seat,
+            phase: self.engine.phase,
+            round_number: view.round_number,
+            dealer: view.dealer,
+            cards_dealt: view.cards_dealt,
+            trick: view
+                .current_trick()
+                .iter()
+                .filter_map(|(card, player_id)| {
+                    let seat = self.engine.seat_of(*player_id)?;
+                    Some(SeatPlay { seat, card: *card })
+                })
+                .collect(),
+            tricks_won: (0..view.player_count())
+                .map(|seat| view.tricks_won(seat))
+                .collect(),
+            legal_actions: self.legal_actions_for(seat, &view),
+            hand: view.hand,
+            bids: view.bids,
+            trump: view.trump,
+            scores: view.scores,
+            to_act,
+        })
+    }
+
+    /// Apply the action of the seat due to act
+    ///
+    /// A rejected action leaves the game unchanged.
+    pub fn step(&mut self, action: BotAction) -> Result<StepResult, GymError> {
+        let seat = self.to_act().ok_or(GymError::GameOver)?;
+        let expected = Decision::for_phase(self.engine.phase).ok_or(GymError::GameOver)?;
+        if action.decision() != expected {
+            return Err(GymError::WrongDecision { expected });
+        }
+
+        let player_id = self.engine.seats[seat].player_id;
+        let action = match action {
+            BotAction::Bid { bid } => Action::Bid { player_id, bid },
+            BotAction::Trump { trump } => Action::ChooseTrump { player_id, trump },
+            BotAction::Card { card } => Action::Play { player_id, card },
+        };
+
+        let mut rewards = vec![0; self.engine.seats.len()];
+        for event in self.engine.apply(action)? {
+            if let Event::RoundScored { results, .. } = event {
+                for result in results {
+                    if let Some(seat) = self.engine.seat_of(result.player_id) {
+                        rewards[seat] = result.points;
+                    }
+                }
+            }
+        }
+
+        let to_act = self.to_act();
+        let legal_actions = match to_act {
+            Some(next) => self.observe(next)?.legal_actions,
+            None => Vec::new(),
+        };
+        Ok(StepResult {
+            seat,
+            rewards,
+            done: self.engine.phase == Phase::Completed,
+            to_act,
+            legal_actions,
+        })
+    }
+
+    fn legal_actions_for(&self, seat: usize, view: &SeatView) -> Vec<BotAction> {
+        match Decision::for_phase(self.engine.phase) {
+            Some(decision) if self.to_act() == Some(seat) => legal_actions(view, decision),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// A line of the `nommie-gym` protocol
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Command {
+    /// Deal a new game
+    Reset {
+        seed: i64,
+        #[serde(default)]
+        config: GymConfig,
+    },
+    /// Show a seat what it can see
+    Observe { seat: usize },
+    /// Apply the action of the seat due to act
+    Step { action: BotAction },
+}
+
+/// Serves one environment a command at a time
+#[derive(Debug, Default)]
+pub struct Gym {
+    environment: Option<Environment>,
+}
+
+impl Gym {
+    /// Answer one line of input with one line of JSON
+    ///
+    /// Successful answers carry `"ok": true`; failures carry `"ok": false`,
+    /// the `error` and a readable `message`.
+    pub fn handle_line(&mut self, line: &str) -> String {
+        let result = match serde_json::from_str(line) {
+            Ok(command) => self.handle(command),
+            Err(e) => Err(GymError::InvalidCommand {
+                message: e.to_string(),
+            }),
+        };
+        let reply = match result {
+            Ok(mut body) => {
+                body["ok"] = true.into();
+                body
+            }
+            Err(error) => serde_json::json!({
+                "ok": false,
+                "message": error.to_string(),
+                "error": error,
+            }),
+        };
+        reply.to_string()
+    }
+
+    /// Run a command, returning the JSON answer
+    pub fn handle(&mut self, command: Command) -> Result<serde_json::Value, GymError> {
+        match command {
+            Command::Reset { seed, config } => {
+                let environment = Environment::reset(seed, &config)?;
+                let to_act = environment.to_act();
+                let observation = match to_act {
+                    Some(seat) => Some(environment.observe(seat)?),
+                    None => None,
+                };
+                self.environment = Some(environment);
+                Ok(serde_json::json!({ "to_act": to_act, "observation": observation }))
+            }
+            Command::Observe { seat } => {
+                let environment = self.environment.as_ref().ok_or(GymError::NotReset)?;
+                let observation = environment.observe(seat)?;
+                Ok(serde_json::json!({ "observation": observation }))
+            }
+            Command::Step { action } => {
+                let environment = self.environment.as_mut().ok_or(GymError::NotReset)?;
+                let result = environment.step(action)?;
+                Ok(serde_json::to_value(result).unwrap_or_default())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_management::scoring::calculate_round_points;
+
+    #[test]
+    fn test_reset_is_reproducible() {
+        let config = GymConfig::default();
+        let first = Environment::reset(9, &config).unwrap();
+        let second = Environment::reset(9, &config).unwrap();
+        let seat = first.to_act().unwrap();
+        assert_eq!(first.observe(seat), second.observe(seat));
+
+        let observation = first.observe(seat).unwrap();
+        assert_eq!(observation.phase, Phase::Bidding);
+        assert_eq!(observation.hand.len(), 13);
+        assert!(!observation.legal_actions.is_empty());
+
+        // Only the seat due to act is offered actions
+        let other = (seat + 1) % 4;
+        assert!(first.observe(other).unwrap().legal_actions.is_empty());
+        assert_eq!(first.observe(4), Err(GymError::UnknownSeat { seat: 4 }));
+    }
+
+    #[test]
+    fn test_rewards_are_round_points() {
+        let mut environment = Environment::reset(3, &GymConfig::default()).unwrap();
+        let mut totals = [0; 4];
+        let mut scored_rounds = 0;
+        let mut legal = environment
+            .observe(environment.to_act().unwrap())
+            .unwrap()
+            .legal_actions;
+
+        loop {
+            let seat = environment.to_act().unwrap();
+            let action = legal[0];
+
+            // Play the same move on a copy of the engine to see what it scores
+            let mut expected = vec![0; 4];
+            let mut engine = environment.engine.clone();
+            let BotAction::Card { card } = action else {
+                let result = environment.step(action).unwrap();
+                assert_eq!(result.rewards, expected);
+                legal = result.legal_actions;
+                continue;
+            };
+            let player_id = engine.seats[seat].player_id;
+            for event in engine.apply(Action::Play { player_id, card }).unwrap() {
+                if let Event::RoundScored { results, .. } = event {
+                    scored_rounds += 1;
+                    for result in results {
+                        let seat = engine.seat_of(result.player_id).unwrap();
+                        expected[seat] = calculate_round_points(result.tricks_won, result.bid);
+                    }
+                }
+            }
+
+            let result = environment.step(action).unwrap();
+            assert_eq!(result.seat, seat);
+            assert_eq!(result.rewards, expected);
+            for (total, reward) in totals.iter_mut().zip(&result.rewards) {
+                *total += reward;
+            }
+            if result.done {
+                assert_eq!(result.to_act, None);
+                assert!(result.legal_actions.is_empty());
+                break;
+            }
+            legal = result.legal_actions;
+        }
+
+        assert_eq!(scored_rounds, RuleSet::standard().round_schedule.len());
+        assert_eq!(totals.to_vec(), environment.observe(0).unwrap().scores);
+        assert_eq!(environment.step(legal[0]), Err(GymError::GameOver));
+    }
+
+    #[test]
+    fn test_illegal_moves_are_structured_errors() {
+        let mut gym = Gym::default();
+        let reply: serde_json::Value =
+            serde_json::from_str(&gym.handle_line(r#"{"cmd":"observe","seat":0}"#)).unwrap();
+        assert_eq!(reply["ok"], false);
+        assert_eq!(reply["error"]["kind"], "not_reset");
+
+        let reply: serde_json::Value =
+            serde_json::from_str(&gym.handle_line(r#"{"cmd":"reset","seed":1}"#)).unwrap();
+        assert_eq!(reply["ok"], true);
+        assert_eq!(reply["observation"]["seat"], reply["to_act"]);
+
+        // A card during bidding
+        let reply: serde_json::Value = serde_json::from_str(
+            &gym.handle_line(r#"{"cmd":"step","action":{"type":"card","card":"AS"}}"#),
+        )
+        .unwrap();
+        assert_eq!(
+            reply["error"],
+            serde_json::json!({ "kind": "wrong_decision", "expected": "bid" })
+        );
+
+        let reply: serde_json::Value = serde_json::from_str(
+            &gym.handle_line(r#"{"cmd":"step","action":{"type":"bid","bid":14}}"#),
+        )
+        .unwrap();
+        assert_eq!(reply["error"]["kind"], "bid_out_of_range");
+
+        // Bid through to play, then play a card the seat does not hold
+        let mut environment = Environment::reset(1, &GymConfig::default()).unwrap();
+        while environment.engine.phase != Phase::Playing {
+            let to_act = environment.to_act().unwrap();
+            let action = environment.observe(to_act).unwrap().legal_actions[0];
+            environment.step(action).unwrap();
+        }
+        let leader = environment.to_act().unwrap();
+        let missing = Card::full_deck()
+            .into_iter()
+            .find(|card| !environment.observe(leader).unwrap().hand.contains(card))
+            .unwrap();
+        let error = environment
+            .step(BotAction::Card { card: missing })
+            .unwrap_err();
+        assert_eq!(
+            error,
+            GymError::IllegalPlay {
+                reason: ApplyPlayError::CardNotInHand
+            }
+        );
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({ "kind": "illegal_play", "reason": "card_not_in_hand" })
+        );
+
+        let reply: serde_json::Value = serde_json::from_str(&gym.handle_line("not json")).unwrap();
+        assert_eq!(reply["error"]["kind"], "invalid_command");
+    }
+}
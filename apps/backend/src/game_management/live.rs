@@ -0,0 +1,406 @@
+//! Live game updates
+//!
+//! Connections watching a game subscribe to it here and are woken whenever a
+//! transaction on the game commits. Each then reads the entries appended to the
+//! game's event log since it last looked, so a wake-up that is missed or merged
+//! with another never loses an event. Everything sent is redacted for the
+//! viewer: other players' hands stay hidden until the round's deal is revealed.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use sea_orm::{
+    AccessMode, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, IsolationLevel,
+    QueryFilter, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::dto::game_snapshot::GameSnapshot;
+use crate::entity::{game_players, games};
+use crate::game_management::bot_protocol::BotAction;
+use crate::game_management::engine::Event;
+use crate::game_management::event_log::{last_sequence, load_events_after, LoggedEvent};
+use crate::game_management::state::build_game_snapshot;
+use crate::game_management::version::{self, ExpectedVersion, VersionError};
+use crate::game_management::{ai_driver, bidding, play_card_transaction};
+
+/// Wake-ups a watcher may fall behind by before they are merged
+const CHANNEL_CAPACITY: usize = 16;
+
+/// Heartbeat interval when `SSE_HEARTBEAT_MS` is not set
+const DEFAULT_HEARTBEAT_MS: u64 = 15_000;
+
+/// Wake-up channels of the games being watched
+static WATCHERS: OnceLock<Mutex<HashMap<Uuid, broadcast::Sender<()>>>> = OnceLock::new();
+
+fn watchers() -> &'static Mutex<HashMap<Uuid, broadcast::Sender<()>>> {
+    WATCHERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Watch a game for changes
+///
+/// The receiver is woken after every committed change; a lagged receiver
+/// should simply look again.
+pub fn subscribe(game_id: Uuid) -> broadcast::Receiver<()> {
+    let mut watchers = watchers().lock().unwrap_or_else(|e| e.into_inner());
+    watchers.retain(|_, sender| sender.receiver_count() > 0);
+    watchers
+        .entry(game_id)
+        .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+        .subscribe()
+}
+
+/// Wake everyone watching a game
+///
+/// Call after any transaction that changes the game has committed.
+pub fn notify(game_id: Uuid) {
+    let mut watchers = watchers().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(sender) = watchers.get(&game_id) {
+        // Sending only fails once the last watcher has gone
+        if sender.send(()).is_err() {
+            watchers.remove(&game_id);
+        }
+    }
+}
+
+/// The player a game is being watched by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewer {
+    pub user_id: Uuid,
+    pub player_id: Uuid,
+}
+
+/// An update pushed to a viewer
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GameUpdate {
+    /// An entry of the game's event log
+    Event { sequence: i32, event: Event },
+    /// The whole game as the viewer now sees it, as of the event at `sequence`
+    Snapshot {
+        sequence: i32,
+        snapshot: Box<GameSnapshot>,
+    },
+}
+
+impl GameUpdate {
+    /// Write the update as a Server-Sent Events message
+    ///
+    /// This function is PURE - the message is named after the event's type (or
+    /// `snapshot`) and its id is the log sequence, which a reconnecting client
+    /// sends back as `Last-Event-ID`.
+    pub fn to_sse(&self) -> Result<String, String> {
+        let (sequence, name, data) = match self {
+            GameUpdate::Event { sequence, event } => {
+                let data = serde_json::to_value(event)
+                    .map_err(|e| format!("Failed to encode game event: {e}"))?;
+                let name = data["type"].as_str().unwrap_or_default().to_string();
+                (sequence, name, data)
+            }
+            GameUpdate::Snapshot { sequence, snapshot } => {
+                let data = serde_json::to_value(snapshot)
+                    .map_err(|e| format!("Failed to encode game snapshot: {e}"))?;
+                (sequence, "snapshot".to_string(), data)
+            }
+        };
+        Ok(format!("id: {sequence}\nevent: {name}\ndata: {data}\n\n"))
+    }
+}
+
+/// Time between heartbeats on an idle event stream
+///
+/// Configured with `SSE_HEARTBEAT_MS`.
+pub fn heartbeat_interval() -> Duration {
+    let millis = env::var("SSE_HEARTBEAT_MS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_HEARTBEAT_MS);
+    Duration::from_millis(millis)
+}
+
+/// Hide every hand in a snapshot but the viewer's own
+///
+/// This function is PURE - only the hands change.
+pub fn redact_snapshot(mut snapshot: GameSnapshot, viewer: &Viewer) -> GameSnapshot {
+    for player in &mut snapshot.players {
+        if player.user_id != viewer.user_id {
+            player.hand = None;
+        }
+    }
+    snapshot
+}
+
+/// Hide what an event gives away about other players' hands
+///
+/// This function is PURE - a dealt round keeps only the viewer's hand, and its
+/// deal seed is zeroed, since the seed alone rebuilds every hand. Both are
+/// published with the round's deal reveal once it is scored.
+pub fn redact_event(event: Event, viewer: &Viewer) -> Event {
+    match event {
+        Event::RoundDealt {
+            round_number,
+            dealer_id,
+            cards_dealt,
+            hands,
+            ..
+        } => Event::RoundDealt {
+            round_number,
+            dealer_id,
+            cards_dealt,
+            deal_seed: 0,
+            hands: hands
+                .into_iter()
+                .filter(|(player_id, _)| *player_id == viewer.player_id)
+                .collect(),
+        },
+        other => other,
+    }
+}
+
+/// Read what a viewer has not seen of a game yet
+///
+/// Returns the events logged after `after`, followed by a fresh snapshot, with
+/// the sequence number of the latest event. Without `after` only the snapshot
+/// is returned, and nothing is returned when no event has been logged since.
+/// Everything is read at one point in time, so the snapshot always matches
+/// the last event.
+pub(crate) async fn updates_since(
+    game_id: Uuid,
+    viewer: &Viewer,
+    after: Option<i32>,
+    db: &DatabaseConnection,
+) -> Result<(Vec<GameUpdate>, i32), String> {
+    let txn = match db
+        .begin_with_config(
+            Some(IsolationLevel::RepeatableRead),
+            Some(AccessMode::ReadOnly),
+        )
+        .await
+    {
+        Ok(txn) => txn,
+        Err(e) => return Err(format!("Failed to begin transaction: {e}")),
+    };
+
+    let (mut updates, latest) = match after {
+        Some(after) => events_since(game_id, viewer, after, &txn).await?,
+        None => (Vec::new(), last_sequence(game_id, &txn).await?),
+    };
+    if after.is_some() && updates.is_empty() {
+        return Ok((updates, latest));
+    }
+
+    let game = match games::Entity::find_by_id(game_id).one(&txn).await {
+        Ok(Some(game)) => game,
+        Ok(None) => return Err("Game not found".to_string()),
+        Err(e) => return Err(format!("Failed to fetch game: {e}")),
+    };
+    let players = match game_players::Entity::find()
+        .filter(game_players::Column::GameId.eq(game_id))
+        .all(&txn)
+        .await
+    {
+        Ok(players) => players,
+        Err(e) => return Err(format!("Failed to fetch game players: {e}")),
+    };
+    let snapshot = build_game_snapshot(game, players, &txn).await?;
+    updates.push(GameUpdate::Snapshot {
+        sequence: latest,
+        snapshot: Box::new(redact_snapshot(snapshot, viewer)),
+    });
+
+    Ok((updates, latest))
+}
+
+/// Read the events a viewer has not seen yet, without a snapshot
+///
+/// Returns the events logged after `after` with the sequence number of the
+/// last one, or `after` itself when nothing has been logged since.
+pub(crate) async fn events_since(
+    game_id: Uuid,
+    viewer: &Viewer,
+    after: i32,
+    db: &(impl ConnectionTrait + std::marker::Send),
+) -> Result<(Vec<GameUpdate>, i32), String> {
+    let mut updates = Vec::new();
+    let mut latest = after;
+    for entry in load_events_after(game_id, after, db).await? {
+        let logged: LoggedEvent = serde_json::from_value(entry.payload)
+            .map_err(|e| format!("Corrupt event {} in log: {e}", entry.sequence))?;
+        latest = entry.sequence;
+        updates.push(GameUpdate::Event {
+            sequence: entry.sequence,
+            event: redact_event(logged.event, viewer),
+        });
+    }
+    Ok((updates, latest))
+}
+
+/// An action a player sends over a live connection
+///
+/// The bot reply format, plus the version the action was chosen at
+/// (`{"type": "bid", "bid": 2, "expected_version": 7}`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct LiveAction {
+    #[serde(flatten)]
+    pub action: BotAction,
+    pub expected_version: Option<i32>,
+}
+
+/// Why a live action was not carried out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionRefused {
+    /// The game has moved on since the action was chosen
+    Stale { current_version: i32 },
+    /// The action broke a rule or could not be stored
+    Failed(String),
+}
+
+impl From<VersionError> for ActionRefused {
+    fn from(error: VersionError) -> Self {
+        match error {
+            VersionError::NotFound => ActionRefused::Failed("Game not found".to_string()),
+            VersionError::Conflict { current_version } => ActionRefused::Stale { current_version },
+            VersionError::Storage(e) => ActionRefused::Failed(e),
+        }
+    }
+}
+
+/// Carry out a player's action sent over a live connection
+///
+/// Locks the game at the version the player saw and runs the same transaction
+/// as the matching HTTP endpoint, then wakes the AI driver and everyone
+/// watching the game.
+pub(crate) async fn perform(
+    game_id: Uuid,
+    user_id: Uuid,
+    live_action: LiveAction,
+    db: &DatabaseConnection,
+) -> Result<(), ActionRefused> {
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| ActionRefused::Failed(format!("Failed to begin transaction: {e}")))?;
+    let expected = ExpectedVersion {
+        if_match: None,
+        version: live_action.expected_version,
+    };
+    version::lock_at_version(game_id, &expected, &txn).await?;
+
+    let result = match live_action.action {
+        BotAction::Bid { bid } => bidding::submit_bid_transaction(game_id, user_id, bid, &txn)
+            .await
+            .map_err(|e| e.to_string()),
+        BotAction::Trump { trump } => {
+            bidding::submit_trump_transaction(game_id, user_id, trump, &txn).await
+        }
+        BotAction::Card { card } => play_card_transaction(game_id, user_id, card, &txn).await,
+    };
+    result.map_err(ActionRefused::Failed)?;
+    txn.commit()
+        .await
+        .map_err(|e| ActionRefused::Failed(format!("Failed to commit transaction: {e}")))?;
+
+    ai_driver::schedule(game_id, db.clone());
+    notify(game_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_management::cards::Card;
+
+    fn viewer() -> Viewer {
+        Viewer {
+            user_id: Uuid::from_u128(10),
+            player_id: Uuid::from_u128(1),
+        }
+    }
+
+    #[test]
+    fn test_dealt_rounds_keep_only_the_viewers_hand() {
+        let hand = |cards: &[&str]| -> Vec<Card> {
+            cards.iter().map(|card| card.parse().unwrap()).collect()
+        };
+        let dealt = Event::RoundDealt {
+            round_number: 1,
+            dealer_id: Uuid::from_u128(2),
+            cards_dealt: 2,
+            deal_seed: 42,
+            hands: vec![
+                (Uuid::from_u128(1), hand(&["AS", "KH"])),
+                (Uuid::from_u128(2), hand(&["2C", "3D"])),
+            ],
+        };
+
+        let Event::RoundDealt {
+            deal_seed, hands, ..
+        } = redact_event(dealt, &viewer())
+        else {
+            panic!("Expected a dealt round");
+        };
+        assert_eq!(deal_seed, 0);
+        assert_eq!(hands, vec![(Uuid::from_u128(1), hand(&["AS", "KH"]))]);
+
+        let bid = Event::BidPlaced {
+            player_id: Uuid::from_u128(2),
+            bid: 1,
+        };
+        assert_eq!(redact_event(bid.clone(), &viewer()), bid);
+    }
+
+    #[test]
+    fn test_sse_messages_are_named_and_numbered_after_the_event() {
+        let update = GameUpdate::Event {
+            sequence: 7,
+            event: Event::BidPlaced {
+                player_id: Uuid::from_u128(2),
+                bid: 1,
+            },
+        };
+        let message = update.to_sse().unwrap();
+        assert!(message.ends_with("\n\n"));
+
+        let lines: Vec<&str> = message.trim_end().lines().collect();
+        assert_eq!(lines[0], "id: 7");
+        assert_eq!(lines[1], "event: bid_placed");
+        let data: serde_json::Value =
+            serde_json::from_str(lines[2].strip_prefix("data: ").unwrap()).unwrap();
+        assert_eq!(data["bid"], 1);
+    }
+
+    #[test]
+    fn test_live_actions_may_name_the_version_they_were_chosen_at() {
+        let action: LiveAction = serde_json::from_str(r#"{"type": "bid", "bid": 2}"#).unwrap();
+        assert_eq!(action.action, BotAction::Bid { bid: 2 });
+        assert_eq!(action.expected_version, None);
+
+        let action: LiveAction =
+            serde_json::from_str(r#"{"type": "card", "card": "AS", "expected_version": 7}"#)
+                .unwrap();
+        assert_eq!(
+            action.action,
+            BotAction::Card {
+                card: "AS".parse().unwrap()
+            }
+        );
+        assert_eq!(action.expected_version, Some(7));
+    }
+
+    #[test]
+    fn test_watchers_are_woken_until_they_leave() {
+        let game_id = Uuid::new_v4();
+        let mut changes = subscribe(game_id);
+        notify(game_id);
+        assert!(changes.try_recv().is_ok());
+
+        drop(changes);
+        notify(game_id);
+        let watchers = watchers().lock().unwrap();
+        assert!(!watchers.contains_key(&game_id));
+    }
+}
@@ -1,42 +1,85 @@
 //! Game management: thin orchestration + cross-cutting helpers.
-//! Domain logic lives in `rules`, `bidding`, `tricks`, `scoring`, `state`.
+//! Domain logic lives in `cards`, `rules`, `bidding`, `tricks`, `scoring`, `fairness`, `state`.
+//! AI seats decide what to do through an `ai` strategy; `ai_driver` has them act when it is their turn.
+//! External bots decide for their seats over the `bot_protocol`, falling back to a built-in strategy.
+//! The `engine` module plays a whole game in memory; `orchestration` loads and persists it.
+//! `simulation` plays AI-only games in memory, for the `nommie-sim` binary.
+//! `gym` is a step-by-step training environment over the engine, for the `nommie-gym` binary.
+//! Every engine event is appended to the `event_log`, which can replay a game from scratch.
+//! `live` wakes connections watching a game after each commit and redacts what they are sent.
+//! Each game's `version` follows its event log, for ETags, long-polling and refusing stale actions.
 //! HTTP handlers are defined in `routes::game` and wired via configure_routes.
 
+pub mod ai;
+pub mod ai_driver;
 pub mod bidding;
+pub mod bot_protocol;
+pub mod cards;
+pub mod engine;
+pub mod event_log;
+pub mod fairness;
+pub mod gym;
+pub mod live;
 pub mod orchestration;
 pub mod rules;
 pub mod scoring;
+pub mod simulation;
 pub mod state;
 pub mod tricks;
+pub mod version;
 
+use sea_orm::{DatabaseConnection, EntityTrait, TransactionError, TransactionTrait};
 use uuid::Uuid;
 
+use crate::entity::game_players;
+
 /// Perform AI card play action
-#[allow(dead_code)]
-async fn perform_ai_card_play(
-    _game_id: Uuid,
-    _player_id: Uuid,
-    _play_request: crate::dto::play_request::PlayRequest,
-    _db: &sea_orm::DatabaseConnection,
-) -> Result<(), String> {
-    // TODO: Implement AI card play logic
-    // This function was temporarily removed during bidding refactor
-    // and needs to be properly implemented
-    Err("AI card play not yet implemented".to_string())
+///
+/// Plays the card the seat's bot or strategy chose at `version` through the
+/// same `orchestration::play_card` path as a human. Returns false, without
+/// playing, when the game has moved on since.
+pub(crate) async fn perform_ai_card_play(
+    game_id: Uuid,
+    player_id: Uuid,
+    card: cards::Card,
+    version: i32,
+    db: &DatabaseConnection,
+) -> Result<bool, String> {
+    let result = db
+        .transaction::<_, bool, String>(|txn| {
+            Box::pin(async move {
+                if orchestration::lock_game_at(game_id, version, txn)
+                    .await?
+                    .is_none()
+                {
+                    return Ok(false);
+                }
+
+                let user_id = match game_players::Entity::find_by_id(player_id).one(txn).await {
+                    Ok(Some(player)) => player.user_id,
+                    Ok(None) => return Err("Player not found in game".to_string()),
+                    Err(e) => return Err(format!("Failed to fetch player data: {e}")),
+                };
+                orchestration::play_card(game_id, user_id, &card, txn).await?;
+                Ok(true)
+            })
+        })
+        .await;
+
+    match result {
+        Ok(played) => Ok(played),
+        Err(TransactionError::Transaction(e)) => Err(e),
+        Err(e) => Err(format!("Failed to play card: {e}")),
+    }
 }
 
 /// Helper function to play a card within a transaction
 pub(crate) async fn play_card_transaction(
     game_id: Uuid,
     user_id: Uuid,
-    card: String,
+    card: cards::Card,
     txn: &sea_orm::DatabaseTransaction,
 ) -> Result<(), String> {
-    // Validate the card format first
-    if !crate::game_management::rules::is_valid_card_format(&card) {
-        return Err("Invalid card format. Use format like '5S', 'AH', 'KD'".to_string());
-    }
-
     // Delegate to the orchestration module for all trick logic
     crate::game_management::orchestration::play_card(game_id, user_id, &card, txn).await
 }
@@ -1,464 +1,622 @@
 //! Game orchestration module
 //!
 //! This module contains database-coupled orchestration logic for game operations.
-//! It handles database transactions, entity management, and coordinates between
-//! pure domain logic modules.
+//! It loads a `GameEngine` from the stored rows, applies a single action to it,
+//! and writes the resulting events back, all within the caller's transaction.
+//! The game rules themselves live in the pure `engine` module.
 
+use std::fmt;
+
+use chrono::{DateTime, FixedOffset, Utc};
+use sea_orm::sea_query::LockType;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseTransaction, EntityTrait, QueryFilter, QueryOrder,
-    QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseTransaction, EntityTrait, Order,
+    QueryFilter, QueryOrder, QuerySelect, Set,
 };
 use uuid::Uuid;
 
-use crate::entity::{game_players, game_rounds, games, round_hands, round_tricks, trick_plays};
-use crate::game_management::tricks;
+use crate::entity::{
+    game_players, game_rounds, games, round_bids, round_hands, round_scores, round_tricks,
+    trick_plays,
+};
+use crate::game_management::cards::{Card, Trump};
+use crate::game_management::engine::{Action, Event, GameEngine, Phase, Round, RuleError, Trick};
+use crate::game_management::event_log::{append_events, LoggedEvent};
+use crate::game_management::fairness::{deal_commitment, generate_salt};
+use crate::game_management::rules::turn_order_from_index;
+use crate::game_management::version::{lock_at_version, ExpectedVersion, VersionError};
+
+/// Reasons an action could not be applied to a stored game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionError {
+    /// The engine rejected the action under the game rules
+    Rule(RuleError),
+    /// Loading or saving the game failed
+    Storage(String),
+}
 
-/// Validate that a play is legal according to game rules:
-/// - Player must own the card
-/// - Must follow suit if possible
-/// - Must be player's turn
-/// - Game must be in playing phase
-pub(crate) async fn validate_play(
-    game_id: Uuid,
-    user_id: Uuid,
-    card: &str,
-    txn: &DatabaseTransaction,
-) -> Result<(), String> {
-    // Lock the game row for update to prevent concurrent modifications
-    let game = match games::Entity::find_by_id(game_id)
-        .lock(sea_orm::sea_query::LockType::Update)
-        .one(txn)
-        .await
-    {
-        Ok(Some(game)) => game,
-        Ok(None) => {
-            return Err("Game not found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch game: {e}"));
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionError::Rule(error) => write!(f, "{error}"),
+            ActionError::Storage(message) => write!(f, "{message}"),
         }
-    };
+    }
+}
 
-    // Validate that the game is in the Playing phase
-    if game.phase != games::GamePhase::Playing {
-        return Err("Game is not in playing phase".to_string());
+impl std::error::Error for ActionError {}
+
+impl From<RuleError> for ActionError {
+    fn from(error: RuleError) -> Self {
+        ActionError::Rule(error)
     }
+}
 
-    // Fetch the current player's game_player record
-    let current_player = match game_players::Entity::find()
-        .filter(game_players::Column::GameId.eq(game_id))
-        .filter(game_players::Column::UserId.eq(user_id))
-        .one(txn)
-        .await
-    {
-        Ok(Some(player)) => player,
-        Ok(None) => {
-            return Err("You are not a participant in this game".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch player data: {e}"));
+impl From<String> for ActionError {
+    fn from(message: String) -> Self {
+        ActionError::Storage(message)
+    }
+}
+
+/// Parse stored card notation back into typed cards
+fn parse_stored_cards<'a>(cards: impl IntoIterator<Item = &'a str>) -> Result<Vec<Card>, String> {
+    cards
+        .into_iter()
+        .map(|card| {
+            card.parse::<Card>()
+                .map_err(|e| format!("Corrupt card in storage: {e}"))
+        })
+        .collect()
+}
+
+/// Map a stored game's state and phase onto the engine phase
+fn engine_phase(game: &games::Model) -> Phase {
+    match (&game.state, &game.phase) {
+        (games::GameState::Waiting, _) => Phase::Waiting,
+        (games::GameState::Completed, _) => Phase::Completed,
+        (games::GameState::Started, games::GamePhase::Bidding) => Phase::Bidding,
+        (games::GameState::Started, games::GamePhase::TrumpSelection) => Phase::TrumpSelection,
+        (games::GameState::Started, games::GamePhase::Playing | games::GamePhase::Scoring) => {
+            Phase::Playing
         }
-    };
+    }
+}
 
-    // Check if it's this player's turn to play
-    let current_turn = game.current_turn.unwrap_or(0);
-    if current_player.turn_order.unwrap_or(-1) != current_turn {
-        return Err("It's not your turn to play".to_string());
+/// Map an engine phase onto the stored game phase, if it has one
+pub(crate) fn stored_phase(phase: Phase) -> Option<games::GamePhase> {
+    match phase {
+        Phase::Bidding => Some(games::GamePhase::Bidding),
+        Phase::TrumpSelection => Some(games::GamePhase::TrumpSelection),
+        Phase::Playing => Some(games::GamePhase::Playing),
+        Phase::Waiting | Phase::Completed => None,
     }
+}
 
-    // Lock the current round row for update
-    let current_round = match game_rounds::Entity::find()
-        .filter(game_rounds::Column::GameId.eq(game_id))
-        .order_by_desc(game_rounds::Column::RoundNumber)
-        .lock(sea_orm::sea_query::LockType::Update)
-        .one(txn)
+/// Look up the `game_players` id of a user seated in a game
+pub(crate) async fn player_id_for_user(
+    game_id: Uuid,
+    user_id: Uuid,
+    db: &(impl ConnectionTrait + std::marker::Send),
+) -> Result<Uuid, ActionError> {
+    match game_players::Entity::find()
+        .filter(game_players::Column::GameId.eq(game_id))
+        .filter(game_players::Column::UserId.eq(user_id))
+        .one(db)
         .await
     {
-        Ok(Some(round)) => round,
-        Ok(None) => {
-            return Err("No current round found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch current round: {e}"));
-        }
-    };
+        Ok(Some(player)) => Ok(player.id),
+        Ok(None) => Err(RuleError::UnknownPlayer.into()),
+        Err(e) => Err(format!("Failed to fetch player data: {e}").into()),
+    }
+}
 
-    // Check if the player has the card in their hand
-    let player_hand = match round_hands::Entity::find()
-        .filter(round_hands::Column::RoundId.eq(current_round.id))
-        .filter(round_hands::Column::PlayerId.eq(current_player.id))
-        .all(txn)
+/// Rebuild the in-memory engine for a stored game
+///
+/// Seats follow turn order, running scores are summed from the scored rounds,
+/// and the latest round is restored with its remaining hands, bids, trump and
+/// tricks. Games without a pinned seed get a fresh random base seed, so any
+/// round dealt from this engine gets a random deal seed.
+pub(crate) async fn load_engine(
+    game: &games::Model,
+    db: &(impl ConnectionTrait + std::marker::Send),
+) -> Result<GameEngine, String> {
+    let rules = game.rules()?;
+
+    let players = match game_players::Entity::find()
+        .filter(game_players::Column::GameId.eq(game.id))
+        .order_by(game_players::Column::TurnOrder, Order::Asc)
+        .all(db)
         .await
     {
-        Ok(hand) => hand,
-        Err(e) => {
-            return Err(format!("Failed to fetch player hand: {e}"));
-        }
+        Ok(players) => players,
+        Err(e) => return Err(format!("Failed to fetch game players: {e}")),
     };
 
-    let has_card = player_hand.iter().any(|h| h.card == card);
-    if !has_card {
-        return Err("You don't have that card in your hand".to_string());
+    let player_ids: Vec<Uuid> = players.iter().map(|player| player.id).collect();
+    let seed = game.deal_seed.unwrap_or_else(rand::random);
+    let mut engine = GameEngine::new(rules, &player_ids, seed)?;
+    for (seat, player) in engine.seats.iter_mut().zip(&players) {
+        seat.is_ready = player.is_ready;
     }
+    engine.phase = engine_phase(game);
 
-    // Get the current trick
-    let current_trick = match round_tricks::Entity::find()
-        .filter(round_tricks::Column::RoundId.eq(current_round.id))
-        .order_by_desc(round_tricks::Column::TrickNumber)
-        .one(txn)
+    let rounds = match game_rounds::Entity::find()
+        .filter(game_rounds::Column::GameId.eq(game.id))
+        .order_by(game_rounds::Column::RoundNumber, Order::Asc)
+        .all(db)
         .await
     {
-        Ok(Some(trick)) => trick,
-        Ok(None) => {
-            return Err("No current trick found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch current trick: {e}"));
-        }
+        Ok(rounds) => rounds,
+        Err(e) => return Err(format!("Failed to fetch game rounds: {e}")),
     };
+    let round_ids: Vec<Uuid> = rounds.iter().map(|round| round.id).collect();
 
-    // Check if this player has already played in this trick (idempotency check)
-    let existing_play = match trick_plays::Entity::find()
-        .filter(trick_plays::Column::TrickId.eq(current_trick.id))
-        .filter(trick_plays::Column::PlayerId.eq(current_player.id))
-        .one(txn)
+    let bids = match round_bids::Entity::find()
+        .filter(round_bids::Column::RoundId.is_in(round_ids.clone()))
+        .all(db)
         .await
     {
-        Ok(Some(_)) => true,
-        Ok(None) => false,
-        Err(e) => {
-            return Err(format!("Failed to check existing play: {e}"));
-        }
+        Ok(bids) => bids,
+        Err(e) => return Err(format!("Failed to fetch round bids: {e}")),
     };
 
-    if existing_play {
-        return Err("You have already played a card in this trick".to_string());
-    }
-
-    // Check if this is the first play in the trick (to determine lead suit)
-    let trick_plays = match trick_plays::Entity::find()
-        .filter(trick_plays::Column::TrickId.eq(current_trick.id))
-        .all(txn)
+    let scores = match round_scores::Entity::find()
+        .filter(round_scores::Column::RoundId.is_in(round_ids))
+        .all(db)
         .await
     {
-        Ok(plays) => plays,
-        Err(e) => {
-            return Err(format!("Failed to fetch trick plays: {e}"));
-        }
+        Ok(scores) => scores,
+        Err(e) => return Err(format!("Failed to fetch round scores: {e}")),
     };
 
-    let is_first_play = trick_plays.is_empty();
-    if !is_first_play {
-        // Enforce follow-suit rule if not the first play
-        let lead_suit = if let Some(first_play) = trick_plays.first() {
-            if first_play.card.len() >= 2 {
-                &first_play.card[1..2]
-            } else {
-                return Err("Invalid first card format".to_string());
-            }
-        } else {
-            return Err("No first play found".to_string());
-        };
-
-        let card_suit = if card.len() >= 2 {
-            &card[1..2]
-        } else {
-            return Err("Invalid card format".to_string());
+    // Running totals follow the game's scoring rule
+    let scoring_rule = engine.rules.scoring_rule();
+    for score in &scores {
+        let Some(seat) = engine.seat_of(score.player_id) else {
+            continue;
         };
+        let bid = bids
+            .iter()
+            .find(|bid| bid.round_id == score.round_id && bid.player_id == score.player_id)
+            .map(|bid| bid.bid)
+            .unwrap_or(0);
+        engine.scores[seat] += scoring_rule.round_points(score.tricks_won, bid);
+    }
 
-        if card_suit != lead_suit {
-            // Check if player has any cards of the lead suit
-            let has_lead_suit = player_hand.iter().any(|h| {
-                if h.card.len() >= 2 {
-                    &h.card[1..2] == lead_suit
-                } else {
-                    false
-                }
-            });
-
-            if has_lead_suit {
-                return Err("You must follow suit if possible".to_string());
-            }
-        }
+    if let Some(round) = rounds.last() {
+        let round_bids: Vec<&round_bids::Model> =
+            bids.iter().filter(|bid| bid.round_id == round.id).collect();
+        engine.round = Some(load_round(&engine, round, &round_bids, db).await?);
     }
 
-    Ok(())
+    Ok(engine)
 }
 
-/// Apply a card play to the current trick
-///
-/// This function records the card play and removes it from the player's hand.
-/// It does not handle turn progression or trick completion.
-pub(crate) async fn apply_play(
-    game_id: Uuid,
-    user_id: Uuid,
-    card: &str,
-    txn: &DatabaseTransaction,
-) -> Result<(), String> {
-    // Lock the current round row for update
-    let current_round = match game_rounds::Entity::find()
-        .filter(game_rounds::Column::GameId.eq(game_id))
-        .order_by_desc(game_rounds::Column::RoundNumber)
-        .lock(sea_orm::sea_query::LockType::Update)
-        .one(txn)
+/// Restore the engine's view of a stored round
+async fn load_round(
+    engine: &GameEngine,
+    round: &game_rounds::Model,
+    bids: &[&round_bids::Model],
+    db: &(impl ConnectionTrait + std::marker::Send),
+) -> Result<Round, String> {
+    let player_count = engine.seats.len();
+
+    let held = match round_hands::Entity::find()
+        .filter(round_hands::Column::RoundId.eq(round.id))
+        .all(db)
         .await
     {
-        Ok(Some(round)) => round,
-        Ok(None) => {
-            return Err("No current round found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch current round: {e}"));
-        }
+        Ok(cards) => cards,
+        Err(e) => return Err(format!("Failed to fetch round hands: {e}")),
     };
 
-    // Fetch the current player's game_player record
-    let current_player = match game_players::Entity::find()
-        .filter(game_players::Column::GameId.eq(game_id))
-        .filter(game_players::Column::UserId.eq(user_id))
-        .one(txn)
-        .await
-    {
-        Ok(Some(player)) => player,
-        Ok(None) => {
-            return Err("You are not a participant in this game".to_string());
+    let mut hands = vec![Vec::new(); player_count];
+    for card in &held {
+        if let Some(seat) = engine.seat_of(card.player_id) {
+            hands[seat].extend(parse_stored_cards([card.card.as_str()])?);
         }
-        Err(e) => {
-            return Err(format!("Failed to fetch player data: {e}"));
+    }
+
+    let mut seat_bids = vec![None; player_count];
+    for bid in bids {
+        if let Some(seat) = engine.seat_of(bid.player_id) {
+            seat_bids[seat] = Some(bid.bid);
         }
+    }
+
+    // Rounds dealt before dealers were recorded follow the standard rotation
+    let dealer = round
+        .dealer_player_id
+        .and_then(|dealer_id| engine.seat_of(dealer_id))
+        .unwrap_or_else(|| engine.rules.dealer_index_for_round(round.round_number));
+
+    let mut restored = Round {
+        round_number: round.round_number,
+        dealer,
+        cards_dealt: round.cards_dealt,
+        deal_seed: round.deal_seed.unwrap_or_default(),
+        hands,
+        bids: seat_bids,
+        trump: round.trump_suit.map(Trump::from),
+        tricks: Vec::new(),
     };
 
-    // Get the current trick
-    let current_trick = match round_tricks::Entity::find()
-        .filter(round_tricks::Column::RoundId.eq(current_round.id))
-        .order_by_desc(round_tricks::Column::TrickNumber)
-        .one(txn)
+    let stored_tricks = match round_tricks::Entity::find()
+        .filter(round_tricks::Column::RoundId.eq(round.id))
+        .order_by(round_tricks::Column::TrickNumber, Order::Asc)
+        .all(db)
         .await
     {
-        Ok(Some(trick)) => trick,
-        Ok(None) => {
-            return Err("No current trick found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch current trick: {e}"));
-        }
+        Ok(tricks) => tricks,
+        Err(e) => return Err(format!("Failed to fetch round tricks: {e}")),
     };
 
-    // Record the card play
-    let play_model = trick_plays::ActiveModel {
-        id: Set(Uuid::new_v4()),
-        trick_id: Set(current_trick.id),
-        player_id: Set(current_player.id),
-        card: Set(card.to_string()),
-        play_order: Set(0), // This will be set by the caller
-    };
+    for trick in stored_tricks {
+        let plays = match trick_plays::Entity::find()
+            .filter(trick_plays::Column::TrickId.eq(trick.id))
+            .order_by(trick_plays::Column::PlayOrder, Order::Asc)
+            .all(db)
+            .await
+        {
+            Ok(plays) => plays,
+            Err(e) => return Err(format!("Failed to fetch trick plays: {e}")),
+        };
 
-    if let Err(e) = play_model.insert(txn).await {
-        return Err(format!("Failed to record card play: {e}"));
+        let cards = parse_stored_cards(plays.iter().map(|play| play.card.as_str()))?;
+        let leader = plays
+            .first()
+            .and_then(|play| engine.seat_of(play.player_id))
+            .unwrap_or_else(|| next_leader(engine, &restored));
+
+        restored.tricks.push(Trick {
+            trick_number: trick.trick_number,
+            leader,
+            plays: cards
+                .into_iter()
+                .zip(plays.iter().map(|play| play.player_id))
+                .collect(),
+            winner: trick.winner_player_id,
+        });
     }
 
-    // Remove the card from the player's hand
-    let hand_to_remove = match round_hands::Entity::find()
-        .filter(round_hands::Column::RoundId.eq(current_round.id))
-        .filter(round_hands::Column::PlayerId.eq(current_player.id))
-        .filter(round_hands::Column::Card.eq(card))
-        .one(txn)
-        .await
+    // Trick rows are created with their first play, so open the next one here
+    let tricks_taken = restored.tricks.len() as i32;
+    if engine.phase == Phase::Playing
+        && restored.open_trick().is_none()
+        && tricks_taken < restored.cards_dealt
     {
-        Ok(Some(hand)) => hand,
-        Ok(None) => {
-            return Err("Card not found in player's hand".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to find card in hand: {e}"));
-        }
-    };
-
-    if let Err(e) = round_hands::Entity::delete_by_id(hand_to_remove.id)
-        .exec(txn)
-        .await
-    {
-        return Err(format!("Failed to remove card from hand: {e}"));
+        restored.tricks.push(Trick {
+            trick_number: tricks_taken + 1,
+            leader: next_leader(engine, &restored),
+            plays: Vec::new(),
+            winner: None,
+        });
     }
 
-    Ok(())
+    Ok(restored)
 }
 
-/// Check if a trick is complete and handle progression
-///
-/// This function checks if all players have played in the current trick
-/// and handles either starting the next trick or completing the round.
-pub(crate) async fn maybe_advance_after_play(
+/// Seat that leads the next trick: the last winner, or the seat after the dealer
+fn next_leader(engine: &GameEngine, round: &Round) -> usize {
+    round
+        .tricks
+        .last()
+        .and_then(|trick| trick.winner)
+        .and_then(|winner_id| engine.seat_of(winner_id))
+        .unwrap_or_else(|| round.first_to_act())
+}
+
+/// Lock a game row and load its engine
+pub(crate) async fn lock_engine(
     game_id: Uuid,
     txn: &DatabaseTransaction,
-) -> Result<(), String> {
-    // Lock the game row for update
+) -> Result<(games::Model, GameEngine), String> {
     let game = match games::Entity::find_by_id(game_id)
-        .lock(sea_orm::sea_query::LockType::Update)
+        .lock(LockType::Update)
         .one(txn)
         .await
     {
         Ok(Some(game)) => game,
-        Ok(None) => {
-            return Err("Game not found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch game: {e}"));
-        }
+        Ok(None) => return Err("Game not found".to_string()),
+        Err(e) => return Err(format!("Failed to fetch game: {e}")),
     };
 
-    // Lock the current round row for update
-    let current_round = match game_rounds::Entity::find()
-        .filter(game_rounds::Column::GameId.eq(game_id))
-        .order_by_desc(game_rounds::Column::RoundNumber)
-        .lock(sea_orm::sea_query::LockType::Update)
-        .one(txn)
-        .await
-    {
-        Ok(Some(round)) => round,
-        Ok(None) => {
-            return Err("No current round found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch current round: {e}"));
-        }
-    };
+    let engine = load_engine(&game, txn).await?;
+    Ok((game, engine))
+}
 
-    // Get the current trick
-    let current_trick = match round_tricks::Entity::find()
-        .filter(round_tricks::Column::RoundId.eq(current_round.id))
-        .order_by_desc(round_tricks::Column::TrickNumber)
-        .one(txn)
-        .await
-    {
-        Ok(Some(trick)) => trick,
-        Ok(None) => {
-            return Err("No current trick found".to_string());
-        }
-        Err(e) => {
-            return Err(format!("Failed to fetch current trick: {e}"));
-        }
+/// Lock a game row, if the game is still at `version`
+///
+/// Returns `None` when the game has changed since, for callers that decided on
+/// an action without holding the lock.
+pub(crate) async fn lock_game_at(
+    game_id: Uuid,
+    version: i32,
+    txn: &DatabaseTransaction,
+) -> Result<Option<games::Model>, String> {
+    let expected = ExpectedVersion {
+        if_match: None,
+        version: Some(version),
     };
+    match lock_at_version(game_id, &expected, txn).await {
+        Ok(game) => Ok(Some(game)),
+        Err(VersionError::Conflict { .. }) => Ok(None),
+        Err(VersionError::NotFound) => Err("Game not found".to_string()),
+        Err(VersionError::Storage(e)) => Err(e),
+    }
+}
 
-    // Get all players in the game
-    let all_players = match game_players::Entity::find()
-        .filter(game_players::Column::GameId.eq(game_id))
-        .all(txn)
-        .await
-    {
-        Ok(players) => players,
-        Err(e) => {
-            return Err(format!("Failed to fetch all players: {e}"));
-        }
-    };
+/// Write the events produced by an action back to the database
+///
+/// Each event maps onto the rows it changes and is appended to the game's event
+/// log; the game row is updated last with the engine's phase and the turn order
+/// of the seat due to act next.
+pub(crate) async fn persist_events(
+    game: games::Model,
+    engine: &GameEngine,
+    events: &[Event],
+    db: &(impl ConnectionTrait + std::marker::Send),
+) -> Result<(), String> {
+    let now: DateTime<FixedOffset> = Utc::now().into();
 
-    // Get current trick plays
-    let trick_plays = match trick_plays::Entity::find()
-        .filter(trick_plays::Column::TrickId.eq(current_trick.id))
-        .all(txn)
+    let mut current_round = match game_rounds::Entity::find()
+        .filter(game_rounds::Column::GameId.eq(game.id))
+        .order_by_desc(game_rounds::Column::RoundNumber)
+        .one(db)
         .await
     {
-        Ok(plays) => plays,
-        Err(e) => {
-            return Err(format!("Failed to fetch trick plays: {e}"));
-        }
+        Ok(round) => round,
+        Err(e) => return Err(format!("Failed to fetch current round: {e}")),
     };
 
-    // Use pure logic to determine advancement
-    let current_turn = game.current_turn.unwrap_or(0);
-    let cards_per_player = current_round.cards_dealt;
-
-    // Convert trick plays to pure domain format
-    let pure_trick_plays: Vec<(String, Uuid)> = trick_plays
-        .iter()
-        .map(|p| (p.card.clone(), p.player_id))
-        .collect();
-
-    let advancement = tricks::advance_trick_logic(
-        &pure_trick_plays,
-        all_players.len(),
-        current_turn,
-        current_trick.trick_number,
-        cards_per_player,
-        &current_round.trump_suit,
-    );
-
-    if advancement.trick_complete {
-        // Record the trick winner
-        if let Some(winner_id) = advancement.winner_user_id {
-            let winner_player = all_players
-                .iter()
-                .find(|p| p.user_id == winner_id)
-                .ok_or("Winner not found in player list")?;
-
-            let mut trick_model: round_tricks::ActiveModel = current_trick.clone().into();
-            trick_model.winner_player_id = Set(Some(winner_player.id));
-            if let Err(e) = trick_model.update(txn).await {
-                return Err(format!("Failed to update trick winner: {e}"));
+    let game_id = game.id;
+    let mut game_update: games::ActiveModel = game.into();
+    let mut log = Vec::with_capacity(events.len());
+
+    for event in events {
+        let mut logged = LoggedEvent::from(event.clone());
+        match event {
+            // Seats are created by the join routes before the engine sees them
+            Event::PlayerJoined { .. } => {}
+            Event::PlayerReady { player_id } => {
+                let player = match game_players::Entity::find_by_id(*player_id).one(db).await {
+                    Ok(Some(player)) => player,
+                    Ok(None) => return Err("Player not found".to_string()),
+                    Err(e) => return Err(format!("Failed to fetch player data: {e}")),
+                };
+                let mut player_update: game_players::ActiveModel = player.into();
+                player_update.is_ready = Set(true);
+                if let Err(e) = player_update.update(db).await {
+                    return Err(format!("Failed to update player readiness: {e}"));
+                }
             }
-        }
+            Event::GameStarted => {
+                game_update.state = Set(games::GameState::Started);
+                game_update.started_at = Set(Some(now));
+            }
+            Event::RoundDealt {
+                round_number,
+                dealer_id,
+                cards_dealt,
+                deal_seed,
+                hands,
+            } => {
+                // Publish a salted commitment of the seed alongside the round
+                let deal_salt = generate_salt(&mut rand::thread_rng());
+                let round = game_rounds::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    game_id: Set(game_id),
+                    round_number: Set(*round_number),
+                    dealer_player_id: Set(Some(*dealer_id)),
+                    trump_suit: Set(None),
+                    cards_dealt: Set(*cards_dealt),
+                    created_at: Set(now),
+                    deal_seed: Set(Some(*deal_seed)),
+                    deal_commitment: Set(Some(deal_commitment(*deal_seed, &deal_salt))),
+                    deal_salt: Set(Some(deal_salt.clone())),
+                };
+                let round = match round.insert(db).await {
+                    Ok(round) => round,
+                    Err(e) => return Err(format!("Failed to create round: {e}")),
+                };
+
+                for (player_id, hand) in hands {
+                    for card in hand {
+                        let round_hand = round_hands::ActiveModel {
+                            id: Set(Uuid::new_v4()),
+                            round_id: Set(round.id),
+                            player_id: Set(*player_id),
+                            card: Set(card.to_string()),
+                        };
+                        if let Err(e) = round_hand.insert(db).await {
+                            return Err(format!("Failed to store card in round_hands: {e}"));
+                        }
+                    }
+                }
 
-        if advancement.round_complete {
-            // Round is complete, advance to scoring
-            let mut game_model: games::ActiveModel = game.into();
-            game_model.phase = Set(games::GamePhase::Scoring);
-            game_model.current_turn = Set(Some(0));
-            if let Err(e) = game_model.update(txn).await {
-                return Err(format!("Failed to update game phase: {e}"));
+                logged.deal_salt = Some(deal_salt);
+                logged.round_id = Some(round.id);
+                current_round = Some(round);
             }
-        } else {
-            // Start next trick
-            let next_trick_number = current_trick.trick_number + 1;
-            let next_trick_model = round_tricks::ActiveModel {
-                id: Set(Uuid::new_v4()),
-                round_id: Set(current_round.id),
-                trick_number: Set(next_trick_number),
-                winner_player_id: Set(None),
-                created_at: Set(chrono::Utc::now().into()),
-            };
-
-            if let Err(e) = next_trick_model.insert(txn).await {
-                return Err(format!("Failed to create next trick: {e}"));
+            Event::BidPlaced { player_id, bid } => {
+                let round = current_round.as_ref().ok_or("No current round found")?;
+                let round_bid = round_bids::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    round_id: Set(round.id),
+                    player_id: Set(*player_id),
+                    bid: Set(*bid),
+                };
+                if let Err(e) = round_bid.insert(db).await {
+                    return Err(format!("Failed to save bid: {e}"));
+                }
             }
+            Event::TrumpChosen { trump, .. } => {
+                let round = current_round.take().ok_or("No current round found")?;
+                let mut round_update: game_rounds::ActiveModel = round.into();
+                round_update.trump_suit = Set(Some((*trump).into()));
+                match round_update.update(db).await {
+                    Ok(round) => current_round = Some(round),
+                    Err(e) => return Err(format!("Failed to update round with trump suit: {e}")),
+                }
+            }
+            Event::CardPlayed {
+                player_id,
+                card,
+                trick_number,
+                play_order,
+            } => {
+                let round = current_round.as_ref().ok_or("No current round found")?;
+                let trick = if *play_order == 0 {
+                    let trick = round_tricks::ActiveModel {
+                        id: Set(Uuid::new_v4()),
+                        round_id: Set(round.id),
+                        trick_number: Set(*trick_number),
+                        winner_player_id: Set(None),
+                        created_at: Set(now),
+                    };
+                    match trick.insert(db).await {
+                        Ok(trick) => {
+                            logged.trick_id = Some(trick.id);
+                            trick
+                        }
+                        Err(e) => return Err(format!("Failed to create trick: {e}")),
+                    }
+                } else {
+                    find_trick(round.id, *trick_number, db).await?
+                };
+
+                let play = trick_plays::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    trick_id: Set(trick.id),
+                    player_id: Set(*player_id),
+                    card: Set(card.to_string()),
+                    play_order: Set(*play_order),
+                };
+                if let Err(e) = play.insert(db).await {
+                    return Err(format!("Failed to record card play: {e}"));
+                }
 
-            // Move to next player's turn using state module
-            if let Err(e) =
-                crate::game_management::state::set_next_player(&game, advancement.next_turn, txn)
+                // Remove the card from the player's hand
+                let held = match round_hands::Entity::find()
+                    .filter(round_hands::Column::RoundId.eq(round.id))
+                    .filter(round_hands::Column::PlayerId.eq(*player_id))
+                    .filter(round_hands::Column::Card.eq(card.to_string()))
+                    .one(db)
                     .await
-            {
-                return Err(format!("Failed to update turn: {e}"));
+                {
+                    Ok(Some(held)) => held,
+                    Ok(None) => return Err("Card not found in player's hand".to_string()),
+                    Err(e) => return Err(format!("Failed to find card in hand: {e}")),
+                };
+                if let Err(e) = round_hands::Entity::delete_by_id(held.id).exec(db).await {
+                    return Err(format!("Failed to remove card from hand: {e}"));
+                }
+            }
+            Event::TrickWon {
+                trick_number,
+                winner_id,
+            } => {
+                let round = current_round.as_ref().ok_or("No current round found")?;
+                let trick = find_trick(round.id, *trick_number, db).await?;
+                let mut trick_update: round_tricks::ActiveModel = trick.into();
+                trick_update.winner_player_id = Set(Some(*winner_id));
+                if let Err(e) = trick_update.update(db).await {
+                    return Err(format!("Failed to update trick winner: {e}"));
+                }
+            }
+            Event::RoundScored { results, .. } => {
+                let round = current_round.as_ref().ok_or("No current round found")?;
+                for result in results {
+                    let round_score = round_scores::ActiveModel {
+                        id: Set(Uuid::new_v4()),
+                        round_id: Set(round.id),
+                        player_id: Set(result.player_id),
+                        tricks_won: Set(result.tricks_won),
+                    };
+                    if let Err(e) = round_score.insert(db).await {
+                        return Err(format!("Failed to create round score: {e}"));
+                    }
+                }
+            }
+            Event::GameCompleted { .. } => {
+                game_update.state = Set(games::GameState::Completed);
+                game_update.completed_at = Set(Some(now));
             }
         }
-    } else {
-        // Move to next player's turn using state module
-        if let Err(e) =
-            crate::game_management::state::set_next_player(&game, advancement.next_turn, txn).await
-        {
-            return Err(format!("Failed to update turn: {e}"));
-        }
+        log.push(logged);
     }
 
-    Ok(())
+    append_events(game_id, &log, db).await?;
+
+    if let Some(phase) = stored_phase(engine.phase) {
+        game_update.phase = Set(phase);
+    }
+    game_update.current_turn = Set(engine.current_turn().map(turn_order_from_index));
+    game_update.updated_at = Set(now);
+
+    match game_update.update(db).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Failed to update game: {e}")),
+    }
+}
+
+/// Fetch a round's trick by number
+async fn find_trick(
+    round_id: Uuid,
+    trick_number: i32,
+    db: &(impl ConnectionTrait + std::marker::Send),
+) -> Result<round_tricks::Model, String> {
+    match round_tricks::Entity::find()
+        .filter(round_tricks::Column::RoundId.eq(round_id))
+        .filter(round_tricks::Column::TrickNumber.eq(trick_number))
+        .one(db)
+        .await
+    {
+        Ok(Some(trick)) => Ok(trick),
+        Ok(None) => Err("No current trick found".to_string()),
+        Err(e) => Err(format!("Failed to fetch current trick: {e}")),
+    }
+}
+
+/// Apply one action to a stored game
+///
+/// Locks the game row, rebuilds the engine, applies the action and persists
+/// the resulting events. Returns the events so callers can react to them.
+pub(crate) async fn apply_action(
+    game_id: Uuid,
+    action: Action,
+    txn: &DatabaseTransaction,
+) -> Result<Vec<Event>, ActionError> {
+    let (game, mut engine) = lock_engine(game_id, txn).await?;
+    let events = engine.apply(action)?;
+    persist_events(game, &engine, &events, txn).await?;
+    Ok(events)
 }
 
 /// Play a card and handle all trick logic
 ///
-/// This is the main entry point for playing a card. It validates the play,
-/// applies it, and handles trick progression.
+/// This is the main entry point for playing a card. The engine validates the
+/// play and works out trick, round and game progression.
 pub(crate) async fn play_card(
     game_id: Uuid,
     user_id: Uuid,
-    card: &str,
+    card: &Card,
     txn: &DatabaseTransaction,
 ) -> Result<(), String> {
-    // Validate the play
-    validate_play(game_id, user_id, card, txn).await?;
-
-    // Apply the play
-    apply_play(game_id, user_id, card, txn).await?;
-
-    // Handle progression
-    maybe_advance_after_play(game_id, txn).await?;
+    let player_id = player_id_for_user(game_id, user_id, txn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    apply_action(
+        game_id,
+        Action::Play {
+            player_id,
+            card: *card,
+        },
+        txn,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -4,119 +4,213 @@
 //! and rule enforcement mechanisms that depend only on
 //! in-memory domain types and std.
 
-/// Total number of rounds in a game
-pub const TOTAL_ROUNDS: i32 = 26;
+use serde::{Deserialize, Serialize};
 
-/// Number of players in a game
-pub const PLAYER_COUNT: usize = 4;
+use crate::game_management::cards::{Card, Suit, Trump};
+use crate::game_management::scoring::{ScoringRule, ScoringVariant};
 
-/// Number of rounds with 2 cards (rounds 12-15)
-pub const TWO_CARD_ROUNDS: i32 = 4;
+/// Number of cards in the deck every game is dealt from
+pub const DECK_SIZE: usize = 52;
 
-/// Round number where 2-card rounds start
-pub const TWO_CARD_ROUNDS_START: i32 = 12;
+/// Fewest seats a table can be created with
+pub const MIN_PLAYERS: usize = 3;
 
-/// Round number where 2-card rounds end
-pub const TWO_CARD_ROUNDS_END: i32 = 15;
+/// Most seats a table can be created with
+pub const MAX_PLAYERS: usize = 7;
 
-/// Round number where card count starts increasing again
-pub const INCREASING_CARDS_START: i32 = 16;
+/// Number of players in the standard game
+pub const STANDARD_PLAYER_COUNT: usize = 4;
 
-/// Maximum cards dealt in a round
-pub const MAX_CARDS_PER_ROUND: i32 = 13;
+/// The configurable rules a single game is played with
+///
+/// A rule set is stored per game, so house variants (table size, round
+/// schedule, bid limits) can be played without touching the game logic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleSet {
+    /// Number of seats at the table; the game starts once all are filled
+    pub player_count: usize,
+    /// Cards dealt to each player, one entry per round (round 1 first)
+    pub round_schedule: Vec<i32>,
+    /// Lowest bid a player may make
+    pub min_bid: i32,
+    /// Highest bid a player may make
+    pub max_bid: i32,
+    /// How bids and tricks won turn into round points
+    #[serde(default)]
+    pub scoring: ScoringVariant,
+    /// Dealer hook: the dealer, who bids last, may not make total bids equal cards dealt
+    #[serde(default)]
+    pub dealer_hook: bool,
+}
 
-/// Minimum cards dealt in a round
-pub const MIN_CARDS_PER_ROUND: i32 = 2;
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
 
-/// Valid card ranks in order from lowest to highest
-pub const VALID_RANKS: [&str; 13] = [
-    "2", "3", "4", "5", "6", "7", "8", "9", "T", "J", "Q", "K", "A",
-];
+impl RuleSet {
+    /// The standard four player game
+    pub fn standard() -> Self {
+        Self::with_player_count(STANDARD_PLAYER_COUNT)
+    }
+
+    /// Rules for a table of `player_count` seats (3 to 7)
+    ///
+    /// The largest hand is `floor(52 / player_count)` and the bid ceiling
+    /// matches it. See `with_player_count` for the round schedule.
+    pub fn for_players(player_count: usize) -> Result<Self, String> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&player_count) {
+            return Err(format!(
+                "Player count must be between {MIN_PLAYERS} and {MAX_PLAYERS}"
+            ));
+        }
+        Ok(Self::with_player_count(player_count))
+    }
+
+    /// Build the rule set for a table size without range checking
+    ///
+    /// Round sequence, where max is floor(52 / player_count):
+    /// max → max-1 → ... → 3 → 2 (once per player) → 3 → ... → max
+    /// With 4 players that is 13 → ... → 3 → 2 → 2 → 2 → 2 → 3 → ... → 13
+    fn with_player_count(player_count: usize) -> Self {
+        let max_cards = (DECK_SIZE / player_count) as i32;
+        let descending = (3..=max_cards).rev();
+        let two_card_rounds = std::iter::repeat_n(2, player_count);
+        let ascending = 3..=max_cards;
+
+        Self {
+            player_count,
+            round_schedule: descending.chain(two_card_rounds).chain(ascending).collect(),
+            min_bid: 0,
+            max_bid: max_cards,
+            scoring: ScoringVariant::Standard,
+            dealer_hook: false,
+        }
+    }
 
-/// Valid card suits
-pub const VALID_SUITS: [&str; 4] = ["S", "H", "D", "C"];
+    /// Check that the rule set describes a playable game
+    pub fn validate(&self) -> Result<(), String> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&self.player_count) {
+            return Err(format!(
+                "Player count must be between {MIN_PLAYERS} and {MAX_PLAYERS}"
+            ));
+        }
+        if self.round_schedule.is_empty() {
+            return Err("Round schedule must contain at least one round".to_string());
+        }
+        if self.round_schedule.iter().any(|&cards| cards < 1) {
+            return Err("Every round must deal at least one card".to_string());
+        }
+        if self.max_cards_per_round() as usize * self.player_count > DECK_SIZE {
+            return Err(format!(
+                "Round schedule deals more than {DECK_SIZE} cards to {} players",
+                self.player_count
+            ));
+        }
+        if self.min_bid < 0 || self.min_bid > self.max_bid {
+            return Err("Bid bounds must satisfy 0 <= min_bid <= max_bid".to_string());
+        }
+        Ok(())
+    }
 
-/// Card rank values for comparison (2=2, 3=3, ..., A=14)
-pub const CARD_RANK_VALUES: [i32; 13] = [2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14];
+    /// Total number of rounds in a game
+    pub fn total_rounds(&self) -> i32 {
+        self.round_schedule.len() as i32
+    }
 
-/// Calculate the number of cards to deal for a given round number
-///
-/// Round sequence: 13 → 12 → 11 → ... → 3 → 2 → 2 → 2 → 2 → 3 → 4 → ... → 13
-/// - Rounds 1-11: 13 cards down to 3 cards (14 - round_number)
-/// - Rounds 12-15: 4 rounds of 2 cards
-/// - Rounds 16-26: 3 cards up to 13 cards (round_number - 15 + 2)
-pub fn calculate_cards_dealt(round_number: i32) -> i32 {
-    if round_number <= 11 {
-        // Rounds 1-11: 13 cards down to 3 cards
-        14 - round_number
-    } else if round_number <= 15 {
-        // Rounds 12-15: 4 rounds of 2 cards
-        2
-    } else {
-        // Rounds 16-26: 3 cards up to 13 cards
-        round_number - 15 + 2
+    /// Maximum cards dealt in any round
+    pub fn max_cards_per_round(&self) -> i32 {
+        self.round_schedule.iter().copied().max().unwrap_or(0)
     }
-}
 
-/// Get the next round number in sequence
-/// Returns None if the game is complete (after round 26)
-pub fn get_next_round_number(current_round: i32) -> Option<i32> {
-    if current_round >= TOTAL_ROUNDS {
-        None
-    } else {
-        Some(current_round + 1)
+    /// Number of cards to deal for a given round number
+    /// Returns None if the round number is outside the schedule
+    pub fn cards_dealt(&self, round_number: i32) -> Option<i32> {
+        if !self.is_valid_round_number(round_number) {
+            return None;
+        }
+        self.round_schedule
+            .get((round_number - 1) as usize)
+            .copied()
     }
-}
 
-/// Get the previous round number in sequence
-/// Returns None if this is the first round
-pub fn get_previous_round_number(current_round: i32) -> Option<i32> {
-    if current_round <= 1 {
-        None
-    } else {
-        Some(current_round - 1)
+    /// Check if a round number is valid (1 to total rounds)
+    pub fn is_valid_round_number(&self, round_number: i32) -> bool {
+        (1..=self.total_rounds()).contains(&round_number)
     }
-}
 
-/// Check if a round number is valid (1-26)
-pub fn is_valid_round_number(round_number: i32) -> bool {
-    (1..=TOTAL_ROUNDS).contains(&round_number)
-}
+    /// Get the next round number in sequence
+    /// Returns None if the game is complete (after the last round)
+    pub fn next_round_number(&self, current_round: i32) -> Option<i32> {
+        if self.is_game_complete(current_round) {
+            None
+        } else {
+            Some(current_round + 1)
+        }
+    }
 
-/// Check if a round is a 2-card round
-pub fn is_two_card_round(round_number: i32) -> bool {
-    (TWO_CARD_ROUNDS_START..=TWO_CARD_ROUNDS_END).contains(&round_number)
-}
+    /// Get the number of cards to deal for the next round
+    /// Returns None if the game is complete.
+    pub fn next_round_cards(&self, current_round: i32) -> Option<i32> {
+        self.next_round_number(current_round)
+            .and_then(|round_number| self.cards_dealt(round_number))
+    }
 
-/// Get the next player index in turn order (with wraparound)
-pub fn get_next_player_index(current_player: usize) -> usize {
-    (current_player + 1) % PLAYER_COUNT
-}
+    /// Check if a game is complete (all rounds in the schedule played)
+    pub fn is_game_complete(&self, current_round: i32) -> bool {
+        current_round >= self.total_rounds()
+    }
 
-/// Get the previous player index in turn order (with wraparound)
-pub fn get_previous_player_index(current_player: usize) -> usize {
-    if current_player == 0 {
-        PLAYER_COUNT - 1
-    } else {
-        current_player - 1
+    /// The scoring rule this game was created with
+    pub fn scoring_rule(&self) -> &'static dyn ScoringRule {
+        self.scoring.rule()
     }
-}
 
-/// Get the next dealer index (with wraparound)
-pub fn get_next_dealer_index(current_dealer: usize) -> usize {
-    (current_dealer + 1) % PLAYER_COUNT
-}
+    /// Check if a bid is within the allowed bounds
+    pub fn is_valid_bid(&self, bid: i32) -> bool {
+        (self.min_bid..=self.max_bid).contains(&bid)
+    }
+
+    /// Highest bid allowed in a round: nobody can win more tricks than cards dealt
+    pub fn max_bid_for_round(&self, cards_dealt: i32) -> i32 {
+        self.max_bid.min(cards_dealt)
+    }
+
+    /// Check if a bid is within the allowed bounds for a round
+    pub fn is_valid_bid_for_round(&self, bid: i32, cards_dealt: i32) -> bool {
+        (self.min_bid..=self.max_bid_for_round(cards_dealt)).contains(&bid)
+    }
+
+    /// Get the next player index in turn order (with wraparound)
+    pub fn next_player_index(&self, current_player: usize) -> usize {
+        (current_player + 1) % self.player_count
+    }
+
+    /// Get the previous player index in turn order (with wraparound)
+    pub fn previous_player_index(&self, current_player: usize) -> usize {
+        if current_player == 0 {
+            self.player_count - 1
+        } else {
+            current_player - 1
+        }
+    }
 
-/// Get the dealer index for a given round number
-/// Dealer rotates each round, starting with player 0
-pub fn get_dealer_index_for_round(round_number: i32) -> usize {
-    ((round_number - 1) % PLAYER_COUNT as i32) as usize
+    /// Get the dealer index for a given round number
+    /// Dealer rotates each round, starting with player 0
+    pub fn dealer_index_for_round(&self, round_number: i32) -> usize {
+        ((round_number - 1) % self.player_count as i32) as usize
+    }
 }
 
-/// Get the canonical player index from a turn order value
-/// This converts database turn_order to 0-based player index
-pub fn canonical_player_index(turn_order: i32) -> usize {
-    (turn_order % PLAYER_COUNT as i32) as usize
+/// Get the previous round number in sequence
+/// Returns None if this is the first round
+pub fn get_previous_round_number(current_round: i32) -> Option<i32> {
+    if current_round <= 1 {
+        None
+    } else {
+        Some(current_round - 1)
+    }
 }
 
 /// Get the turn order value from a canonical player index
@@ -128,67 +222,22 @@ pub fn turn_order_from_index(player_index: usize) -> i32 {
 /// Validate card format (e.g., "AS", "KH", "2C")
 /// Card must be exactly 2 characters: rank + suit
 pub fn is_valid_card_format(card: &str) -> bool {
-    if card.len() != 2 {
-        return false;
-    }
-
-    let rank = &card[0..1];
-    let suit = &card[1..2];
-
-    VALID_RANKS.contains(&rank) && VALID_SUITS.contains(&suit)
-}
-
-/// Get card rank value for comparison (2=2, 3=3, ..., A=14)
-pub fn get_card_rank_value(rank: &str) -> i32 {
-    match rank {
-        "2" => 2,
-        "3" => 3,
-        "4" => 4,
-        "5" => 5,
-        "6" => 6,
-        "7" => 7,
-        "8" => 8,
-        "9" => 9,
-        "T" => 10,
-        "J" => 11,
-        "Q" => 12,
-        "K" => 13,
-        "A" => 14,
-        _ => 0,
-    }
+    card.parse::<Card>().is_ok()
 }
 
-/// Check if a suit is the trump suit
-pub fn is_trump_suit(suit: &str, trump_suit: &Option<String>) -> bool {
-    trump_suit.as_ref().is_some_and(|trump| suit == trump)
-}
-
-/// Get the lead suit from a card
-pub fn get_card_suit(card: &str) -> Option<&str> {
-    if card.len() >= 2 {
-        Some(&card[1..2])
-    } else {
-        None
-    }
-}
-
-/// Get the rank from a card
-pub fn get_card_rank(card: &str) -> Option<&str> {
-    if !card.is_empty() {
-        Some(&card[0..1])
-    } else {
-        None
-    }
+/// Check if a suit is the trump suit (never true in a no trump round)
+pub fn is_trump_suit(suit: Suit, trump: Trump) -> bool {
+    trump.suit() == Some(suit)
 }
 
 /// Check if a card follows suit (matches the lead suit)
-pub fn follows_suit(card: &str, lead_suit: &str) -> bool {
-    get_card_suit(card) == Some(lead_suit)
+pub fn follows_suit(card: &Card, lead_suit: Suit) -> bool {
+    card.suit == lead_suit
 }
 
 /// Check if a card is trump
-pub fn is_trump_card(card: &str, trump_suit: &Option<String>) -> bool {
-    get_card_suit(card).is_some_and(|suit| is_trump_suit(suit, trump_suit))
+pub fn is_trump_card(card: &Card, trump: Trump) -> bool {
+    is_trump_suit(card.suit, trump)
 }
 
 /// Compare two cards to determine which wins
@@ -198,20 +247,15 @@ pub fn is_trump_card(card: &str, trump_suit: &Option<String>) -> bool {
 /// - Some(Ordering::Equal) if cards are equal
 /// - None if cards cannot be compared (different suits, no trump)
 pub fn compare_cards(
-    card1: &str,
-    card2: &str,
-    lead_suit: &str,
-    trump_suit: &Option<String>,
+    card1: &Card,
+    card2: &Card,
+    lead_suit: Suit,
+    trump: Trump,
 ) -> Option<std::cmp::Ordering> {
-    let suit1 = get_card_suit(card1)?;
-    let suit2 = get_card_suit(card2)?;
-    let rank1 = get_card_rank(card1)?;
-    let rank2 = get_card_rank(card2)?;
-
-    let is_trump1 = is_trump_suit(suit1, trump_suit);
-    let is_trump2 = is_trump_suit(suit2, trump_suit);
-    let follows_lead1 = suit1 == lead_suit;
-    let follows_lead2 = suit2 == lead_suit;
+    let is_trump1 = is_trump_card(card1, trump);
+    let is_trump2 = is_trump_card(card2, trump);
+    let follows_lead1 = follows_suit(card1, lead_suit);
+    let follows_lead2 = follows_suit(card2, lead_suit);
 
     // Trump beats non-trump
     if is_trump1 && !is_trump2 {
@@ -223,25 +267,13 @@ pub fn compare_cards(
 
     // If both are trump or both follow lead, compare ranks
     if (is_trump1 && is_trump2) || (follows_lead1 && follows_lead2) {
-        let value1 = get_card_rank_value(rank1);
-        let value2 = get_card_rank_value(rank2);
-        return Some(value1.cmp(&value2));
+        return Some(card1.rank.cmp(&card2.rank));
     }
 
     // Cards cannot be compared (different non-trump suits)
     None
 }
 
-/// Get the round sequence as a vector for testing/validation
-pub fn get_round_sequence() -> Vec<i32> {
-    (1..=TOTAL_ROUNDS).collect()
-}
-
-/// Get the card counts for each round as a vector for testing/validation
-pub fn get_round_card_counts() -> Vec<i32> {
-    (1..=TOTAL_ROUNDS).map(calculate_cards_dealt).collect()
-}
-
 /// Check if a round is complete (all tricks have been played)
 ///
 /// This function is PURE - it checks if the round has the expected number of tricks.
@@ -253,7 +285,7 @@ pub fn is_round_complete(tricks_played: i32, cards_dealt: i32) -> bool {
 /// Get the next dealer for the next round
 ///
 /// This function is PURE - it calculates the next dealer using modulo arithmetic.
-/// Returns the next dealer index (0-3) with wraparound.
+/// Returns the next dealer index (0 to player_count - 1) with wraparound.
 pub fn get_next_dealer_for_round(current_dealer: Option<usize>, player_count: usize) -> usize {
     if let Some(dealer) = current_dealer {
         (dealer + 1) % player_count
@@ -262,26 +294,6 @@ pub fn get_next_dealer_for_round(current_dealer: Option<usize>, player_count: us
     }
 }
 
-/// Check if a game is complete (all 26 rounds played)
-///
-/// This function is PURE - it checks if the game has reached the maximum rounds.
-/// Returns true if the game is complete.
-pub fn is_game_complete(current_round: i32) -> bool {
-    current_round >= TOTAL_ROUNDS
-}
-
-/// Get the number of cards to deal for the next round
-///
-/// This function is PURE - it calculates the next round's card count.
-/// Returns the number of cards to deal, or None if the game is complete.
-pub fn get_next_round_cards(current_round: i32) -> Option<i32> {
-    if current_round >= TOTAL_ROUNDS {
-        None
-    } else {
-        Some(calculate_cards_dealt(current_round + 1))
-    }
-}
-
 /// Validate that a player's turn is valid
 ///
 /// This function is PURE - it checks if a turn index is within valid bounds.
@@ -310,21 +322,23 @@ pub fn turn_order_from_player_index(player_index: usize) -> i32 {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_round_sequence_length() {
-        assert_eq!(get_round_sequence().len(), TOTAL_ROUNDS as usize);
+    fn card(notation: &str) -> Card {
+        notation.parse().unwrap()
     }
 
     #[test]
-    fn test_round_sequence_bounds() {
-        let sequence = get_round_sequence();
-        assert_eq!(sequence.first(), Some(&1));
-        assert_eq!(sequence.last(), Some(&TOTAL_ROUNDS));
+    fn test_standard_rule_set_is_valid() {
+        let rules = RuleSet::standard();
+        assert_eq!(rules.validate(), Ok(()));
+        assert_eq!(rules, RuleSet::default());
+        assert_eq!(rules.player_count, 4);
+        assert_eq!(rules.total_rounds(), 26);
+        assert_eq!(rules.max_cards_per_round(), 13);
     }
 
     #[test]
-    fn test_round_sequence_strictly_decreasing_then_increasing() {
-        let card_counts = get_round_card_counts();
+    fn test_round_schedule_strictly_decreasing_then_increasing() {
+        let card_counts = RuleSet::standard().round_schedule;
 
         // Rounds 1-11: strictly decreasing (13 → 12 → 11 → ... → 3)
         for i in 0..10 {
@@ -344,51 +358,168 @@ mod tests {
 
     #[test]
     fn test_exactly_four_two_card_rounds() {
-        let two_card_count = get_round_card_counts()
+        let two_card_count = RuleSet::standard()
+            .round_schedule
             .iter()
             .filter(|&&count| count == 2)
             .count();
-        assert_eq!(two_card_count, TWO_CARD_ROUNDS as usize);
+        assert_eq!(two_card_count, 4);
     }
 
     #[test]
-    fn test_round_sequence_starts_and_ends_with_13() {
-        let card_counts = get_round_card_counts();
-        assert_eq!(card_counts[0], MAX_CARDS_PER_ROUND); // Round 1: 13 cards
-        assert_eq!(card_counts[25], MAX_CARDS_PER_ROUND); // Round 26: 13 cards
+    fn test_cards_dealt() {
+        let rules = RuleSet::standard();
+        assert_eq!(rules.cards_dealt(1), Some(13));
+        assert_eq!(rules.cards_dealt(11), Some(3));
+        assert_eq!(rules.cards_dealt(12), Some(2));
+        assert_eq!(rules.cards_dealt(15), Some(2));
+        assert_eq!(rules.cards_dealt(16), Some(3));
+        assert_eq!(rules.cards_dealt(26), Some(13));
+        assert_eq!(rules.cards_dealt(0), None);
+        assert_eq!(rules.cards_dealt(27), None);
     }
 
     #[test]
     fn test_turn_order_wraparound() {
-        assert_eq!(get_next_player_index(0), 1);
-        assert_eq!(get_next_player_index(1), 2);
-        assert_eq!(get_next_player_index(2), 3);
-        assert_eq!(get_next_player_index(3), 0); // Wraparound
+        let rules = RuleSet::standard();
+        assert_eq!(rules.next_player_index(0), 1);
+        assert_eq!(rules.next_player_index(1), 2);
+        assert_eq!(rules.next_player_index(2), 3);
+        assert_eq!(rules.next_player_index(3), 0); // Wraparound
 
-        assert_eq!(get_previous_player_index(0), 3); // Wraparound
-        assert_eq!(get_previous_player_index(1), 0);
-        assert_eq!(get_previous_player_index(2), 1);
-        assert_eq!(get_previous_player_index(3), 2);
+        assert_eq!(rules.previous_player_index(0), 3); // Wraparound
+        assert_eq!(rules.previous_player_index(1), 0);
+        assert_eq!(rules.previous_player_index(2), 1);
+        assert_eq!(rules.previous_player_index(3), 2);
     }
 
     #[test]
     fn test_dealer_rotation() {
-        assert_eq!(get_dealer_index_for_round(1), 0);
-        assert_eq!(get_dealer_index_for_round(2), 1);
-        assert_eq!(get_dealer_index_for_round(3), 2);
-        assert_eq!(get_dealer_index_for_round(4), 3);
-        assert_eq!(get_dealer_index_for_round(5), 0); // Wraparound
-        assert_eq!(get_dealer_index_for_round(26), 1); // Last round
+        let rules = RuleSet::standard();
+        assert_eq!(rules.dealer_index_for_round(1), 0);
+        assert_eq!(rules.dealer_index_for_round(2), 1);
+        assert_eq!(rules.dealer_index_for_round(3), 2);
+        assert_eq!(rules.dealer_index_for_round(4), 3);
+        assert_eq!(rules.dealer_index_for_round(5), 0); // Wraparound
+        assert_eq!(rules.dealer_index_for_round(26), 1); // Last round
+    }
+
+    #[test]
+    fn test_bid_bounds() {
+        let rules = RuleSet::standard();
+        assert!(rules.is_valid_bid(0));
+        assert!(rules.is_valid_bid(7));
+        assert!(rules.is_valid_bid(13));
+        assert!(!rules.is_valid_bid(-1));
+        assert!(!rules.is_valid_bid(14));
+
+        assert_eq!(rules.max_bid_for_round(2), 2);
+        assert_eq!(rules.max_bid_for_round(13), 13);
+        assert!(rules.is_valid_bid_for_round(2, 2));
+        assert!(!rules.is_valid_bid_for_round(3, 2));
+        assert!(!rules.is_valid_bid_for_round(-1, 2));
+    }
+
+    #[test]
+    fn test_house_variant_rule_set() {
+        let rules = RuleSet {
+            player_count: 3,
+            round_schedule: vec![5, 3, 1, 3, 5],
+            min_bid: 0,
+            max_bid: 5,
+            scoring: ScoringVariant::MinusDifference,
+            dealer_hook: true,
+        };
+        assert_eq!(rules.validate(), Ok(()));
+        assert_eq!(rules.total_rounds(), 5);
+        assert_eq!(rules.next_player_index(2), 0);
+        assert_eq!(rules.dealer_index_for_round(4), 0);
+        assert_eq!(rules.next_round_cards(2), Some(1));
+        assert!(rules.is_game_complete(5));
+        assert!(!rules.is_valid_bid(6));
     }
 
     #[test]
-    fn test_canonical_player_index() {
-        assert_eq!(canonical_player_index(0), 0);
-        assert_eq!(canonical_player_index(1), 1);
-        assert_eq!(canonical_player_index(2), 2);
-        assert_eq!(canonical_player_index(3), 3);
-        assert_eq!(canonical_player_index(4), 0); // Wraparound
-        assert_eq!(canonical_player_index(7), 3); // Wraparound
+    fn test_rule_sets_for_every_table_size() {
+        for player_count in MIN_PLAYERS..=MAX_PLAYERS {
+            let rules = RuleSet::for_players(player_count).unwrap();
+            let max_cards = (DECK_SIZE / player_count) as i32;
+
+            assert_eq!(rules.validate(), Ok(()));
+            assert_eq!(rules.player_count, player_count);
+            assert_eq!(rules.max_cards_per_round(), max_cards);
+            assert_eq!(rules.max_bid, max_cards);
+            assert_eq!(rules.cards_dealt(1), Some(max_cards));
+            assert_eq!(rules.cards_dealt(rules.total_rounds()), Some(max_cards));
+
+            // Every player deals exactly one 2-card round
+            let two_card_rounds = rules
+                .round_schedule
+                .iter()
+                .filter(|&&count| count == 2)
+                .count();
+            assert_eq!(two_card_rounds, player_count);
+
+            // Dealer rotation covers every seat
+            assert_eq!(
+                rules.dealer_index_for_round(player_count as i32),
+                player_count - 1
+            );
+            assert_eq!(rules.dealer_index_for_round(player_count as i32 + 1), 0);
+        }
+
+        assert_eq!(RuleSet::for_players(4).unwrap(), RuleSet::standard());
+        assert_eq!(RuleSet::for_players(7).unwrap().total_rounds(), 17);
+        assert!(RuleSet::for_players(2).is_err());
+        assert!(RuleSet::for_players(8).is_err());
+    }
+
+    #[test]
+    fn test_rule_set_validation() {
+        let too_many_cards = RuleSet {
+            player_count: 5,
+            ..RuleSet::standard()
+        };
+        assert!(too_many_cards.validate().is_err());
+
+        let too_many_players = RuleSet {
+            player_count: 8,
+            ..RuleSet::for_players(7).unwrap()
+        };
+        assert!(too_many_players.validate().is_err());
+
+        let too_many_cards = RuleSet {
+            round_schedule: vec![18],
+            ..RuleSet::for_players(3).unwrap()
+        };
+        assert!(too_many_cards.validate().is_err());
+
+        let no_rounds = RuleSet {
+            round_schedule: vec![],
+            ..RuleSet::standard()
+        };
+        assert!(no_rounds.validate().is_err());
+
+        let inverted_bids = RuleSet {
+            min_bid: 5,
+            max_bid: 4,
+            ..RuleSet::standard()
+        };
+        assert!(inverted_bids.validate().is_err());
+    }
+
+    #[test]
+    fn test_rule_set_serde_round_trip() {
+        let rules = RuleSet::standard();
+        let json = serde_json::to_value(&rules).unwrap();
+        assert_eq!(json["player_count"], 4);
+        assert_eq!(json["scoring"], "standard");
+        assert_eq!(serde_json::from_value::<RuleSet>(json).unwrap(), rules);
+
+        // Rule sets stored before scoring was configurable use standard scoring
+        let mut legacy = serde_json::to_value(&rules).unwrap();
+        legacy.as_object_mut().unwrap().remove("scoring");
+        assert_eq!(serde_json::from_value::<RuleSet>(legacy).unwrap(), rules);
     }
 
     #[test]
@@ -412,92 +543,78 @@ mod tests {
         assert!(!is_valid_card_format("AX")); // Invalid suit
     }
 
-    #[test]
-    fn test_card_rank_values() {
-        assert_eq!(get_card_rank_value("2"), 2);
-        assert_eq!(get_card_rank_value("9"), 9);
-        assert_eq!(get_card_rank_value("T"), 10);
-        assert_eq!(get_card_rank_value("J"), 11);
-        assert_eq!(get_card_rank_value("Q"), 12);
-        assert_eq!(get_card_rank_value("K"), 13);
-        assert_eq!(get_card_rank_value("A"), 14);
-        assert_eq!(get_card_rank_value("X"), 0); // Invalid rank
-    }
-
     #[test]
     fn test_trump_suit_checking() {
-        assert!(is_trump_suit("H", &Some("H".to_string())));
-        assert!(!is_trump_suit("S", &Some("H".to_string())));
-        assert!(!is_trump_suit("H", &None));
-    }
-
-    #[test]
-    fn test_card_suit_and_rank_extraction() {
-        assert_eq!(get_card_suit("AS"), Some("S"));
-        assert_eq!(get_card_rank("AS"), Some("A"));
-        assert_eq!(get_card_suit(""), None);
-        assert_eq!(get_card_rank(""), None);
+        assert!(is_trump_suit(Suit::Hearts, Trump::Suit(Suit::Hearts)));
+        assert!(!is_trump_suit(Suit::Spades, Trump::Suit(Suit::Hearts)));
+        assert!(!is_trump_suit(Suit::Hearts, Trump::NoTrump));
     }
 
     #[test]
     fn test_follows_suit() {
-        assert!(follows_suit("AS", "S"));
-        assert!(!follows_suit("AH", "S"));
+        assert!(follows_suit(&card("AS"), Suit::Spades));
+        assert!(!follows_suit(&card("AH"), Suit::Spades));
     }
 
     #[test]
     fn test_is_trump_card() {
-        assert!(is_trump_card("AH", &Some("H".to_string())));
-        assert!(!is_trump_card("AS", &Some("H".to_string())));
-        assert!(!is_trump_card("AH", &None));
+        assert!(is_trump_card(&card("AH"), Trump::Suit(Suit::Hearts)));
+        assert!(!is_trump_card(&card("AS"), Trump::Suit(Suit::Hearts)));
+        assert!(!is_trump_card(&card("AH"), Trump::NoTrump));
     }
 
     #[test]
     fn test_compare_cards() {
         // Same suit, different ranks
         assert_eq!(
-            compare_cards("AS", "KS", "S", &None),
+            compare_cards(&card("AS"), &card("KS"), Suit::Spades, Trump::NoTrump),
             Some(std::cmp::Ordering::Greater)
         );
         assert_eq!(
-            compare_cards("KS", "AS", "S", &None),
+            compare_cards(&card("KS"), &card("AS"), Suit::Spades, Trump::NoTrump),
             Some(std::cmp::Ordering::Less)
         );
 
         // Trump vs non-trump
         assert_eq!(
-            compare_cards("AH", "AS", "S", &Some("H".to_string())),
+            compare_cards(
+                &card("AH"),
+                &card("AS"),
+                Suit::Spades,
+                Trump::Suit(Suit::Hearts)
+            ),
+            Some(std::cmp::Ordering::Greater)
+        );
+
+        // Ten ranks above nine (no byte-wise comparison of 'T' vs '9')
+        assert_eq!(
+            compare_cards(&card("TD"), &card("9D"), Suit::Diamonds, Trump::NoTrump),
             Some(std::cmp::Ordering::Greater)
         );
 
         // Different non-trump suits (cannot compare)
-        assert_eq!(compare_cards("AS", "KH", "S", &None), None);
+        assert_eq!(
+            compare_cards(&card("AS"), &card("KH"), Suit::Spades, Trump::NoTrump),
+            None
+        );
     }
 
     #[test]
     fn test_round_number_validation() {
-        assert!(is_valid_round_number(1));
-        assert!(is_valid_round_number(13));
-        assert!(is_valid_round_number(26));
-        assert!(!is_valid_round_number(0));
-        assert!(!is_valid_round_number(27));
-    }
-
-    #[test]
-    fn test_two_card_rounds() {
-        assert!(!is_two_card_round(11));
-        assert!(is_two_card_round(12));
-        assert!(is_two_card_round(13));
-        assert!(is_two_card_round(14));
-        assert!(is_two_card_round(15));
-        assert!(!is_two_card_round(16));
+        let rules = RuleSet::standard();
+        assert!(rules.is_valid_round_number(1));
+        assert!(rules.is_valid_round_number(13));
+        assert!(rules.is_valid_round_number(26));
+        assert!(!rules.is_valid_round_number(0));
+        assert!(!rules.is_valid_round_number(27));
     }
 
     #[test]
     fn test_next_round_number() {
-        assert_eq!(get_next_round_number(1), Some(2));
-        assert_eq!(get_next_round_number(25), Some(26));
-        assert_eq!(get_next_round_number(26), None);
+        let rules = RuleSet::standard();
+        assert_eq!(rules.next_round_number(1), Some(2));
+        assert_eq!(rules.next_round_number(25), Some(26));
+        assert_eq!(rules.next_round_number(26), None);
     }
 
     #[test]
@@ -526,17 +643,19 @@ mod tests {
 
     #[test]
     fn test_is_game_complete() {
-        assert!(!is_game_complete(25));
-        assert!(is_game_complete(26));
-        assert!(is_game_complete(27));
+        let rules = RuleSet::standard();
+        assert!(!rules.is_game_complete(25));
+        assert!(rules.is_game_complete(26));
+        assert!(rules.is_game_complete(27));
     }
 
     #[test]
     fn test_get_next_round_cards() {
-        assert_eq!(get_next_round_cards(1), Some(12)); // Round 2: 12 cards
-        assert_eq!(get_next_round_cards(11), Some(2)); // Round 12: 2 cards
-        assert_eq!(get_next_round_cards(15), Some(3)); // Round 16: 3 cards
-        assert_eq!(get_next_round_cards(26), None); // Game complete
+        let rules = RuleSet::standard();
+        assert_eq!(rules.next_round_cards(1), Some(12)); // Round 2: 12 cards
+        assert_eq!(rules.next_round_cards(11), Some(2)); // Round 12: 2 cards
+        assert_eq!(rules.next_round_cards(15), Some(3)); // Round 16: 3 cards
+        assert_eq!(rules.next_round_cards(26), None); // Game complete
     }
 
     #[test]
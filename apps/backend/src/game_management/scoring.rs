@@ -1,9 +1,113 @@
 //! Scoring module
 //!
 //! This module contains pure scoring calculation logic and point calculation
-//! helpers for the Nommie card game.
+//! helpers for the Nommie card game. The scoring variant a game uses is
+//! chosen at creation and stored in its `RuleSet`.
 
-/// Calculate points for a round based on tricks won and bid
+use serde::{Deserialize, Serialize};
+
+/// Bonus for making an exact bid under the standard rule
+pub const EXACT_BID_BONUS: i32 = 10;
+
+/// Extra bonus for making a zero bid under the zero-bid bonus rule
+pub const ZERO_BID_BONUS: i32 = 10;
+
+/// A way of turning a player's bid and tricks won into round points
+pub trait ScoringRule: Send + Sync {
+    /// Points scored for a single round
+    fn round_points(&self, tricks_won: i32, bid: i32) -> i32;
+
+    /// Whether the player earned this rule's bonus for the round
+    fn earns_bonus(&self, tricks_won: i32, bid: i32) -> bool {
+        has_exact_bid_bonus(tricks_won, bid)
+    }
+}
+
+/// 1 point per trick, plus 10 when the bid is exact
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StandardScoring;
+
+impl ScoringRule for StandardScoring {
+    fn round_points(&self, tricks_won: i32, bid: i32) -> i32 {
+        calculate_round_points(tricks_won, bid)
+    }
+}
+
+/// 10 + bid when the bid is exact, nothing otherwise
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExactBidScoring;
+
+impl ScoringRule for ExactBidScoring {
+    fn round_points(&self, tricks_won: i32, bid: i32) -> i32 {
+        if has_exact_bid_bonus(tricks_won, bid) {
+            EXACT_BID_BONUS + bid
+        } else {
+            0
+        }
+    }
+}
+
+/// 10 + bid when the bid is exact, minus the difference when missed
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinusDifferenceScoring;
+
+impl ScoringRule for MinusDifferenceScoring {
+    fn round_points(&self, tricks_won: i32, bid: i32) -> i32 {
+        if has_exact_bid_bonus(tricks_won, bid) {
+            EXACT_BID_BONUS + bid
+        } else {
+            -(tricks_won - bid).abs()
+        }
+    }
+}
+
+/// Standard scoring, with an extra bonus for making a zero bid
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZeroBidBonusScoring;
+
+impl ScoringRule for ZeroBidBonusScoring {
+    fn round_points(&self, tricks_won: i32, bid: i32) -> i32 {
+        let zero_bid_bonus = if bid == 0 && tricks_won == 0 {
+            ZERO_BID_BONUS
+        } else {
+            0
+        };
+        calculate_round_points(tricks_won, bid) + zero_bid_bonus
+    }
+}
+
+/// The built-in scoring rules a game can be created with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoringVariant {
+    #[default]
+    Standard,
+    ExactBid,
+    MinusDifference,
+    ZeroBidBonus,
+}
+
+impl ScoringVariant {
+    /// Every built-in scoring variant
+    pub const ALL: [ScoringVariant; 4] = [
+        ScoringVariant::Standard,
+        ScoringVariant::ExactBid,
+        ScoringVariant::MinusDifference,
+        ScoringVariant::ZeroBidBonus,
+    ];
+
+    /// The scoring rule implementing this variant
+    pub fn rule(self) -> &'static dyn ScoringRule {
+        match self {
+            ScoringVariant::Standard => &StandardScoring,
+            ScoringVariant::ExactBid => &ExactBidScoring,
+            ScoringVariant::MinusDifference => &MinusDifferenceScoring,
+            ScoringVariant::ZeroBidBonus => &ZeroBidBonusScoring,
+        }
+    }
+}
+
+/// Calculate points for a round based on tricks won and bid (standard rule)
 ///
 /// Points calculation:
 /// - 1 point per trick won
@@ -17,8 +121,7 @@
 /// * Total points for the round
 pub fn calculate_round_points(tricks_won: i32, bid: i32) -> i32 {
     let base_points = tricks_won;
-    let bonus = if tricks_won == bid { 10 } else { 0 };
-    base_points + bonus
+    base_points + calculate_bonus_amount(tricks_won, bid)
 }
 
 /// Calculate total score for a player across multiple rounds
@@ -28,13 +131,14 @@ pub fn calculate_round_points(tricks_won: i32, bid: i32) -> i32 {
 ///
 /// # Arguments
 /// * `round_data` - Vector of tuples containing (tricks_won, bid) for each round
+/// * `rule` - The scoring rule the game is played with
 ///
 /// # Returns
 /// * Total calculated score
-pub fn calculate_total_score_from_rounds(round_data: &[(i32, i32)]) -> i32 {
+pub fn calculate_total_score_from_rounds(round_data: &[(i32, i32)], rule: &dyn ScoringRule) -> i32 {
     round_data
         .iter()
-        .map(|(tricks_won, bid)| calculate_round_points(*tricks_won, *bid))
+        .map(|(tricks_won, bid)| rule.round_points(*tricks_won, *bid))
         .sum()
 }
 
@@ -60,7 +164,7 @@ pub fn has_exact_bid_bonus(tricks_won: i32, bid: i32) -> bool {
 /// * Bonus amount (10 if exact match, 0 otherwise)
 pub fn calculate_bonus_amount(tricks_won: i32, bid: i32) -> i32 {
     if has_exact_bid_bonus(tricks_won, bid) {
-        10
+        EXACT_BID_BONUS
     } else {
         0
     }
@@ -158,7 +262,7 @@ mod tests {
             (2, 4), // Round 4: under bid, 2 points
         ];
 
-        let total_score = calculate_total_score_from_rounds(&round_data);
+        let total_score = calculate_total_score_from_rounds(&round_data, &StandardScoring);
 
         // 15 + 3 + 17 + 2 = 37 points
         assert_eq!(total_score, 37);
@@ -167,14 +271,89 @@ mod tests {
     #[test]
     fn test_calculate_total_score_from_rounds_empty() {
         let round_data: Vec<(i32, i32)> = vec![];
-        let total_score = calculate_total_score_from_rounds(&round_data);
+        let total_score = calculate_total_score_from_rounds(&round_data, &StandardScoring);
         assert_eq!(total_score, 0);
     }
 
     #[test]
     fn test_calculate_total_score_from_rounds_single_round() {
         let round_data = vec![(6, 6)]; // Single round with exact bid
-        let total_score = calculate_total_score_from_rounds(&round_data);
+        let total_score = calculate_total_score_from_rounds(&round_data, &StandardScoring);
         assert_eq!(total_score, 16); // 6 + 10 bonus
     }
+
+    // Test scoring variants
+    #[test]
+    fn test_exact_bid_scoring() {
+        let rule = ScoringVariant::ExactBid.rule();
+        assert_eq!(rule.round_points(5, 5), 15);
+        assert_eq!(rule.round_points(0, 0), 10);
+        assert_eq!(rule.round_points(3, 5), 0);
+        assert_eq!(rule.round_points(7, 5), 0);
+    }
+
+    #[test]
+    fn test_minus_difference_scoring() {
+        let rule = ScoringVariant::MinusDifference.rule();
+        assert_eq!(rule.round_points(5, 5), 15);
+        assert_eq!(rule.round_points(3, 5), -2);
+        assert_eq!(rule.round_points(7, 5), -2);
+        assert_eq!(rule.round_points(1, 0), -1);
+    }
+
+    #[test]
+    fn test_zero_bid_bonus_scoring() {
+        let rule = ScoringVariant::ZeroBidBonus.rule();
+        assert_eq!(rule.round_points(0, 0), 20);
+        assert_eq!(rule.round_points(5, 5), 15);
+        assert_eq!(rule.round_points(3, 5), 3);
+        assert_eq!(rule.round_points(1, 0), 1);
+    }
+
+    #[test]
+    fn test_standard_variant_matches_round_points() {
+        let rule = ScoringVariant::default().rule();
+        for (tricks_won, bid) in [(5, 5), (3, 5), (0, 0), (7, 5)] {
+            assert_eq!(
+                rule.round_points(tricks_won, bid),
+                calculate_round_points(tricks_won, bid)
+            );
+        }
+    }
+
+    #[test]
+    fn test_bonus_tracks_exact_bid_under_every_variant() {
+        for variant in ScoringVariant::ALL {
+            let rule = variant.rule();
+            assert!(rule.earns_bonus(5, 5), "{variant:?}");
+            assert!(rule.earns_bonus(0, 0), "{variant:?}");
+            assert!(!rule.earns_bonus(3, 5), "{variant:?}");
+            assert!(!rule.earns_bonus(1, 0), "{variant:?}");
+        }
+    }
+
+    #[test]
+    fn test_total_score_uses_rule() {
+        let round_data = vec![(5, 5), (3, 5), (0, 0)];
+        assert_eq!(
+            calculate_total_score_from_rounds(&round_data, ScoringVariant::Standard.rule()),
+            28
+        );
+        assert_eq!(
+            calculate_total_score_from_rounds(&round_data, ScoringVariant::MinusDifference.rule()),
+            23
+        );
+    }
+
+    #[test]
+    fn test_scoring_variant_serde_names() {
+        assert_eq!(
+            serde_json::to_string(&ScoringVariant::MinusDifference).unwrap(),
+            "\"minus_difference\""
+        );
+        assert_eq!(
+            serde_json::from_str::<ScoringVariant>("\"zero_bid_bonus\"").unwrap(),
+            ScoringVariant::ZeroBidBonus
+        );
+    }
 }
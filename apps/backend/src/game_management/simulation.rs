@@ -0,0 +1,388 @@
+//! Simulation module
+//!
+//! Plays complete games between AI strategies entirely in memory, through the
+//! same engine and rules as live games but with no database or HTTP. Used by
+//! the `nommie-sim` binary to compare AI levels and rule variants.
+//!
+//! Runs are reproducible: every game draws its deal and its AI choices from a
+//! seed derived from the run's seed, and results are combined in game order
+//! however many threads play them.
+
+use std::fmt::Write as _;
+use std::thread;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::game_management::ai::{Difficulty, IsmctsStrategy, SearchBudget, SeatView, Strategy};
+use crate::game_management::engine::{Action, Event, GameEngine, Phase};
+use crate::game_management::rules::RuleSet;
+
+/// z-score for a 95% confidence interval
+const Z_95: f64 = 1.96;
+
+/// What to simulate
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    /// Rules for every game; the player count must match `players`
+    pub rules: RuleSet,
+    /// Difficulty of each seat; seats rotate every game so no level keeps
+    /// the same position at the table
+    pub players: Vec<Difficulty>,
+    pub games: usize,
+    pub seed: u64,
+    /// Worker threads; at least one is used
+    pub threads: usize,
+    /// Search budget for expert seats
+    pub expert_budget: SearchBudget,
+}
+
+/// How one seat did in one game
+#[derive(Debug, Clone, PartialEq)]
+struct SeatResult {
+    difficulty: Difficulty,
+    score: i32,
+    rounds: u32,
+    exact_bids: u32,
+    /// Share of the win: 1 for an outright win, split evenly on a tie
+    win: f64,
+}
+
+/// Results for one difficulty across every seat it played
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StrategyStats {
+    pub difficulty: Difficulty,
+    /// Games played, counting each seat separately
+    pub seat_games: usize,
+    pub mean_score: f64,
+    /// Half-width of the 95% confidence interval for the mean score
+    pub score_margin: f64,
+    pub exact_bid_rate: f64,
+    pub exact_bid_margin: f64,
+    pub win_rate: f64,
+    pub win_margin: f64,
+}
+
+/// Results of a simulation run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimReport {
+    pub games: usize,
+    pub seed: u64,
+    pub players: Vec<Difficulty>,
+    /// One entry per difficulty, in order of first seat
+    pub strategies: Vec<StrategyStats>,
+}
+
+impl SimReport {
+    /// Render the report as an aligned text table
+    pub fn to_table(&self) -> String {
+        let mut table = format!("{} games, seed {}\n", self.games, self.seed);
+        let _ = writeln!(
+            table,
+            "{:<10} {:>10} {:>18} {:>18} {:>18}",
+            "strategy", "seat games", "mean score", "exact bids", "wins"
+        );
+        for stats in &self.strategies {
+            let name = serde_json::to_value(stats.difficulty)
+                .ok()
+                .and_then(|name| name.as_str().map(str::to_string))
+                .unwrap_or_default();
+            let _ = writeln!(
+                table,
+                "{:<10} {:>10} {:>18} {:>18} {:>18}",
+                name,
+                stats.seat_games,
+                format!("{:.1} ± {:.1}", stats.mean_score, stats.score_margin),
+                format!(
+                    "{:.1}% ± {:.1}%",
+                    stats.exact_bid_rate * 100.0,
+                    stats.exact_bid_margin * 100.0
+                ),
+                format!(
+                    "{:.1}% ± {:.1}%",
+                    stats.win_rate * 100.0,
+                    stats.win_margin * 100.0
+                ),
+            );
+        }
+        table
+    }
+}
+
+/// Play every game of a run and summarise the results
+///
+/// Games are shared out across threads; the report does not depend on how
+/// many.
+pub fn run(config: &SimConfig) -> Result<SimReport, String> {
+    config.rules.validate()?;
+    if config.players.len() != config.rules.player_count {
+        return Err(format!(
+            "Expected {} players for these rules, got {}",
+            config.rules.player_count,
+            config.players.len()
+        ));
+    }
+
+    let threads = config.threads.clamp(1, config.games.max(1));
+    let mut games: Vec<(usize, Vec<SeatResult>)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|worker| {
+                scope.spawn(move || {
+                    (worker..config.games)
+                        .step_by(threads)
+                        .map(|index| play_game(config, index).map(|seats| (index, seats)))
+                        .collect::<Result<Vec<_>, String>>()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| match worker.join() {
+                Ok(games) => games,
+                Err(_) => Err("Simulation worker panicked".to_string()),
+            })
+            .collect::<Result<Vec<_>, String>>()
+            .map(|games| games.into_iter().flatten().collect())
+    })?;
+    games.sort_by_key(|(index, _)| *index);
+
+    let results: Vec<SeatResult> = games.into_iter().flat_map(|(_, seats)| seats).collect();
+    let mut difficulties: Vec<Difficulty> = Vec::new();
+    for difficulty in &config.players {
+        if !difficulties.contains(difficulty) {
+            difficulties.push(*difficulty);
+        }
+    }
+
+    Ok(SimReport {
+        games: config.games,
+        seed: config.seed,
+        players: config.players.clone(),
+        strategies: difficulties
+            .into_iter()
+            .map(|difficulty| summarise(difficulty, &results))
+            .collect(),
+    })
+}
+
+/// Play one game of a run to completion
+fn play_game(config: &SimConfig, index: usize) -> Result<Vec<SeatResult>, String> {
+    let player_count = config.players.len();
+    let game_seed = config.seed.wrapping_add(index as u64);
+    let mut rng = StdRng::seed_from_u64(game_seed);
+
+    // Rotate the seating each game
+    let seating: Vec<Difficulty> = (0..player_count)
+        .map(|seat| config.players[(seat + index) % player_count])
+        .collect();
+    let expert = IsmctsStrategy::new(config.expert_budget);
+    let strategies: Vec<&dyn Strategy> = seating
+        .iter()
+        .map(|difficulty| match difficulty {
+            Difficulty::Expert => &expert as &dyn Strategy,
+            _ => difficulty.strategy(),
+        })
+        .collect();
+
+    let player_ids: Vec<Uuid> = (0..player_count)
+        .map(|seat| Uuid::from_u128(seat as u128 + 1))
+        .collect();
+    let mut engine = GameEngine::new(config.rules.clone(), &player_ids, game_seed as i64)?;
+    for player_id in &player_ids {
+        engine
+            .apply(Action::Ready {
+                player_id: *player_id,
+            })
+            .map_err(|e| e.to_string())?;
+    }
+
+    let mut results: Vec<SeatResult> = seating
+        .iter()
+        .map(|difficulty| SeatResult {
+            difficulty: *difficulty,
+            score: 0,
+            rounds: 0,
+            exact_bids: 0,
+            win: 0.0,
+        })
+        .collect();
+
+    while engine.phase != Phase::Completed {
+        let seat = engine.current_turn().ok_or("No seat is due to act")?;
+        let player_id = player_ids[seat];
+        let view = SeatView::new(&engine, seat).ok_or("No current round found")?;
+        let strategy = strategies[seat];
+        let action = match engine.phase {
+            Phase::Bidding => Action::Bid {
+                player_id,
+                bid: strategy.choose_bid(&view, &mut rng),
+            },
+            Phase::TrumpSelection => Action::ChooseTrump {
+                player_id,
+                trump: strategy.choose_trump(&view, &mut rng),
+            },
+            _ => Action::Play {
+                player_id,
+                card: strategy.choose_card(&view, &mut rng),
+            },
+        };
+
+        for event in engine.apply(action).map_err(|e| e.to_string())? {
+            if let Event::RoundScored { results: round, .. } = event {
+                for result in round {
+                    if let Some(seat) = engine.seat_of(result.player_id) {
+                        results[seat].rounds += 1;
+                        results[seat].exact_bids += u32::from(result.bid == result.tricks_won);
+                    }
+                }
+            }
+        }
+    }
+
+    let best = engine.scores.iter().max().copied().unwrap_or_default();
+    let winners = engine.scores.iter().filter(|score| **score == best).count();
+    for (result, score) in results.iter_mut().zip(&engine.scores) {
+        result.score = *score;
+        if *score == best {
+            result.win = 1.0 / winners as f64;
+        }
+    }
+    Ok(results)
+}
+
+/// Mean and 95% margin of a sample
+///
+/// This function is PURE - it uses the normal approximation, so the margin
+/// is only meaningful for a reasonable number of samples.
+fn mean_and_margin(samples: &[f64]) -> (f64, f64) {
+    if samples.is_empty() {
+        return (0.0, 0.0);
+    }
+    let count = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / count;
+    if samples.len() < 2 {
+        return (mean, 0.0);
+    }
+    let variance = samples
+        .iter()
+        .map(|sample| (sample - mean).powi(2))
+        .sum::<f64>()
+        / (count - 1.0);
+    (mean, Z_95 * (variance / count).sqrt())
+}
+
+/// Combine every seat one difficulty played
+fn summarise(difficulty: Difficulty, results: &[SeatResult]) -> StrategyStats {
+    let seats: Vec<&SeatResult> = results
+        .iter()
+        .filter(|result| result.difficulty == difficulty)
+        .collect();
+
+    let scores: Vec<f64> = seats.iter().map(|seat| seat.score as f64).collect();
+    let (mean_score, score_margin) = mean_and_margin(&scores);
+    let wins: Vec<f64> = seats.iter().map(|seat| seat.win).collect();
+    let (win_rate, win_margin) = mean_and_margin(&wins);
+
+    let rounds: u32 = seats.iter().map(|seat| seat.rounds).sum();
+    let exact: u32 = seats.iter().map(|seat| seat.exact_bids).sum();
+    let exact_bid_rate = if rounds > 0 {
+        exact as f64 / rounds as f64
+    } else {
+        0.0
+    };
+    let exact_bid_margin = if rounds > 0 {
+        Z_95 * (exact_bid_rate * (1.0 - exact_bid_rate) / rounds as f64).sqrt()
+    } else {
+        0.0
+    };
+
+    StrategyStats {
+        difficulty,
+        seat_games: seats.len(),
+        mean_score,
+        score_margin,
+        exact_bid_rate,
+        exact_bid_margin,
+        win_rate,
+        win_margin,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(threads: usize) -> SimConfig {
+        SimConfig {
+            rules: RuleSet::standard(),
+            players: vec![
+                Difficulty::Easy,
+                Difficulty::Hard,
+                Difficulty::Easy,
+                Difficulty::Hard,
+            ],
+            games: 8,
+            seed: 42,
+            threads,
+            expert_budget: SearchBudget::default(),
+        }
+    }
+
+    #[test]
+    fn test_mean_and_margin() {
+        assert_eq!(mean_and_margin(&[]), (0.0, 0.0));
+        assert_eq!(mean_and_margin(&[3.0]), (3.0, 0.0));
+        let (mean, margin) = mean_and_margin(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(mean, 2.5);
+        assert!((margin - Z_95 * (5.0f64 / 12.0).sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_run_is_reproducible_across_threads() {
+        let report = run(&config(1)).unwrap();
+        assert_eq!(report, run(&config(1)).unwrap());
+        assert_eq!(report, run(&config(3)).unwrap());
+
+        let mut reseeded = config(1);
+        reseeded.seed = 43;
+        assert_ne!(report, run(&reseeded).unwrap());
+    }
+
+    #[test]
+    fn test_run_reports_each_difficulty() {
+        let report = run(&config(2)).unwrap();
+        let difficulties: Vec<Difficulty> = report
+            .strategies
+            .iter()
+            .map(|stats| stats.difficulty)
+            .collect();
+        assert_eq!(difficulties, [Difficulty::Easy, Difficulty::Hard]);
+
+        let total_wins: f64 = report
+            .strategies
+            .iter()
+            .map(|stats| stats.win_rate * stats.seat_games as f64)
+            .sum();
+        assert!((total_wins - 8.0).abs() < 1e-9);
+        for stats in &report.strategies {
+            assert_eq!(stats.seat_games, 16);
+            assert!((0.0..=1.0).contains(&stats.exact_bid_rate));
+        }
+
+        // Bidding from the hand beats bidding at random
+        let (easy, hard) = (&report.strategies[0], &report.strategies[1]);
+        assert!(hard.exact_bid_rate > easy.exact_bid_rate);
+        assert!(hard.mean_score > easy.mean_score);
+
+        assert!(report.to_table().contains("hard"));
+    }
+
+    #[test]
+    fn test_run_rejects_mismatched_players() {
+        let mut mismatched = config(1);
+        mismatched.players.pop();
+        assert!(run(&mismatched).is_err());
+    }
+}
@@ -4,45 +4,33 @@
 //! and trick completion mechanisms that depend only on
 //! in-memory domain types and std.
 
-use crate::game_management::rules::get_card_rank_value;
+use crate::game_management::cards::{Card, Suit, Trump};
+use crate::game_management::rules::{follows_suit, is_trump_card};
+use serde::Serialize;
 use uuid::Uuid;
 
 // Pure domain types for trick logic
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub(crate) struct TrickState {
-    plays: Vec<(String, Uuid)>, // (card, player_id) tuples
+    plays: Vec<(Card, Uuid)>, // (card, player_id) tuples
 }
 
-/// Result of pure trick advancement logic
-///
-/// This type contains all the information needed to advance a trick
-/// after a card play, without any database dependencies.
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-pub(crate) struct TrickAdvancement {
-    /// Whether the trick is complete (all players have played)
-    pub trick_complete: bool,
-    /// The winner of the trick (if complete)
-    pub winner_user_id: Option<Uuid>,
-    /// The next player to lead (if trick complete)
-    pub next_leader_user_id: Option<Uuid>,
-    /// Whether the round is complete and should advance to scoring
-    pub round_complete: bool,
-    /// The next turn index for the game
-    pub next_turn: i32,
+impl TrickState {
+    pub(crate) fn new(plays: Vec<(Card, Uuid)>) -> Self {
+        Self { plays }
+    }
 }
 
 #[derive(Debug)]
 #[allow(dead_code)]
 pub(crate) struct ApplyPlayOutcome {
-    card_to_remove: String,
+    card_to_remove: Card,
 }
 
-#[derive(Debug)]
-#[allow(dead_code)]
-pub(crate) enum ApplyPlayError {
-    InvalidCardFormat,
+/// Reasons a card cannot be played into the current trick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplyPlayError {
     CardNotInHand,
     FollowSuitViolation,
 }
@@ -61,34 +49,25 @@ pub(crate) enum ApplyPlayError {
 /// # Returns
 /// * `Ok(ApplyPlayOutcome)` - The card to remove from hand
 /// * `Err(ApplyPlayError)` - Validation error if the play is invalid
-#[allow(dead_code)]
 pub(crate) fn apply_play_logic(
-    card: &str,
+    card: &Card,
     current_trick: &TrickState,
-    player_hand: &[String],
+    player_hand: &[Card],
 ) -> Result<ApplyPlayOutcome, ApplyPlayError> {
-    // Validate card format
-    if card.len() < 2 {
-        return Err(ApplyPlayError::InvalidCardFormat);
-    }
-
     // Check if card is in hand
-    if !player_hand.contains(&card.to_string()) {
+    if !player_hand.contains(card) {
         return Err(ApplyPlayError::CardNotInHand);
     }
 
     // Validate follow-suit rule if this isn't the first play
-    if !current_trick.plays.is_empty() {
-        let lead_suit = get_lead_suit_from_trick(&current_trick.plays).unwrap();
-        let card_suit = &card[1..2];
-
-        if card_suit != lead_suit && can_follow_suit(player_hand, &lead_suit) {
+    if let Some(lead_suit) = get_lead_suit_from_trick(&current_trick.plays) {
+        if !validate_follow_suit_rule(card, lead_suit, player_hand) {
             return Err(ApplyPlayError::FollowSuitViolation);
         }
     }
 
     Ok(ApplyPlayOutcome {
-        card_to_remove: card.to_string(),
+        card_to_remove: *card,
     })
 }
 
@@ -99,43 +78,28 @@ pub(crate) fn apply_play_logic(
 /// - Trump beats non-trump
 /// - Within the same category (trump or non-trump), highest rank wins
 /// - Lead suit is determined by the first card played
+/// - In a no trump round only the lead suit can win
 ///
 /// Returns the player ID of the trick winner.
 pub fn determine_trick_winner(
-    plays: &[(String, uuid::Uuid)], // (card, player_id) tuples
-    trump_suit: &Option<String>,
+    plays: &[(Card, uuid::Uuid)], // (card, player_id) tuples
+    trump: Trump,
 ) -> Result<uuid::Uuid, String> {
-    if plays.is_empty() {
+    let Some((lead_card, _)) = plays.first() else {
         return Err("No plays found for trick".to_string());
-    }
+    };
 
     // Get the lead suit (suit of the first card played)
-    let lead_card = &plays[0].0;
-    let lead_suit = if lead_card.len() >= 2 {
-        &lead_card[1..2]
-    } else {
-        return Err("Invalid card format".to_string());
-    };
+    let lead_suit = lead_card.suit;
 
     let mut winning_play = &plays[0];
-    let mut winning_rank = get_card_rank_value(&plays[0].0[0..1]);
-    let mut winning_is_trump = trump_suit
-        .as_ref()
-        .is_some_and(|trump| &plays[0].0[1..2] == trump);
-    let mut winning_follows_lead = &plays[0].0[1..2] == lead_suit;
+    let mut winning_is_trump = is_trump_card(lead_card, trump);
+    let mut winning_follows_lead = true;
 
     for play in &plays[1..] {
         let card = &play.0;
-        if card.len() < 2 {
-            continue; // Skip invalid cards
-        }
-
-        let rank = &card[0..1];
-        let suit = &card[1..2];
-
-        let card_rank = get_card_rank_value(rank);
-        let is_trump = trump_suit.as_ref().is_some_and(|trump| suit == trump);
-        let follows_lead = suit == lead_suit;
+        let is_trump = is_trump_card(card, trump);
+        let follows_lead = follows_suit(card, lead_suit);
 
         // Determine if this card should win
         let should_win = if is_trump && !winning_is_trump {
@@ -146,10 +110,10 @@ pub fn determine_trick_winner(
             false
         } else if is_trump && winning_is_trump {
             // Both are trump, highest rank wins
-            card_rank > winning_rank
+            card.rank > winning_play.0.rank
         } else if follows_lead && winning_follows_lead {
             // Both follow lead suit, highest rank wins
-            card_rank > winning_rank
+            card.rank > winning_play.0.rank
         } else if follows_lead && !winning_follows_lead {
             // This follows lead suit, current winner doesn't
             true
@@ -160,7 +124,6 @@ pub fn determine_trick_winner(
 
         if should_win {
             winning_play = play;
-            winning_rank = card_rank;
             winning_is_trump = is_trump;
             winning_follows_lead = follows_lead;
         }
@@ -173,25 +136,19 @@ pub fn determine_trick_winner(
 ///
 /// This function is PURE - it checks if a player has cards of the lead suit
 /// without any side effects. Returns true if the player can follow suit.
-pub fn can_follow_suit(player_hand: &[String], lead_suit: &str) -> bool {
-    player_hand.iter().any(|card| {
-        if card.len() >= 2 {
-            &card[1..2] == lead_suit
-        } else {
-            false
-        }
-    })
+pub fn can_follow_suit(player_hand: &[Card], lead_suit: Suit) -> bool {
+    player_hand.iter().any(|card| card.suit == lead_suit)
 }
 
 /// Get the next player's turn after a trick
 ///
 /// This function is PURE - it calculates the next turn using modulo arithmetic.
-/// Returns the next turn index (0-3) with wraparound.
-pub fn get_next_trick_turn(current_turn: i32) -> i32 {
-    (current_turn + 1) % 4
+/// Returns the next turn index (0 to player_count - 1) with wraparound.
+pub fn get_next_trick_turn(current_turn: i32, player_count: usize) -> i32 {
+    (current_turn + 1) % player_count as i32
 }
 
-/// Check if a trick is complete (all 4 players have played)
+/// Check if a trick is complete (every player has played)
 ///
 /// This function is PURE - it checks if the trick has the expected number of plays.
 /// Returns true if the trick is complete.
@@ -201,123 +158,34 @@ pub fn is_trick_complete(play_count: usize, player_count: usize) -> bool {
 
 /// Get the lead suit from the first card played in a trick
 ///
-/// This function is PURE - it extracts the suit from a card string.
-/// Returns the lead suit as a string, or None if the card format is invalid.
-pub fn get_lead_suit_from_trick(plays: &[(String, Uuid)]) -> Option<String> {
-    if let Some((first_card, _)) = plays.first() {
-        if first_card.len() >= 2 {
-            Some(first_card[1..2].to_string())
-        } else {
-            None
-        }
-    } else {
-        None
-    }
-}
-
-/// Determine trick advancement after a card play (pure logic, no DB I/O)
-///
-/// This function is PURE - it determines all advancement outcomes based on
-/// the current trick state, without any side effects or database operations.
-/// It reuses existing pure helpers to determine winners, completion status,
-/// and next player turns.
-///
-/// # Arguments
-/// * `trick_plays` - The current plays in the trick (card, player_id) tuples
-/// * `player_count` - Total number of players in the game
-/// * `current_turn` - Current turn index in the game
-/// * `trick_number` - Current trick number in the round
-/// * `cards_per_player` - Number of cards dealt per player this round
-/// * `trump_suit` - The trump suit for this round (if any)
-///
-/// # Returns
-/// * `TrickAdvancement` - Complete advancement information for the caller to persist
-pub(crate) fn advance_trick_logic(
-    trick_plays: &[(String, Uuid)],
-    player_count: usize,
-    current_turn: i32,
-    trick_number: i32,
-    cards_per_player: i32,
-    trump_suit: &Option<String>,
-) -> TrickAdvancement {
-    let trick_complete = is_trick_complete(trick_plays.len(), player_count);
-
-    if trick_complete {
-        // Determine the winner of the trick
-        let winner_user_id = determine_trick_winner(trick_plays, trump_suit).ok();
-
-        // Check if this was the last trick of the round
-        let total_tricks = cards_per_player;
-        let round_complete = trick_number == total_tricks;
-
-        if round_complete {
-            // Round is complete, next turn will be 0 (first player)
-            TrickAdvancement {
-                trick_complete: true,
-                winner_user_id,
-                next_leader_user_id: None, // Not relevant for round completion
-                round_complete: true,
-                next_turn: 0,
-            }
-        } else {
-            // Move to next player's turn (the winner of the trick)
-            // Note: We need to find the winner's turn order, but this function is pure
-            // so we'll return the winner's ID and let the caller handle the mapping
-            TrickAdvancement {
-                trick_complete: true,
-                winner_user_id,
-                next_leader_user_id: winner_user_id,
-                round_complete: false,
-                next_turn: -1, // Caller needs to map winner_user_id to turn order
-            }
-        }
-    } else {
-        // Move to next player's turn
-        let next_turn = get_next_trick_turn(current_turn);
-
-        TrickAdvancement {
-            trick_complete: false,
-            winner_user_id: None,
-            next_leader_user_id: None,
-            round_complete: false,
-            next_turn,
-        }
-    }
+/// This function is PURE - it extracts the suit of the first card.
+/// Returns the lead suit, or None if no card has been played yet.
+pub fn get_lead_suit_from_trick(plays: &[(Card, Uuid)]) -> Option<Suit> {
+    plays.first().map(|(first_card, _)| first_card.suit)
 }
 
 /// Validate that a card play follows the follow-suit rule
 ///
 /// This function is PURE - it validates card plays against game rules.
 /// Returns true if the play is valid, false if it violates follow-suit.
-pub fn validate_follow_suit_rule(card: &str, lead_suit: &str, player_hand: &[String]) -> bool {
-    let card_suit = if card.len() >= 2 {
-        &card[1..2]
-    } else {
-        return false;
-    };
-
+pub fn validate_follow_suit_rule(card: &Card, lead_suit: Suit, player_hand: &[Card]) -> bool {
     // If playing the lead suit, it's always valid
-    if card_suit == lead_suit {
+    if follows_suit(card, lead_suit) {
         return true;
     }
 
-    // If not playing the lead suit, check if player has any cards of lead suit
-    let has_lead_suit = player_hand.iter().any(|h| {
-        if h.len() >= 2 {
-            &h[1..2] == lead_suit
-        } else {
-            false
-        }
-    });
-
     // If player has lead suit cards, they must follow suit
-    !has_lead_suit
+    !can_follow_suit(player_hand, lead_suit)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn card(notation: &str) -> Card {
+        notation.parse().unwrap()
+    }
+
     #[test]
     fn test_determine_trick_winner_highest_lead_suit() {
         let player1 = uuid::Uuid::new_v4();
@@ -326,14 +194,14 @@ mod tests {
         let player4 = uuid::Uuid::new_v4();
 
         let plays = vec![
-            ("7H".to_string(), player1), // 7 of hearts
-            ("KH".to_string(), player2), // King of hearts
-            ("2H".to_string(), player3), // 2 of hearts
-            ("9H".to_string(), player4), // 9 of hearts
+            (card("7H"), player1), // 7 of hearts
+            (card("KH"), player2), // King of hearts
+            (card("2H"), player3), // 2 of hearts
+            (card("9H"), player4), // 9 of hearts
         ];
-        let trump_suit = None;
+        let trump = Trump::NoTrump;
 
-        let winner = determine_trick_winner(&plays, &trump_suit).unwrap();
+        let winner = determine_trick_winner(&plays, trump).unwrap();
         assert_eq!(winner, player2); // King of hearts should win
     }
 
@@ -345,43 +213,57 @@ mod tests {
         let player4 = uuid::Uuid::new_v4();
 
         let plays = vec![
-            ("AH".to_string(), player1), // Ace of hearts
-            ("2S".to_string(), player2), // 2 of spades (trump)
-            ("7H".to_string(), player3), // 7 of hearts
-            ("KS".to_string(), player4), // King of spades (trump)
+            (card("AH"), player1), // Ace of hearts
+            (card("2S"), player2), // 2 of spades (trump)
+            (card("7H"), player3), // 7 of hearts
+            (card("KS"), player4), // King of spades (trump)
         ];
-        let trump_suit = Some("S".to_string());
+        let trump = Trump::Suit(Suit::Spades);
 
-        let winner = determine_trick_winner(&plays, &trump_suit).unwrap();
+        let winner = determine_trick_winner(&plays, trump).unwrap();
         assert_eq!(winner, player4); // King of spades should win (highest trump)
     }
 
     #[test]
     fn test_determine_trick_winner_empty_plays() {
-        let plays: Vec<(String, uuid::Uuid)> = vec![];
-        let trump_suit = None;
+        let plays: Vec<(Card, uuid::Uuid)> = vec![];
+        let trump = Trump::NoTrump;
 
-        let result = determine_trick_winner(&plays, &trump_suit);
+        let result = determine_trick_winner(&plays, trump);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), "No plays found for trick");
     }
 
     #[test]
     fn test_can_follow_suit() {
-        let hand = vec!["AS".to_string(), "KH".to_string(), "2C".to_string()];
+        let hand = vec![card("AS"), card("KH"), card("2C")];
+
+        assert!(can_follow_suit(&hand, Suit::Spades));
+        assert!(can_follow_suit(&hand, Suit::Hearts));
+        assert!(can_follow_suit(&hand, Suit::Clubs));
+        assert!(!can_follow_suit(&hand, Suit::Diamonds));
+    }
 
-        assert!(can_follow_suit(&hand, "S"));
-        assert!(can_follow_suit(&hand, "H"));
-        assert!(can_follow_suit(&hand, "C"));
-        assert!(!can_follow_suit(&hand, "D"));
+    #[test]
+    fn test_validate_follow_suit_rule() {
+        let hand = vec![card("AS"), card("KH"), card("2C")];
+
+        assert!(validate_follow_suit_rule(&card("AS"), Suit::Spades, &hand));
+        assert!(!validate_follow_suit_rule(&card("KH"), Suit::Spades, &hand));
+        assert!(validate_follow_suit_rule(
+            &card("KH"),
+            Suit::Diamonds,
+            &hand
+        ));
     }
 
     #[test]
     fn test_get_next_trick_turn() {
-        assert_eq!(get_next_trick_turn(0), 1);
-        assert_eq!(get_next_trick_turn(1), 2);
-        assert_eq!(get_next_trick_turn(2), 3);
-        assert_eq!(get_next_trick_turn(3), 0); // Wraparound
+        assert_eq!(get_next_trick_turn(0, 4), 1);
+        assert_eq!(get_next_trick_turn(1, 4), 2);
+        assert_eq!(get_next_trick_turn(2, 4), 3);
+        assert_eq!(get_next_trick_turn(3, 4), 0); // Wraparound
+        assert_eq!(get_next_trick_turn(2, 3), 0); // Three player table
     }
 
     #[test]
@@ -395,110 +277,64 @@ mod tests {
     #[test]
     fn test_get_lead_suit_from_trick() {
         let plays = vec![
-            ("7H".to_string(), uuid::Uuid::new_v4()),
-            ("KH".to_string(), uuid::Uuid::new_v4()),
+            (card("7H"), uuid::Uuid::new_v4()),
+            (card("KH"), uuid::Uuid::new_v4()),
         ];
 
         let lead_suit = get_lead_suit_from_trick(&plays);
-        assert_eq!(lead_suit, Some("H".to_string()));
+        assert_eq!(lead_suit, Some(Suit::Hearts));
     }
 
     #[test]
     fn test_get_lead_suit_from_empty_trick() {
-        let plays: Vec<(String, uuid::Uuid)> = vec![];
+        let plays: Vec<(Card, uuid::Uuid)> = vec![];
         let lead_suit = get_lead_suit_from_trick(&plays);
         assert_eq!(lead_suit, None);
     }
 
     #[test]
-    fn test_advance_trick_logic_trick_not_complete() {
-        let plays = vec![
-            ("7H".to_string(), uuid::Uuid::new_v4()),
-            ("KH".to_string(), uuid::Uuid::new_v4()),
-        ];
-
-        let advancement = advance_trick_logic(
-            &plays, 4,     // 4 players
-            1,     // Current turn (player 2)
-            1,     // Trick 1
-            13,    // 13 cards per player
-            &None, // No trump
-        );
-
-        assert!(!advancement.trick_complete);
-        assert_eq!(advancement.next_turn, 2); // Next player's turn
-        assert!(!advancement.round_complete);
-    }
-
-    #[test]
-    fn test_advance_trick_logic_trick_complete_not_round_end() {
-        let plays = vec![
-            ("7H".to_string(), uuid::Uuid::new_v4()),
-            ("KH".to_string(), uuid::Uuid::new_v4()),
-            ("2H".to_string(), uuid::Uuid::new_v4()),
-            ("9H".to_string(), uuid::Uuid::new_v4()),
-        ];
-
-        let advancement = advance_trick_logic(
-            &plays, 4,     // 4 players
-            1,     // Current turn (player 2)
-            1,     // Trick 1
-            13,    // 13 cards per player
-            &None, // No trump
-        );
-
-        assert!(advancement.trick_complete);
-        assert!(!advancement.round_complete);
-        assert_eq!(advancement.next_turn, -1); // Caller needs to map winner to turn
-    }
+    fn test_determine_trick_winner_no_trump_uses_lead_suit_only() {
+        let player1 = uuid::Uuid::new_v4();
+        let player2 = uuid::Uuid::new_v4();
+        let player3 = uuid::Uuid::new_v4();
+        let player4 = uuid::Uuid::new_v4();
 
-    #[test]
-    fn test_advance_trick_logic_round_complete() {
         let plays = vec![
-            ("7H".to_string(), uuid::Uuid::new_v4()),
-            ("KH".to_string(), uuid::Uuid::new_v4()),
-            ("2H".to_string(), uuid::Uuid::new_v4()),
-            ("9H".to_string(), uuid::Uuid::new_v4()),
+            (card("7H"), player1), // 7 of hearts leads
+            (card("AS"), player2), // Ace of spades (off suit)
+            (card("9H"), player3), // 9 of hearts
+            (card("AC"), player4), // Ace of clubs (off suit)
         ];
 
-        let advancement = advance_trick_logic(
-            &plays, 4,     // 4 players
-            1,     // Current turn (player 2)
-            13,    // Trick 13 (last trick of round)
-            13,    // 13 cards per player
-            &None, // No trump
-        );
-
-        assert!(advancement.trick_complete);
-        assert!(advancement.round_complete);
-        assert_eq!(advancement.next_turn, 0); // Reset to first player
+        let winner = determine_trick_winner(&plays, Trump::NoTrump).unwrap();
+        assert_eq!(winner, player3); // Highest heart wins, off-suit aces cannot
     }
 
     #[test]
-    fn test_advance_trick_logic_with_trump() {
+    fn test_determine_trick_winner_for_every_trump() {
         let player1 = uuid::Uuid::new_v4();
         let player2 = uuid::Uuid::new_v4();
         let player3 = uuid::Uuid::new_v4();
         let player4 = uuid::Uuid::new_v4();
 
+        // Hearts led; each other suit is played once as a low card
         let plays = vec![
-            ("AH".to_string(), player1), // Ace of hearts
-            ("2S".to_string(), player2), // 2 of spades (trump)
-            ("7H".to_string(), player3), // 7 of hearts
-            ("KS".to_string(), player4), // King of spades (trump)
+            (card("KH"), player1),
+            (card("2S"), player2),
+            (card("2D"), player3),
+            (card("2C"), player4),
         ];
 
-        let advancement = advance_trick_logic(
-            &plays,
-            4,                      // 4 players
-            1,                      // Current turn (player 2)
-            1,                      // Trick 1
-            13,                     // 13 cards per player
-            &Some("S".to_string()), // Spades is trump
-        );
-
-        assert!(advancement.trick_complete);
-        assert_eq!(advancement.winner_user_id, Some(player4)); // King of spades should win (highest trump)
-        assert!(!advancement.round_complete);
+        for trump in Trump::ALL {
+            let expected = match trump.suit() {
+                Some(Suit::Spades) => player2,
+                Some(Suit::Diamonds) => player3,
+                Some(Suit::Clubs) => player4,
+                Some(Suit::Hearts) | None => player1,
+            };
+
+            let winner = determine_trick_winner(&plays, trump).unwrap();
+            assert_eq!(winner, expected, "wrong winner with trump {trump}");
+        }
     }
 }
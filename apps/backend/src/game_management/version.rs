@@ -0,0 +1,193 @@
+//! Game versions
+//!
+//! A game's version is the sequence of the latest event in its log, so it goes
+//! up with every committed change. It is published as a strong ETag, letting
+//! clients skip unchanged snapshots with `If-None-Match` or wait for the next
+//! change by long-polling. Players acting on a game may name the version they
+//! saw, with `If-Match` or an `expected_version` field, and an action on a game
+//! that has moved on since is refused.
+
+use std::env;
+use std::time::Duration;
+
+use sea_orm::sea_query::LockType;
+use sea_orm::{DatabaseConnection, DatabaseTransaction, EntityTrait, QuerySelect};
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use crate::entity::games;
+use crate::game_management::live;
+
+/// Longest a long-poll waits when `LONG_POLL_TIMEOUT_MS` is not set
+const DEFAULT_LONG_POLL_TIMEOUT_MS: u64 = 30_000;
+
+/// Longest a long-poll waits for a game to change
+///
+/// Configured with `LONG_POLL_TIMEOUT_MS`.
+pub fn long_poll_timeout() -> Duration {
+    let millis = env::var("LONG_POLL_TIMEOUT_MS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_LONG_POLL_TIMEOUT_MS);
+    Duration::from_millis(millis)
+}
+
+/// ETag for a game version
+///
+/// This function is PURE - versions are written as strong entity tags.
+pub fn etag(version: i32) -> String {
+    format!("\"{version}\"")
+}
+
+/// Read the version out of an entity tag
+///
+/// This function is PURE - weak tags (`W/"3"`) are read like strong ones, and
+/// anything that is not a quoted version gives `None`.
+pub fn parse_etag(tag: &str) -> Option<i32> {
+    let tag = tag.trim();
+    let tag = tag.strip_prefix("W/").unwrap_or(tag);
+    tag.strip_prefix('"')?.strip_suffix('"')?.parse().ok()
+}
+
+/// Whether an `If-None-Match` or `If-Match` header names a version
+///
+/// This function is PURE - the header is a comma-separated list of entity
+/// tags, or `*` for any version.
+pub fn header_matches(header: &str, version: i32) -> bool {
+    header.trim() == "*"
+        || header
+            .split(',')
+            .any(|tag| parse_etag(tag) == Some(version))
+}
+
+/// The version a player saw when they chose an action
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpectedVersion {
+    /// Entity tags sent as `If-Match`
+    pub if_match: Option<String>,
+    /// Version sent in the request body
+    pub version: Option<i32>,
+}
+
+impl ExpectedVersion {
+    /// Whether an action may be taken on a game at `version`
+    ///
+    /// This function is PURE - a player that named no version acts on any.
+    pub fn allows(&self, version: i32) -> bool {
+        self.if_match
+            .as_deref()
+            .is_none_or(|header| header_matches(header, version))
+            && self.version.is_none_or(|expected| expected == version)
+    }
+}
+
+/// Why a game could not be locked at the version a player expected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionError {
+    NotFound,
+    /// The game has moved on since the action was chosen
+    Conflict {
+        current_version: i32,
+    },
+    Storage(String),
+}
+
+/// Lock a game row, if it is still at the version expected
+///
+/// The row stays locked until `txn` ends, so nothing else can change the game
+/// between the check and the action.
+pub(crate) async fn lock_at_version(
+    game_id: Uuid,
+    expected: &ExpectedVersion,
+    txn: &DatabaseTransaction,
+) -> Result<games::Model, VersionError> {
+    let game = games::Entity::find_by_id(game_id)
+        .lock(LockType::Update)
+        .one(txn)
+        .await
+        .map_err(|e| VersionError::Storage(format!("Failed to fetch game: {e}")))?
+        .ok_or(VersionError::NotFound)?;
+
+    if !expected.allows(game.version) {
+        return Err(VersionError::Conflict {
+            current_version: game.version,
+        });
+    }
+    Ok(game)
+}
+
+/// Wait until a game moves past a version, or the long-poll timeout passes
+///
+/// Returns right away when the game is already past it.
+pub(crate) async fn wait_past(
+    game_id: Uuid,
+    version: i32,
+    db: &DatabaseConnection,
+) -> Result<(), String> {
+    // Subscribe before the first read, so no change can slip in between
+    let mut changes = live::subscribe(game_id);
+    let deadline = tokio::time::Instant::now() + long_poll_timeout();
+    loop {
+        let current = match games::Entity::find_by_id(game_id).one(db).await {
+            Ok(Some(game)) => game.version,
+            Ok(None) => return Err("Game not found".to_string()),
+            Err(e) => return Err(format!("Failed to fetch game: {e}")),
+        };
+        if current > version {
+            return Ok(());
+        }
+
+        match tokio::time::timeout_at(deadline, changes.recv()).await {
+            Ok(Ok(())) | Ok(Err(RecvError::Lagged(_))) => {}
+            Ok(Err(RecvError::Closed)) | Err(_) => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_etags_round_trip() {
+        assert_eq!(etag(12), "\"12\"");
+        assert_eq!(parse_etag(&etag(12)), Some(12));
+        assert_eq!(parse_etag(" W/\"12\" "), Some(12));
+        assert_eq!(parse_etag("12"), None);
+        assert_eq!(parse_etag("\"twelve\""), None);
+    }
+
+    #[test]
+    fn test_headers_match_any_listed_version() {
+        assert!(header_matches("\"3\"", 3));
+        assert!(header_matches("\"1\", W/\"3\"", 3));
+        assert!(header_matches("*", 3));
+        assert!(!header_matches("\"4\"", 3));
+        assert!(!header_matches("", 3));
+    }
+
+    #[test]
+    fn test_actions_are_allowed_only_on_the_expected_version() {
+        assert!(ExpectedVersion::default().allows(3));
+
+        let header = ExpectedVersion {
+            if_match: Some("\"3\"".to_string()),
+            version: None,
+        };
+        assert!(header.allows(3));
+        assert!(!header.allows(4));
+
+        let field = ExpectedVersion {
+            if_match: None,
+            version: Some(3),
+        };
+        assert!(field.allows(3));
+        assert!(!field.allows(4));
+
+        let both = ExpectedVersion {
+            if_match: Some("\"3\"".to_string()),
+            version: Some(4),
+        };
+        assert!(!both.allows(3) && !both.allows(4));
+    }
+}
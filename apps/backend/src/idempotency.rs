@@ -0,0 +1,463 @@
+//! Idempotency keys for mutating requests
+//!
+//! A client that may retry a request sends it with an `Idempotency-Key`
+//! header. The first request with a key is handled as usual and its response
+//! is stored against the user and key; a retry is answered with that response
+//! instead of being handled again. Keys expire after `IDEMPOTENCY_KEY_TTL_MS`
+//! and are swept from the table on a schedule. A key whose request never got
+//! an answer stored, say because the server stopped mid-request, is held for
+//! `IDEMPOTENCY_LEASE_MS` only and then handed to the next retry.
+
+use std::env;
+use std::rc::Rc;
+use std::time::Duration;
+
+use actix_web::body::{to_bytes, BoxBody, MessageBody};
+use actix_web::http::{Method, StatusCode};
+use actix_web::{
+    dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform},
+    web, Error, HttpResponse,
+};
+use chrono::Utc;
+use futures_util::future::{ready, LocalBoxFuture, Ready};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::entity::idempotency_keys;
+use crate::jwt::get_user;
+
+/// Header a client names a request with, so retries of it are not repeated
+pub const IDEMPOTENCY_KEY: &str = "Idempotency-Key";
+
+/// Header marking a response as the stored answer to an earlier request
+pub const IDEMPOTENT_REPLAYED: &str = "Idempotent-Replayed";
+
+/// Longest key accepted, matching the column
+const MAX_KEY_LENGTH: usize = 255;
+
+/// Key lifetime when `IDEMPOTENCY_KEY_TTL_MS` is not set
+const DEFAULT_KEY_TTL_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Hold on an unanswered key when `IDEMPOTENCY_LEASE_MS` is not set
+const DEFAULT_LEASE_MS: u64 = 30_000;
+
+/// Time between sweeps of expired keys
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// How long a key is remembered
+///
+/// Configured with `IDEMPOTENCY_KEY_TTL_MS`.
+pub fn key_ttl() -> Duration {
+    let millis = env::var("IDEMPOTENCY_KEY_TTL_MS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_KEY_TTL_MS);
+    Duration::from_millis(millis)
+}
+
+/// How long a key is held for a request still being handled
+///
+/// Configured with `IDEMPOTENCY_LEASE_MS`. A key left unanswered for longer is
+/// taken to be abandoned.
+pub fn lease() -> Duration {
+    let millis = env::var("IDEMPOTENCY_LEASE_MS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_LEASE_MS);
+    Duration::from_millis(millis)
+}
+
+/// Whether a client's key can be stored
+///
+/// This function is PURE - keys are 1 to 255 visible ASCII characters.
+pub fn valid_key(key: &str) -> bool {
+    !key.is_empty() && key.len() <= MAX_KEY_LENGTH && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Fingerprint a request, so a key can't be reused for a different one
+///
+/// This function is PURE - the hash covers the method, path with query string
+/// and body.
+pub fn request_hash(method: &str, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+/// Oldest creation time of a key still remembered
+fn cutoff() -> chrono::DateTime<Utc> {
+    Utc::now() - chrono::Duration::from_std(key_ttl()).unwrap_or(chrono::Duration::MAX)
+}
+
+/// Oldest creation time of an unanswered key still held
+fn lease_cutoff() -> chrono::DateTime<Utc> {
+    Utc::now() - chrono::Duration::from_std(lease()).unwrap_or(chrono::Duration::MAX)
+}
+
+/// What to do with a request sent with a key
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Reservation {
+    /// The key is new and now held for this request
+    Reserved,
+    /// The request was handled before; answer with its response
+    Replay { status: i32, body: String },
+    /// The first request with the key has not been answered yet
+    InProgress,
+    /// The key was used for a different request
+    Mismatch,
+}
+
+/// Hold a user's key for a request, unless it was used before
+async fn reserve(
+    user_id: Uuid,
+    key: &str,
+    hash: &str,
+    db: &DatabaseConnection,
+) -> Result<Reservation, String> {
+    // An expired or abandoned key is free for reuse, even before the sweep
+    // removes it
+    if let Err(e) = idempotency_keys::Entity::delete_many()
+        .filter(idempotency_keys::Column::UserId.eq(user_id))
+        .filter(idempotency_keys::Column::Key.eq(key))
+        .filter(
+            Condition::any()
+                .add(idempotency_keys::Column::CreatedAt.lt(cutoff()))
+                .add(
+                    Condition::all()
+                        .add(idempotency_keys::Column::ResponseStatus.is_null())
+                        .add(idempotency_keys::Column::CreatedAt.lt(lease_cutoff())),
+                ),
+        )
+        .exec(db)
+        .await
+    {
+        return Err(format!("Failed to expire idempotency key: {e}"));
+    }
+
+    let reservation = idempotency_keys::ActiveModel {
+        user_id: Set(user_id),
+        key: Set(key.to_string()),
+        request_hash: Set(hash.to_string()),
+        response_status: Set(None),
+        response_body: Set(None),
+        created_at: Set(Utc::now().into()),
+    };
+    let inserted = match idempotency_keys::Entity::insert(reservation)
+        .on_conflict(
+            OnConflict::columns([
+                idempotency_keys::Column::UserId,
+                idempotency_keys::Column::Key,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await
+    {
+        Ok(inserted) => inserted,
+        Err(e) => return Err(format!("Failed to reserve idempotency key: {e}")),
+    };
+    if inserted > 0 {
+        return Ok(Reservation::Reserved);
+    }
+
+    let stored = match idempotency_keys::Entity::find_by_id((user_id, key.to_string()))
+        .one(db)
+        .await
+    {
+        Ok(stored) => stored,
+        Err(e) => return Err(format!("Failed to fetch idempotency key: {e}")),
+    };
+    Ok(match stored {
+        Some(stored) if stored.request_hash != hash => Reservation::Mismatch,
+        Some(idempotency_keys::Model {
+            response_status: Some(status),
+            response_body,
+            ..
+        }) => Reservation::Replay {
+            status,
+            body: response_body.unwrap_or_default(),
+        },
+        // Still being handled within its lease, or expired just now; either
+        // way the client retries
+        _ => Reservation::InProgress,
+    })
+}
+
+/// Store the response to replay for a reserved key
+async fn record(
+    user_id: Uuid,
+    key: &str,
+    status: StatusCode,
+    body: &[u8],
+    db: &DatabaseConnection,
+) -> Result<(), String> {
+    let stored = idempotency_keys::ActiveModel {
+        user_id: Set(user_id),
+        key: Set(key.to_string()),
+        response_status: Set(Some(i32::from(status.as_u16()))),
+        response_body: Set(Some(String::from_utf8_lossy(body).into_owned())),
+        ..Default::default()
+    };
+    match idempotency_keys::Entity::update(stored).exec(db).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Failed to store idempotent response: {e}")),
+    }
+}
+
+/// Give up a reserved key, so the request can be retried for real
+async fn release(user_id: Uuid, key: &str, db: &DatabaseConnection) {
+    if let Err(e) = idempotency_keys::Entity::delete_by_id((user_id, key.to_string()))
+        .exec(db)
+        .await
+    {
+        warn!("Failed to release idempotency key: {e}");
+    }
+}
+
+/// Delete every key older than the key lifetime
+///
+/// Returns the number of keys deleted.
+pub async fn purge_expired(db: &DatabaseConnection) -> Result<u64, String> {
+    match idempotency_keys::Entity::delete_many()
+        .filter(idempotency_keys::Column::CreatedAt.lt(cutoff()))
+        .exec(db)
+        .await
+    {
+        Ok(deleted) => Ok(deleted.rows_affected),
+        Err(e) => Err(format!("Failed to purge idempotency keys: {e}")),
+    }
+}
+
+/// Sweep expired keys from the table every hour, for as long as the server runs
+pub fn spawn_sweeper(db: DatabaseConnection) {
+    tokio::spawn(async move {
+        let mut sweep = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            sweep.tick().await;
+            if let Err(e) = purge_expired(&db).await {
+                warn!("{e}");
+            }
+        }
+    });
+}
+
+/// Answer a request without handing it to the route
+fn respond(req: ServiceRequest, response: HttpResponse) -> ServiceResponse<BoxBody> {
+    let (req, _pl) = req.into_parts();
+    ServiceResponse::new(req, response)
+}
+
+/// Middleware answering retried requests with their stored response
+///
+/// Must be wrapped inside `JwtAuth`, since keys are kept per user. Requests
+/// without the header, and reads, are passed straight through.
+pub struct IdempotencyKeys;
+
+impl Default for IdempotencyKeys {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdempotencyKeys {
+    pub fn new() -> Self {
+        IdempotencyKeys
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for IdempotencyKeys
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = IdempotencyKeysMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(IdempotencyKeysMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct IdempotencyKeysMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for IdempotencyKeysMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let svc = self.service.clone();
+
+        Box::pin(async move {
+            let header = req.headers().get(IDEMPOTENCY_KEY).cloned();
+            let user = get_user(req.request());
+            let db = req
+                .app_data::<web::Data<DatabaseConnection>>()
+                .map(|db| db.get_ref().clone());
+            let (Some(header), Some(user), Some(db)) = (header, user, db) else {
+                let res = svc.call(req).await?;
+                return Ok(res.map_into_boxed_body());
+            };
+            if req.method() == Method::GET || req.method() == Method::HEAD {
+                let res = svc.call(req).await?;
+                return Ok(res.map_into_boxed_body());
+            }
+
+            let key = match header.to_str() {
+                Ok(key) if valid_key(key) => key.to_string(),
+                _ => {
+                    let resp = HttpResponse::BadRequest()
+                        .content_type("application/json")
+                        .json(serde_json::json!({
+                            "error": "Idempotency-Key must be 1 to 255 visible ASCII characters"
+                        }));
+                    return Ok(respond(req, resp));
+                }
+            };
+
+            // Read the body to fingerprint the request, then hand it back for the route
+            let body = req.extract::<web::Bytes>().await?;
+            let path = req
+                .uri()
+                .path_and_query()
+                .map(|path| path.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let hash = request_hash(req.method().as_str(), &path, &body);
+            req.set_payload(Payload::from(body));
+
+            match reserve(user.id, &key, &hash, &db).await {
+                Ok(Reservation::Reserved) => {}
+                Ok(Reservation::Replay { status, body }) => {
+                    let status = u16::try_from(status)
+                        .ok()
+                        .and_then(|status| StatusCode::from_u16(status).ok())
+                        .unwrap_or(StatusCode::OK);
+                    let resp = HttpResponse::build(status)
+                        .content_type("application/json")
+                        .insert_header((IDEMPOTENT_REPLAYED, "true"))
+                        .body(body);
+                    return Ok(respond(req, resp));
+                }
+                Ok(Reservation::InProgress) => {
+                    let resp = HttpResponse::Conflict()
+                        .content_type("application/json")
+                        .json(serde_json::json!({
+                            "error": "A request with this Idempotency-Key is still being handled"
+                        }));
+                    return Ok(respond(req, resp));
+                }
+                Ok(Reservation::Mismatch) => {
+                    let resp = HttpResponse::UnprocessableEntity()
+                        .content_type("application/json")
+                        .json(serde_json::json!({
+                            "error": "Idempotency-Key was already used for a different request"
+                        }));
+                    return Ok(respond(req, resp));
+                }
+                Err(e) => {
+                    let resp = HttpResponse::InternalServerError()
+                        .content_type("application/json")
+                        .json(serde_json::json!({
+                            "error": "Failed to check idempotency key",
+                            "details": e
+                        }));
+                    return Ok(respond(req, resp));
+                }
+            }
+
+            let res = match svc.call(req).await {
+                Ok(res) => res,
+                Err(e) => {
+                    release(user.id, &key, &db).await;
+                    return Err(e);
+                }
+            };
+
+            // Keep the response to replay; server errors are worth retrying for real
+            let status = res.status();
+            let (req, res) = res.into_parts();
+            let (res, body) = res.into_parts();
+            let body = match to_bytes(body).await {
+                Ok(body) => body,
+                Err(_) => {
+                    release(user.id, &key, &db).await;
+                    let resp = HttpResponse::InternalServerError()
+                        .content_type("application/json")
+                        .json(serde_json::json!({
+                            "error": "Failed to read response"
+                        }));
+                    return Ok(ServiceResponse::new(req, resp));
+                }
+            };
+            if status.is_server_error() {
+                release(user.id, &key, &db).await;
+            } else if let Err(e) = record(user.id, &key, status, &body, &db).await {
+                warn!("{e}");
+                release(user.id, &key, &db).await;
+            }
+            Ok(ServiceResponse::new(
+                req,
+                res.set_body(body).map_into_boxed_body(),
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_are_short_visible_ascii() {
+        assert!(valid_key("3f2a9c1e-retry"));
+        assert!(!valid_key(""));
+        assert!(!valid_key("has space"));
+        assert!(!valid_key("clé"));
+        assert!(!valid_key(&"k".repeat(MAX_KEY_LENGTH + 1)));
+    }
+
+    #[test]
+    fn test_requests_hash_by_method_path_and_body() {
+        let bid = request_hash("POST", "/api/game/1/bid", br#"{"bid":2}"#);
+        assert_eq!(
+            bid,
+            request_hash("POST", "/api/game/1/bid", br#"{"bid":2}"#)
+        );
+        assert_ne!(
+            bid,
+            request_hash("POST", "/api/game/1/bid", br#"{"bid":3}"#)
+        );
+        assert_ne!(
+            bid,
+            request_hash("POST", "/api/game/2/bid", br#"{"bid":2}"#)
+        );
+        assert_ne!(
+            bid,
+            request_hash("DELETE", "/api/game/1/bid", br#"{"bid":2}"#)
+        );
+    }
+}
@@ -2,6 +2,7 @@ pub mod bootstrap;
 pub mod dto;
 pub mod entity;
 pub mod game_management;
+pub mod idempotency;
 pub mod jwt;
 pub mod routes;
 pub mod user_management;
@@ -10,16 +11,25 @@ pub use bootstrap::{connect_and_migrate_from_env, init_tracing, load_dotenv};
 
 use actix_web::web;
 
+use idempotency::IdempotencyKeys;
 use jwt::{get_claims, get_user, JwtAuth};
+use routes::admin::{get_game_replay, get_round_deal};
+use routes::bots::{connect_bot, register_bot};
 use routes::game::{
     add_ai_player, create_game, delete_game, get_game_state, get_game_summary, get_games,
-    join_game, mark_player_ready, play_card, submit_bid, submit_trump,
+    join_game, mark_player_ready, play_card, submit_bid, submit_trump, verify_round_deal,
 };
+use routes::json_body::json_config;
+use routes::live::{game_events, game_socket};
 
 /// Configure all routes for the application
 pub fn configure_routes(cfg: &mut actix_web::web::ServiceConfig) {
-    cfg.service(hello).service(
+    // Bots authenticate the WebSocket with their own token, not a user JWT.
+    // Idempotency keys are kept per user, so they are checked after the JWT.
+    cfg.service(hello).service(connect_bot).service(
         web::scope("/api")
+            .app_data(json_config())
+            .wrap(IdempotencyKeys::new())
             .wrap(JwtAuth::new())
             .service(protected_route)
             .service(create_game)
@@ -28,11 +38,17 @@ pub fn configure_routes(cfg: &mut actix_web::web::ServiceConfig) {
             .service(add_ai_player)
             .service(join_game)
             .service(get_game_state)
+            .service(game_socket)
+            .service(game_events)
             .service(get_game_summary)
             .service(submit_bid)
             .service(submit_trump)
             .service(play_card)
-            .service(delete_game),
+            .service(verify_round_deal)
+            .service(delete_game)
+            .service(get_round_deal)
+            .service(get_game_replay)
+            .service(register_bot),
     );
 }
 
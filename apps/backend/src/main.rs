@@ -5,6 +5,8 @@ use tracing::warn;
 use tracing_actix_web::TracingLogger;
 
 // Import bootstrap functions and route configurator
+use backend::game_management::ai_driver;
+use backend::idempotency;
 use backend::{configure_routes, connect_and_migrate_from_env, init_tracing, load_dotenv};
 
 #[actix_web::main]
@@ -14,6 +16,14 @@ async fn main() -> std::io::Result<()> {
     init_tracing();
     let db = connect_and_migrate_from_env().await;
 
+    // Take any AI turns left pending when the server last stopped
+    if let Err(e) = ai_driver::resume_pending(&db).await {
+        warn!("Failed to resume AI turns: {e}");
+    }
+
+    // Forget idempotency keys once they expire
+    idempotency::spawn_sweeper(db.clone());
+
     // Start the HTTP server
     HttpServer::new(move || {
         // Configure CORS
@@ -27,6 +37,10 @@ async fn main() -> std::io::Result<()> {
             .allowed_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"])
             .allowed_headers(vec![http::header::AUTHORIZATION, http::header::ACCEPT])
             .allowed_header(http::header::CONTENT_TYPE)
+            .allowed_headers(vec![http::header::IF_MATCH, http::header::IF_NONE_MATCH])
+            .allowed_header(idempotency::IDEMPOTENCY_KEY)
+            .expose_headers(vec![http::header::ETAG])
+            .expose_headers(vec![idempotency::IDEMPOTENT_REPLAYED])
             .supports_credentials()
             .max_age(3600);
 
@@ -0,0 +1,256 @@
+use actix_web::{get, web, HttpRequest, HttpResponse, Result as ActixResult};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, Order, QueryFilter, QueryOrder};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::entity::{game_players, game_rounds, games, users};
+use crate::game_management::bidding::{deal_hands, deck_for_seed};
+use crate::game_management::event_log::{load_events, replay_snapshot};
+use crate::jwt::get_user;
+use crate::user_management::is_admin;
+
+/// Regenerate the exact deal of a past round from its recorded seed
+#[get("/admin/game/{game_id}/round/{round_number}/deal")]
+pub async fn get_round_deal(
+    req: HttpRequest,
+    path: web::Path<(String, i32)>,
+    db: web::Data<DatabaseConnection>,
+) -> ActixResult<HttpResponse> {
+    // Extract user from JWT authentication
+    let user = match get_user(&req) {
+        Some(user) => user,
+        None => {
+            return Ok(HttpResponse::Unauthorized()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "User not authenticated"
+                })));
+        }
+    };
+
+    if !is_admin(&user) {
+        return Ok(HttpResponse::Forbidden()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Admin access required"
+            })));
+    }
+
+    // Parse game ID from path
+    let (game_id, round_number) = path.into_inner();
+    let game_id = match Uuid::parse_str(&game_id) {
+        Ok(id) => id,
+        Err(_) => {
+            return Ok(HttpResponse::BadRequest()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Invalid game ID format"
+                })));
+        }
+    };
+
+    // Fetch the requested round
+    let round = match game_rounds::Entity::find()
+        .filter(game_rounds::Column::GameId.eq(game_id))
+        .filter(game_rounds::Column::RoundNumber.eq(round_number))
+        .one(&**db)
+        .await
+    {
+        Ok(Some(round)) => round,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Round not found"
+                })));
+        }
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to fetch round",
+                    "details": e.to_string()
+                })));
+        }
+    };
+
+    // Rounds dealt before seeds were recorded cannot be regenerated
+    let Some(deal_seed) = round.deal_seed else {
+        return Ok(HttpResponse::NotFound()
+            .content_type("application/json")
+            .json(json!({
+                "error": "No deal seed recorded for this round"
+            })));
+    };
+
+    // Seats are dealt in turn order
+    let players = match game_players::Entity::find()
+        .filter(game_players::Column::GameId.eq(game_id))
+        .order_by(game_players::Column::TurnOrder, Order::Asc)
+        .all(&**db)
+        .await
+    {
+        Ok(players) => players,
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to fetch game players",
+                    "details": e.to_string()
+                })));
+        }
+    };
+
+    let deck = deck_for_seed(deal_seed);
+    let hands = match deal_hands(&deck, players.len(), round.cards_dealt) {
+        Ok(hands) => hands,
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to regenerate deal",
+                    "details": e
+                })));
+        }
+    };
+
+    let seats: Vec<_> = players
+        .iter()
+        .zip(hands)
+        .map(|(player, hand)| {
+            json!({
+                "player_id": player.id,
+                "user_id": player.user_id,
+                "turn_order": player.turn_order,
+                "hand": hand
+            })
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(json!({
+            "game_id": game_id,
+            "round_number": round.round_number,
+            "cards_dealt": round.cards_dealt,
+            "deal_seed": deal_seed,
+            "hands": seats
+        })))
+}
+
+/// Rebuild a game's snapshot from its event log alone
+#[get("/admin/game/{game_id}/replay")]
+pub async fn get_game_replay(
+    req: HttpRequest,
+    path: web::Path<String>,
+    db: web::Data<DatabaseConnection>,
+) -> ActixResult<HttpResponse> {
+    // Extract user from JWT authentication
+    let user = match get_user(&req) {
+        Some(user) => user,
+        None => {
+            return Ok(HttpResponse::Unauthorized()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "User not authenticated"
+                })));
+        }
+    };
+
+    if !is_admin(&user) {
+        return Ok(HttpResponse::Forbidden()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Admin access required"
+            })));
+    }
+
+    // Parse game ID from path
+    let game_id = match Uuid::parse_str(&path.into_inner()) {
+        Ok(id) => id,
+        Err(_) => {
+            return Ok(HttpResponse::BadRequest()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Invalid game ID format"
+                })));
+        }
+    };
+
+    // The game row is only read for its rule set
+    let game = match games::Entity::find_by_id(game_id).one(&**db).await {
+        Ok(Some(game)) => game,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Game not found"
+                })));
+        }
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to fetch game",
+                    "details": e.to_string()
+                })));
+        }
+    };
+    let rules = match game.rules() {
+        Ok(rules) => rules,
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to load game rules",
+                    "details": e
+                })));
+        }
+    };
+
+    let log = match load_events(game_id, &**db).await {
+        Ok(log) => log,
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to fetch game events",
+                    "details": e
+                })));
+        }
+    };
+
+    // Display details for everyone who joined
+    let user_ids: Vec<Uuid> = log
+        .iter()
+        .filter(|entry| entry.event_type == "player_joined")
+        .filter_map(|entry| entry.payload["user_id"].as_str()?.parse().ok())
+        .collect();
+    let joined_users = match users::Entity::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(&**db)
+        .await
+    {
+        Ok(users) => users,
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to fetch users",
+                    "details": e.to_string()
+                })));
+        }
+    };
+
+    match replay_snapshot(game_id, &rules, &log, &joined_users) {
+        Ok(snapshot) => Ok(HttpResponse::Ok()
+            .content_type("application/json")
+            .json(snapshot)),
+        Err(e) => Ok(HttpResponse::InternalServerError()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Failed to replay game events",
+                "details": e
+            }))),
+    }
+}
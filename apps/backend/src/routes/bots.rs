@@ -0,0 +1,200 @@
+use actix_web::{get, post, web, HttpRequest, HttpResponse, Result as ActixResult};
+use actix_ws::Message;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
+    TransactionError, TransactionTrait,
+};
+use serde_json::json;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::dto::register_bot_request::RegisterBotRequest;
+use crate::entity::{bots, users};
+use crate::game_management::bot_protocol;
+use crate::jwt::get_user;
+
+/// Register a bot that can be seated in games
+///
+/// The bot gets its own AI user to take seats with. Its token is returned
+/// once and only its hash is kept.
+#[post("/bots")]
+pub async fn register_bot(
+    req: HttpRequest,
+    bot_data: web::Json<RegisterBotRequest>,
+    db: web::Data<DatabaseConnection>,
+) -> ActixResult<HttpResponse> {
+    // Extract user from JWT authentication
+    let user = match get_user(&req) {
+        Some(user) => user,
+        None => {
+            return Ok(HttpResponse::Unauthorized()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "User not authenticated"
+                })));
+        }
+    };
+
+    let RegisterBotRequest { name, callback_url } = bot_data.into_inner();
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Ok(HttpResponse::BadRequest()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Bot name is required"
+            })));
+    }
+    if let Some(url) = &callback_url {
+        if let Err(e) = bot_protocol::check_callback_url(url).await {
+            return Ok(HttpResponse::BadRequest()
+                .content_type("application/json")
+                .json(json!({
+                    "error": e
+                })));
+        }
+    }
+
+    let bot_id = Uuid::new_v4();
+    let token = bot_protocol::generate_token();
+    let token_hash = bot_protocol::hash_token(&token);
+    let owner_id = user.id;
+    let result = db
+        .transaction::<_, bots::Model, sea_orm::DbErr>(|txn| {
+            Box::pin(async move {
+                let now = Utc::now();
+                let bot_user = users::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    external_id: Set(format!("bot:{bot_id}")),
+                    email: Set(format!("bot-{bot_id}@bots.nommie.dev")),
+                    name: Set(Some(name.clone())),
+                    is_ai: Set(true),
+                    created_at: Set(now.into()),
+                    updated_at: Set(now.into()),
+                }
+                .insert(txn)
+                .await?;
+
+                bots::ActiveModel {
+                    id: Set(bot_id),
+                    user_id: Set(bot_user.id),
+                    owner_id: Set(owner_id),
+                    name: Set(name),
+                    callback_url: Set(callback_url),
+                    token_hash: Set(token_hash),
+                    created_at: Set(now.into()),
+                }
+                .insert(txn)
+                .await
+            })
+        })
+        .await;
+
+    match result {
+        Ok(bot) => Ok(HttpResponse::Ok()
+            .content_type("application/json")
+            .json(json!({
+                "success": true,
+                "bot": bot,
+                "token": token
+            }))),
+        Err(TransactionError::Connection(e)) | Err(TransactionError::Transaction(e)) => {
+            Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to register bot",
+                    "details": e.to_string()
+                })))
+        }
+    }
+}
+
+/// Open the WebSocket a bot without a callback URL receives decisions on
+///
+/// Authenticated with the bot's token as `Authorization: Bot <token>`.
+/// Requests are sent as text messages and replies are read back the same way.
+#[get("/bots/connect")]
+pub async fn connect_bot(
+    req: HttpRequest,
+    body: web::Payload,
+    db: web::Data<DatabaseConnection>,
+) -> ActixResult<HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bot "));
+    let Some(token) = token else {
+        return Ok(HttpResponse::Unauthorized()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Bot token required"
+            })));
+    };
+
+    let bot = match bots::Entity::find()
+        .filter(bots::Column::TokenHash.eq(bot_protocol::hash_token(token)))
+        .one(&**db)
+        .await
+    {
+        Ok(Some(bot)) => bot,
+        Ok(None) => {
+            return Ok(HttpResponse::Unauthorized()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Invalid bot token"
+                })));
+        }
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to fetch bot",
+                    "details": e.to_string()
+                })));
+        }
+    };
+    if bot.callback_url.is_some() {
+        return Ok(HttpResponse::BadRequest()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Bot receives decisions at its callback URL"
+            })));
+    }
+
+    let (response, mut session, mut messages) = actix_ws::handle(&req, body)?;
+    let (connection_id, mut requests) = bot_protocol::connect(bot.id);
+    actix_web::rt::spawn(async move {
+        loop {
+            tokio::select! {
+                message = messages.recv() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        if let Err(e) = bot_protocol::receive_reply(&text) {
+                            warn!("Ignoring message from bot {}: {e}", bot.id);
+                        }
+                    }
+                    Some(Ok(Message::Ping(bytes))) => {
+                        if session.pong(&bytes).await.is_err() {
+                            break;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                },
+                request = requests.recv() => match request {
+                    Some(request) => {
+                        if session.text(request).await.is_err() {
+                            break;
+                        }
+                    }
+                    // A newer connection took over
+                    None => break,
+                },
+            }
+        }
+        bot_protocol::disconnect(bot.id, connection_id);
+        let _ = session.close(None).await;
+    });
+
+    Ok(response)
+}
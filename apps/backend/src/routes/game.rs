@@ -1,30 +1,49 @@
+use actix_web::http::header;
 use actix_web::{delete, get, post, web, HttpRequest, HttpResponse, Result as ActixResult};
 use chrono::{DateTime, FixedOffset, Utc};
 use sea_orm::sea_query::Query;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, Order, QueryFilter, QueryOrder,
-    Set, TransactionTrait,
+    AccessMode, ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction,
+    EntityTrait, IsolationLevel, Order, QueryFilter, QueryOrder, Set, TransactionError,
+    TransactionTrait,
 };
 use serde_json::json;
 use uuid::Uuid;
 
+use crate::dto::add_ai_request::AddAiRequest;
 use crate::dto::bid_request::BidRequest;
+use crate::dto::create_game_request::CreateGameRequest;
 use crate::dto::game_summary::{
     FinalRoundSummary, GameSummary, GameSummaryInfo, PlayerRoundResult, PlayerSummary,
     RoundBidSummary, RoundScoreSummary, RoundSummary, UserSummary,
 };
 use crate::dto::play_request::PlayRequest;
+use crate::dto::state_query::StateQuery;
 use crate::dto::trump_request::TrumpRequest;
-use crate::entity::{game_players, game_rounds, games, round_bids, round_scores, users};
+use crate::dto::version_request::VersionRequest;
+use crate::entity::{bots, game_players, game_rounds, games, round_bids, round_scores, users};
 use crate::game_management::{
-    bidding, play_card_transaction, scoring::calculate_round_points, scoring::has_exact_bid_bonus,
-    state::build_game_snapshot, state::calculate_player_total_score, state::check_and_start_game,
+    ai_driver, bidding,
+    cards::Trump,
+    engine::{Action, Event},
+    event_log::{append_events, LoggedEvent},
+    fairness::verify_deal,
+    live, orchestration, play_card_transaction,
+    rules::{RuleSet, STANDARD_PLAYER_COUNT},
+    state::build_game_snapshot,
+    state::calculate_player_total_score,
+    state::check_and_start_game,
+    state::{deal_reveal, load_dealt_hands},
+    version::{self, ExpectedVersion, VersionError},
 };
 use crate::jwt::get_user;
+use crate::routes::json_body;
+use crate::user_management::is_admin;
 
 #[post("/create_game")]
 pub async fn create_game(
     req: HttpRequest,
+    body: web::Bytes,
     db: web::Data<DatabaseConnection>,
 ) -> ActixResult<HttpResponse> {
     // Extract user from JWT authentication
@@ -39,6 +58,36 @@ pub async fn create_game(
         }
     };
 
+    // Build the rule set for the requested table size and scoring rule
+    let create_data: CreateGameRequest = match json_body::optional(&body) {
+        Ok(create_data) => create_data.unwrap_or_default(),
+        Err(e) => return Ok(json_body::invalid_body(e)),
+    };
+
+    // A pinned seed reveals every hand, so only admins may set one
+    if create_data.deal_seed.is_some() && !is_admin(&user) {
+        return Ok(HttpResponse::Forbidden()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Only admins can pin the deal seed"
+            })));
+    }
+    let player_count = create_data.player_count.unwrap_or(STANDARD_PLAYER_COUNT);
+    let rules = match RuleSet::for_players(player_count) {
+        Ok(rules) => RuleSet {
+            scoring: create_data.scoring.unwrap_or_default(),
+            dealer_hook: create_data.dealer_hook.unwrap_or_default(),
+            ..rules
+        },
+        Err(e) => {
+            return Ok(HttpResponse::BadRequest()
+                .content_type("application/json")
+                .json(json!({
+                    "error": e
+                })));
+        }
+    };
+
     // Create a new game
     let game_id = Uuid::new_v4();
     let now: DateTime<FixedOffset> = Utc::now().into();
@@ -52,10 +101,26 @@ pub async fn create_game(
         updated_at: Set(now),
         started_at: Set(None),
         completed_at: Set(None),
+        rule_set: Set(json!(rules)),
+        deal_seed: Set(create_data.deal_seed),
+        version: Set(0),
+    };
+
+    // The game, its creator's seat and the join event are stored together
+    let txn = match db.begin().await {
+        Ok(txn) => txn,
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to begin transaction",
+                    "details": e.to_string()
+                })));
+        }
     };
 
     // Insert the game into the database
-    let game_result = match game.insert(&**db).await {
+    let game_result = match game.insert(&txn).await {
         Ok(game) => game,
         Err(e) => {
             return Ok(HttpResponse::InternalServerError()
@@ -75,10 +140,11 @@ pub async fn create_game(
         user_id: Set(user.id),
         turn_order: Set(Some(0)), // First player gets turn order 0
         is_ready: Set(false),
+        ai_difficulty: Set(None),
     };
 
     // Insert the game player into the database
-    let game_player_result = match game_player.insert(&**db).await {
+    let game_player_result = match game_player.insert(&txn).await {
         Ok(game_player) => game_player,
         Err(e) => {
             return Ok(HttpResponse::InternalServerError()
@@ -90,6 +156,33 @@ pub async fn create_game(
         }
     };
 
+    if let Err(e) = append_events(
+        game_id,
+        &[LoggedEvent::from(Event::PlayerJoined {
+            player_id: game_player_id,
+            user_id: user.id,
+        })],
+        &txn,
+    )
+    .await
+    {
+        return Ok(HttpResponse::InternalServerError()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Failed to record game event",
+                "details": e
+            })));
+    }
+
+    if let Err(e) = txn.commit().await {
+        return Ok(HttpResponse::InternalServerError()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Failed to create game",
+                "details": e.to_string()
+            })));
+    }
+
     // Return the created game and its player
     Ok(HttpResponse::Ok()
         .content_type("application/json")
@@ -151,6 +244,18 @@ pub async fn get_games(
             .filter(|gp| gp.game_id == game.id)
             .collect();
 
+        let rules = match game.rules() {
+            Ok(rules) => rules,
+            Err(e) => {
+                return Ok(HttpResponse::InternalServerError()
+                    .content_type("application/json")
+                    .json(json!({
+                        "error": "Failed to load game rules",
+                        "details": e
+                    })));
+            }
+        };
+
         let player_count = game_players.len();
         let is_player_in_game = game_players.iter().any(|gp| gp.user_id == user.id);
 
@@ -163,7 +268,7 @@ pub async fn get_games(
             "id": game.id,
             "state": game.state,
             "player_count": player_count,
-            "max_players": 4, // Assuming 4 players max for now
+            "max_players": rules.player_count,
             "is_player_in_game": is_player_in_game,
             "is_creator": is_creator
         }));
@@ -180,6 +285,7 @@ pub async fn get_games(
 pub async fn mark_player_ready(
     req: HttpRequest,
     path: web::Path<String>,
+    body: web::Bytes,
     db: web::Data<DatabaseConnection>,
 ) -> ActixResult<HttpResponse> {
     // Extract user from JWT authentication
@@ -206,24 +312,15 @@ pub async fn mark_player_ready(
         }
     };
 
-    // Fetch the game to check its state
-    let game = match games::Entity::find_by_id(game_id).one(&**db).await {
-        Ok(Some(game)) => game,
-        Ok(None) => {
-            return Ok(HttpResponse::NotFound()
-                .content_type("application/json")
-                .json(json!({
-                    "error": "Game not found"
-                })));
-        }
-        Err(e) => {
-            return Ok(HttpResponse::InternalServerError()
-                .content_type("application/json")
-                .json(json!({
-                    "error": "Failed to fetch game",
-                    "details": e.to_string()
-                })));
-        }
+    // Lock the game, so the player readies up at the version they saw
+    let version_data: VersionRequest = match json_body::optional(&body) {
+        Ok(version_data) => version_data.unwrap_or_default(),
+        Err(e) => return Ok(json_body::invalid_body(e)),
+    };
+    let expected = expected_version(&req, version_data.expected_version);
+    let (txn, game) = match begin_at_version(&db, game_id, &expected).await {
+        Ok(locked) => locked,
+        Err(response) => return Ok(response),
     };
 
     // Check if game is in waiting state
@@ -239,7 +336,7 @@ pub async fn mark_player_ready(
     let game_player = match game_players::Entity::find()
         .filter(game_players::Column::GameId.eq(game_id))
         .filter(game_players::Column::UserId.eq(user.id))
-        .one(&**db)
+        .one(&txn)
         .await
     {
         Ok(Some(game_player)) => game_player,
@@ -269,54 +366,66 @@ pub async fn mark_player_ready(
             })));
     }
 
-    // Update the game player to mark as ready
-    let mut game_player_model: game_players::ActiveModel = game_player.into();
-    game_player_model.is_ready = Set(true);
-
-    let updated_game_player = match game_player_model.update(&**db).await {
-        Ok(game_player) => game_player,
+    // Mark the player as ready through the engine, starting the game if the
+    // table is now full and ready
+    let player_id = game_player.id;
+    let events = match orchestration::apply_action(game_id, Action::Ready { player_id }, &txn).await
+    {
+        Ok(events) => events,
         Err(e) => {
-            return Ok(HttpResponse::InternalServerError()
+            return Ok(HttpResponse::BadRequest()
                 .content_type("application/json")
                 .json(json!({
-                    "error": "Failed to update player readiness",
-                    "details": e.to_string()
+                    "error": e.to_string()
                 })));
         }
     };
+    if let Err(e) = txn.commit().await {
+        return Ok(HttpResponse::InternalServerError()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Failed to update player readiness",
+                "details": e.to_string()
+            })));
+    }
+    let game_started = events.contains(&Event::GameStarted);
+    if game_started {
+        ai_driver::schedule(game_id, db.get_ref().clone());
+    }
+    live::notify(game_id);
 
-    // Check if all players are ready and start the game if so
-    match check_and_start_game(game, &db).await {
-        Ok(true) => {
-            return Ok(HttpResponse::Ok()
+    let updated_game_player = match game_players::Entity::find_by_id(player_id).one(&**db).await {
+        Ok(Some(game_player)) => game_player,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound()
                 .content_type("application/json")
                 .json(json!({
-                    "success": true,
-                    "message": "Player marked as ready and game started",
-                    "game_player": updated_game_player,
-                    "game_started": true
+                    "error": "Player not found in game"
                 })));
         }
-        Ok(false) => {
-            // Game not started, continue to normal response
-        }
         Err(e) => {
             return Ok(HttpResponse::InternalServerError()
                 .content_type("application/json")
                 .json(json!({
-                    "error": "Failed to start game",
-                    "details": e
+                    "error": "Failed to fetch game player",
+                    "details": e.to_string()
                 })));
         }
-    }
+    };
+
+    let message = if game_started {
+        "Player marked as ready and game started"
+    } else {
+        "Player marked as ready"
+    };
 
     Ok(HttpResponse::Ok()
         .content_type("application/json")
         .json(json!({
             "success": true,
-            "message": "Player marked as ready",
+            "message": message,
             "game_player": updated_game_player,
-            "game_started": false
+            "game_started": game_started
         })))
 }
 
@@ -324,6 +433,7 @@ pub async fn mark_player_ready(
 pub async fn add_ai_player(
     req: HttpRequest,
     path: web::Path<String>,
+    body: web::Bytes,
     db: web::Data<DatabaseConnection>,
 ) -> ActixResult<HttpResponse> {
     // Extract user from JWT authentication
@@ -350,24 +460,17 @@ pub async fn add_ai_player(
         }
     };
 
-    // Fetch the game to check its state
-    let game = match games::Entity::find_by_id(game_id).one(&**db).await {
-        Ok(Some(game)) => game,
-        Ok(None) => {
-            return Ok(HttpResponse::NotFound()
-                .content_type("application/json")
-                .json(json!({
-                    "error": "Game not found"
-                })));
-        }
-        Err(e) => {
-            return Ok(HttpResponse::InternalServerError()
-                .content_type("application/json")
-                .json(json!({
-                    "error": "Failed to fetch game",
-                    "details": e.to_string()
-                })));
-        }
+    let add_data: AddAiRequest = match json_body::optional(&body) {
+        Ok(add_data) => add_data.unwrap_or_default(),
+        Err(e) => return Ok(json_body::invalid_body(e)),
+    };
+    let difficulty = add_data.difficulty.unwrap_or_default();
+
+    // Lock the game, so seats are handed out one at a time
+    let expected = expected_version(&req, add_data.version.expected_version);
+    let (txn, game) = match begin_at_version(&db, game_id, &expected).await {
+        Ok(locked) => locked,
+        Err(response) => return Ok(response),
     };
 
     // Check if game is in waiting state
@@ -383,7 +486,7 @@ pub async fn add_ai_player(
     let user_in_game = match game_players::Entity::find()
         .filter(game_players::Column::GameId.eq(game_id))
         .filter(game_players::Column::UserId.eq(user.id))
-        .one(&**db)
+        .one(&txn)
         .await
     {
         Ok(Some(_)) => true,
@@ -409,7 +512,7 @@ pub async fn add_ai_player(
     // Get current player count
     let current_players = match game_players::Entity::find()
         .filter(game_players::Column::GameId.eq(game_id))
-        .all(&**db)
+        .all(&txn)
         .await
     {
         Ok(players) => players,
@@ -423,8 +526,20 @@ pub async fn add_ai_player(
         }
     };
 
-    // Check if game is full (max 4 players)
-    if current_players.len() >= 4 {
+    let rules = match game.rules() {
+        Ok(rules) => rules,
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to load game rules",
+                    "details": e
+                })));
+        }
+    };
+
+    // Check if game is full
+    if current_players.len() >= rules.player_count {
         return Ok(HttpResponse::BadRequest()
             .content_type("application/json")
             .json(json!({
@@ -432,52 +547,87 @@ pub async fn add_ai_player(
             })));
     }
 
-    // Find an available AI user that's not already in this game
-    let ai_user = match users::Entity::find()
-        .filter(users::Column::IsAi.eq(true))
-        .filter(users::Column::Email.like("__ai+%@nommie.dev"))
-        .filter(
-            users::Column::Id.not_in_subquery(
-                Query::select()
-                    .column(game_players::Column::UserId)
-                    .from(game_players::Entity)
-                    .and_where(game_players::Column::GameId.eq(game_id))
-                    .to_owned(),
-            ),
-        )
-        .one(&**db)
-        .await
-    {
-        Ok(Some(user)) => user,
-        Ok(None) => {
-            return Ok(HttpResponse::InternalServerError()
-                .content_type("application/json")
-                .json(json!({
-                    "error": "No AI users available"
-                })));
-        }
-        Err(e) => {
-            return Ok(HttpResponse::InternalServerError()
-                .content_type("application/json")
-                .json(json!({
-                    "error": "Failed to fetch AI user",
-                    "details": e.to_string()
-                })));
+    let ai_user_id = match add_data.bot_id {
+        // Seat a registered bot through its own AI user
+        Some(bot_id) => {
+            let bot = match bots::Entity::find_by_id(bot_id).one(&txn).await {
+                Ok(Some(bot)) => bot,
+                Ok(None) => {
+                    return Ok(HttpResponse::NotFound()
+                        .content_type("application/json")
+                        .json(json!({
+                            "error": "Bot not found"
+                        })));
+                }
+                Err(e) => {
+                    return Ok(HttpResponse::InternalServerError()
+                        .content_type("application/json")
+                        .json(json!({
+                            "error": "Failed to fetch bot",
+                            "details": e.to_string()
+                        })));
+                }
+            };
+            if current_players
+                .iter()
+                .any(|player| player.user_id == bot.user_id)
+            {
+                return Ok(HttpResponse::BadRequest()
+                    .content_type("application/json")
+                    .json(json!({
+                        "error": "Bot is already in this game"
+                    })));
+            }
+            bot.user_id
         }
+        // Find an available AI user that's not already in this game
+        None => match users::Entity::find()
+            .filter(users::Column::IsAi.eq(true))
+            .filter(users::Column::Email.like("__ai+%@nommie.dev"))
+            .filter(
+                users::Column::Id.not_in_subquery(
+                    Query::select()
+                        .column(game_players::Column::UserId)
+                        .from(game_players::Entity)
+                        .and_where(game_players::Column::GameId.eq(game_id))
+                        .to_owned(),
+                ),
+            )
+            .one(&txn)
+            .await
+        {
+            Ok(Some(user)) => user.id,
+            Ok(None) => {
+                return Ok(HttpResponse::InternalServerError()
+                    .content_type("application/json")
+                    .json(json!({
+                        "error": "No AI users available"
+                    })));
+            }
+            Err(e) => {
+                return Ok(HttpResponse::InternalServerError()
+                    .content_type("application/json")
+                    .json(json!({
+                        "error": "Failed to fetch AI user",
+                        "details": e.to_string()
+                    })));
+            }
+        },
     };
 
-    // Create AI game player
+    // Create AI game player at the requested level
     let ai_game_player_id = Uuid::new_v4();
     let ai_game_player = game_players::ActiveModel {
         id: Set(ai_game_player_id),
         game_id: Set(game_id),
-        user_id: Set(ai_user.id),
+        user_id: Set(ai_user_id),
         turn_order: Set(Some(current_players.len() as i32)), // Assign next available turn order
         is_ready: Set(true),                                 // AI players are automatically ready
+        ai_difficulty: Set(Some(difficulty.into())),
     };
 
     // Insert the AI game player into the database
-    let ai_game_player_result = match ai_game_player.insert(&**db).await {
+    let ai_game_player_result = match ai_game_player.insert(&txn).await {
         Ok(game_player) => game_player,
         Err(e) => {
             return Ok(HttpResponse::InternalServerError()
@@ -489,8 +639,53 @@ pub async fn add_ai_player(
         }
     };
 
+    if let Err(e) = append_events(
+        game_id,
+        &[
+            LoggedEvent::from(Event::PlayerJoined {
+                player_id: ai_game_player_id,
+                user_id: ai_user_id,
+            }),
+            LoggedEvent::from(Event::PlayerReady {
+                player_id: ai_game_player_id,
+            }),
+        ],
+        &txn,
+    )
+    .await
+    {
+        return Ok(HttpResponse::InternalServerError()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Failed to record game event",
+                "details": e
+            })));
+    }
+
     // Check if game should start (all players ready)
-    let game_started = (check_and_start_game(game, &db).await).unwrap_or_default();
+    let game_started = match check_and_start_game(game_id, &txn).await {
+        Ok(started) => started,
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to start game",
+                    "details": e
+                })));
+        }
+    };
+    if let Err(e) = txn.commit().await {
+        return Ok(HttpResponse::InternalServerError()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Failed to add AI player",
+                "details": e.to_string()
+            })));
+    }
+    if game_started {
+        ai_driver::schedule(game_id, db.get_ref().clone());
+    }
+    live::notify(game_id);
 
     Ok(HttpResponse::Ok()
         .content_type("application/json")
@@ -506,6 +701,7 @@ pub async fn add_ai_player(
 pub async fn join_game(
     req: HttpRequest,
     query: web::Query<std::collections::HashMap<String, String>>,
+    body: web::Bytes,
     db: web::Data<DatabaseConnection>,
 ) -> ActixResult<HttpResponse> {
     // Extract user from JWT authentication
@@ -541,24 +737,15 @@ pub async fn join_game(
         }
     };
 
-    // Fetch the game to check its state
-    let game = match games::Entity::find_by_id(game_id).one(&**db).await {
-        Ok(Some(game)) => game,
-        Ok(None) => {
-            return Ok(HttpResponse::NotFound()
-                .content_type("application/json")
-                .json(json!({
-                    "error": "Game not found"
-                })));
-        }
-        Err(e) => {
-            return Ok(HttpResponse::InternalServerError()
-                .content_type("application/json")
-                .json(json!({
-                    "error": "Failed to fetch game",
-                    "details": e.to_string()
-                })));
-        }
+    // Lock the game, so two users can never take the same seat
+    let version_data: VersionRequest = match json_body::optional(&body) {
+        Ok(version_data) => version_data.unwrap_or_default(),
+        Err(e) => return Ok(json_body::invalid_body(e)),
+    };
+    let expected = expected_version(&req, version_data.expected_version);
+    let (txn, game) = match begin_at_version(&db, game_id, &expected).await {
+        Ok(locked) => locked,
+        Err(response) => return Ok(response),
     };
 
     // Check if game is in waiting state
@@ -574,7 +761,7 @@ pub async fn join_game(
     let user_already_in_game = match game_players::Entity::find()
         .filter(game_players::Column::GameId.eq(game_id))
         .filter(game_players::Column::UserId.eq(user.id))
-        .one(&**db)
+        .one(&txn)
         .await
     {
         Ok(Some(_)) => true,
@@ -600,7 +787,7 @@ pub async fn join_game(
     // Get current player count
     let current_players = match game_players::Entity::find()
         .filter(game_players::Column::GameId.eq(game_id))
-        .all(&**db)
+        .all(&txn)
         .await
     {
         Ok(players) => players,
@@ -614,8 +801,20 @@ pub async fn join_game(
         }
     };
 
-    // Check if game is full (max 4 players)
-    if current_players.len() >= 4 {
+    let rules = match game.rules() {
+        Ok(rules) => rules,
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to load game rules",
+                    "details": e
+                })));
+        }
+    };
+
+    // Check if game is full
+    if current_players.len() >= rules.player_count {
         return Ok(HttpResponse::BadRequest()
             .content_type("application/json")
             .json(json!({
@@ -634,10 +833,11 @@ pub async fn join_game(
         user_id: Set(user.id),
         turn_order: Set(Some(turn_order)),
         is_ready: Set(false),
+        ai_difficulty: Set(None),
     };
 
     // Insert the game player into the database
-    let game_player_result = match game_player.insert(&**db).await {
+    let game_player_result = match game_player.insert(&txn).await {
         Ok(game_player) => game_player,
         Err(e) => {
             return Ok(HttpResponse::InternalServerError()
@@ -649,6 +849,33 @@ pub async fn join_game(
         }
     };
 
+    if let Err(e) = append_events(
+        game_id,
+        &[LoggedEvent::from(Event::PlayerJoined {
+            player_id: game_player_id,
+            user_id: user.id,
+        })],
+        &txn,
+    )
+    .await
+    {
+        return Ok(HttpResponse::InternalServerError()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Failed to record game event",
+                "details": e
+            })));
+    }
+    if let Err(e) = txn.commit().await {
+        return Ok(HttpResponse::InternalServerError()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Failed to join game",
+                "details": e.to_string()
+            })));
+    }
+    live::notify(game_id);
+
     Ok(HttpResponse::Ok()
         .content_type("application/json")
         .json(json!({
@@ -663,6 +890,7 @@ pub async fn join_game(
 pub async fn get_game_state(
     req: HttpRequest,
     path: web::Path<String>,
+    query: web::Query<StateQuery>,
     db: web::Data<DatabaseConnection>,
 ) -> ActixResult<HttpResponse> {
     // Extract user from JWT authentication
@@ -689,8 +917,51 @@ pub async fn get_game_state(
         }
     };
 
+    // Long-polling players wait for the game to move past the version they
+    // hold; anyone else falls through to the checks below
+    if let Some(version) = query.wait_for_version {
+        let waiting = game_players::Entity::find()
+            .filter(game_players::Column::GameId.eq(game_id))
+            .filter(game_players::Column::UserId.eq(user.id))
+            .one(&**db)
+            .await;
+        let waited = match waiting {
+            Ok(Some(_)) => version::wait_past(game_id, version, &db).await,
+            Ok(None) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = waited {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to wait for game changes",
+                    "details": e
+                })));
+        }
+    }
+
+    // Read the whole snapshot at one point in time, so AI seats acting
+    // meanwhile can't leave it half updated
+    let txn = match db
+        .begin_with_config(
+            Some(IsolationLevel::RepeatableRead),
+            Some(AccessMode::ReadOnly),
+        )
+        .await
+    {
+        Ok(txn) => txn,
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to begin transaction",
+                    "details": e.to_string()
+                })));
+        }
+    };
+
     // Fetch the game
-    let game = match games::Entity::find_by_id(game_id).one(&**db).await {
+    let game = match games::Entity::find_by_id(game_id).one(&txn).await {
         Ok(Some(game)) => game,
         Ok(None) => {
             return Ok(HttpResponse::NotFound()
@@ -722,7 +993,7 @@ pub async fn get_game_state(
     let user_in_game = match game_players::Entity::find()
         .filter(game_players::Column::GameId.eq(game_id))
         .filter(game_players::Column::UserId.eq(user.id))
-        .one(&**db)
+        .one(&txn)
         .await
     {
         Ok(Some(_)) => true,
@@ -748,7 +1019,7 @@ pub async fn get_game_state(
     // Fetch all game players for this game
     let game_players = match game_players::Entity::find()
         .filter(game_players::Column::GameId.eq(game_id))
-        .all(&**db)
+        .all(&txn)
         .await
     {
         Ok(players) => players,
@@ -762,8 +1033,25 @@ pub async fn get_game_state(
         }
     };
 
+    // Nothing new for a client already holding this version, or whose
+    // long-poll timed out
+    let etag = version::etag(game.version);
+    let unchanged = query
+        .wait_for_version
+        .is_some_and(|version| game.version <= version)
+        || req
+            .headers()
+            .get(header::IF_NONE_MATCH)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|tags| version::header_matches(tags, game.version));
+    if unchanged {
+        return Ok(HttpResponse::NotModified()
+            .insert_header((header::ETAG, etag))
+            .finish());
+    }
+
     // Build game snapshot using the state module
-    let game_snapshot = match build_game_snapshot(game, game_players, &db).await {
+    let game_snapshot = match build_game_snapshot(game, game_players, &txn).await {
         Ok(snapshot) => snapshot,
         Err(e) => {
             return Ok(HttpResponse::InternalServerError()
@@ -777,6 +1065,7 @@ pub async fn get_game_state(
 
     Ok(HttpResponse::Ok()
         .content_type("application/json")
+        .insert_header((header::ETAG, etag))
         .json(game_snapshot))
 }
 
@@ -811,32 +1100,57 @@ pub async fn submit_bid(
         }
     };
 
-    // Validate bid value (0-13)
+    // Bid bounds depend on the game's rule set and are checked in the transaction
     let bid_value = bid_data.bid;
-    if !(0..=13).contains(&bid_value) {
-        return Ok(HttpResponse::BadRequest()
-            .content_type("application/json")
-            .json(json!({
-                "error": "Bid must be between 0 and 13"
-            })));
-    }
 
-    // Execute the entire operation in a transaction with row locks
-    let result = db
-        .transaction(|txn| {
-            Box::pin(bidding::submit_bid_transaction(
-                game_id, user.id, bid_value, txn,
-            ))
-        })
-        .await;
+    // Lock the game, so the action is taken at the version the player saw
+    let expected = expected_version(&req, bid_data.version.expected_version);
+    let (txn, _) = match begin_at_version(&db, game_id, &expected).await {
+        Ok(locked) => locked,
+        Err(response) => return Ok(response),
+    };
+
+    // Execute the entire operation in the transaction
+    let result = match bidding::submit_bid_transaction(game_id, user.id, bid_value, &txn).await {
+        Ok(()) => txn.commit().await.map_err(TransactionError::Connection),
+        Err(e) => Err(TransactionError::Transaction(e)),
+    };
 
     match result {
-        Ok(_) => Ok(HttpResponse::Ok()
-            .content_type("application/json")
-            .json(json!({
-                "message": "Bid submitted successfully",
-                "bid": bid_value
-            }))),
+        Ok(_) => {
+            ai_driver::schedule(game_id, db.get_ref().clone());
+            live::notify(game_id);
+            Ok(HttpResponse::Ok()
+                .content_type("application/json")
+                .json(json!({
+                    "message": "Bid submitted successfully",
+                    "bid": bid_value
+                })))
+        }
+        Err(TransactionError::Transaction(e)) => {
+            let mut body = json!({
+                "error": e.to_string(),
+                "code": e.code()
+            });
+            match e {
+                bidding::BidError::HookRule { forbidden_bid } => {
+                    body["forbidden_bid"] = json!(forbidden_bid);
+                }
+                bidding::BidError::OutOfRange {
+                    min_bid,
+                    max_bid,
+                    cards_dealt,
+                } => {
+                    body["min_bid"] = json!(min_bid);
+                    body["max_bid"] = json!(max_bid);
+                    body["cards_dealt"] = json!(cards_dealt);
+                }
+                bidding::BidError::Rejected(_) => {}
+            }
+            Ok(HttpResponse::BadRequest()
+                .content_type("application/json")
+                .json(body))
+        }
         Err(e) => Ok(HttpResponse::BadRequest()
             .content_type("application/json")
             .json(json!({
@@ -876,37 +1190,33 @@ pub async fn submit_trump(
         }
     };
 
-    // Validate trump suit
-    let trump_suit = &trump_data.trump_suit;
-    let valid_suits = ["Spades", "Hearts", "Diamonds", "Clubs", "NoTrump"];
-    if !valid_suits.contains(&trump_suit.as_str()) {
-        return Ok(HttpResponse::BadRequest()
-            .content_type("application/json")
-            .json(json!({
-                "error": "Invalid trump suit. Must be one of: Spades, Hearts, Diamonds, Clubs, NoTrump"
-            })));
-    }
+    let trump_suit = trump_data.trump_suit;
+
+    // Lock the game, so the action is taken at the version the player saw
+    let expected = expected_version(&req, trump_data.version.expected_version);
+    let (txn, _) = match begin_at_version(&db, game_id, &expected).await {
+        Ok(locked) => locked,
+        Err(response) => return Ok(response),
+    };
 
-    // Execute the entire operation in a transaction with row locks
-    let result = db
-        .transaction(|txn| {
-            Box::pin(bidding::submit_trump_transaction(
-                game_id,
-                user.id,
-                trump_suit.clone(),
-                txn,
-            ))
-        })
-        .await;
+    // Execute the entire operation in the transaction
+    let result = match bidding::submit_trump_transaction(game_id, user.id, trump_suit, &txn).await {
+        Ok(()) => txn.commit().await.map_err(|e| e.to_string()),
+        Err(e) => Err(e),
+    };
 
     match result {
-        Ok(_) => Ok(HttpResponse::Ok()
-            .content_type("application/json")
-            .json(json!({
-                "message": "Trump suit selected successfully",
-                "trump_suit": trump_suit,
-                "phase": "playing"
-            }))),
+        Ok(_) => {
+            ai_driver::schedule(game_id, db.get_ref().clone());
+            live::notify(game_id);
+            Ok(HttpResponse::Ok()
+                .content_type("application/json")
+                .json(json!({
+                    "message": "Trump suit selected successfully",
+                    "trump_suit": trump_suit,
+                    "phase": "playing"
+                })))
+        }
         Err(e) => Ok(HttpResponse::BadRequest()
             .content_type("application/json")
             .json(json!({
@@ -946,20 +1256,32 @@ pub async fn play_card(
         }
     };
 
-    // Extract the card from the request
-    let card = play_data.card.clone();
+    // Extract the card from the request (already validated by deserialization)
+    let card = play_data.card;
+
+    // Lock the game, so the action is taken at the version the player saw
+    let expected = expected_version(&req, play_data.version.expected_version);
+    let (txn, _) = match begin_at_version(&db, game_id, &expected).await {
+        Ok(locked) => locked,
+        Err(response) => return Ok(response),
+    };
 
-    // Execute the entire operation in a transaction with row locks
-    let result = db
-        .transaction(|txn| Box::pin(play_card_transaction(game_id, user.id, card.clone(), txn)))
-        .await;
+    // Execute the entire operation in the transaction
+    let result = match play_card_transaction(game_id, user.id, card, &txn).await {
+        Ok(()) => txn.commit().await.map_err(|e| e.to_string()),
+        Err(e) => Err(e),
+    };
 
     match result {
-        Ok(_) => Ok(HttpResponse::Ok()
-            .content_type("application/json")
-            .json(json!({
-                "message": "Card played successfully"
-            }))),
+        Ok(_) => {
+            ai_driver::schedule(game_id, db.get_ref().clone());
+            live::notify(game_id);
+            Ok(HttpResponse::Ok()
+                .content_type("application/json")
+                .json(json!({
+                    "message": "Card played successfully"
+                })))
+        }
         Err(e) => Ok(HttpResponse::BadRequest()
             .content_type("application/json")
             .json(json!({
@@ -1027,6 +1349,19 @@ pub async fn get_game_summary(
             })));
     }
 
+    // Round points and bonuses follow the game's chosen scoring rule
+    let scoring_rule = match game.rules() {
+        Ok(rules) => rules.scoring_rule(),
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to load game rules",
+                    "details": e
+                })));
+        }
+    };
+
     // Check if user is a participant in this game
     let user_in_game = match game_players::Entity::find()
         .filter(game_players::Column::GameId.eq(game_id))
@@ -1090,7 +1425,7 @@ pub async fn get_game_summary(
         };
 
         // Calculate total score for this player
-        let final_score = (calculate_player_total_score(&game_player.id, &game_id, &db).await)
+        let final_score = (calculate_player_total_score(&game_player.id, &game_id, &**db).await)
             .unwrap_or_default();
 
         let player_summary = PlayerSummary {
@@ -1107,7 +1442,7 @@ pub async fn get_game_summary(
     }
 
     // Sort players by final score (descending) and assign ranks with tie support
-    players_with_details.sort_by(|a, b| b.final_score.cmp(&a.final_score));
+    players_with_details.sort_by_key(|p| std::cmp::Reverse(p.final_score));
 
     // Assign ranks with tie support
     let mut current_rank = 1;
@@ -1191,8 +1526,8 @@ pub async fn get_game_summary(
                 .map(|s| s.tricks_won)
                 .unwrap_or(0);
 
-            let bonus = has_exact_bid_bonus(score, bid) && bid > 0;
-            let points = calculate_round_points(score, bid);
+            let bonus = scoring_rule.earns_bonus(score, bid);
+            let points = scoring_rule.round_points(score, bid);
 
             player_results.push(PlayerRoundResult {
                 player_id: player.id,
@@ -1206,8 +1541,10 @@ pub async fn get_game_summary(
         rounds_summary.push(RoundSummary {
             round_number: round.round_number,
             cards_dealt: round.cards_dealt,
-            trump_suit: round.trump_suit.clone(),
+            trump_suit: round.trump_suit.map(Trump::from),
             dealer_player_id: round.dealer_player_id,
+            deal_commitment: round.deal_commitment.clone(),
+            deal_reveal: deal_reveal(round, round_scores.len(), players_with_details.len()),
             player_results,
         });
     }
@@ -1242,7 +1579,7 @@ pub async fn get_game_summary(
                     .find(|b| b.player_id == score.player_id)
                     .map(|b| b.bid)
                     .unwrap_or(0);
-                let points = calculate_round_points(score.tricks_won, bid);
+                let points = scoring_rule.round_points(score.tricks_won, bid);
 
                 RoundScoreSummary {
                     player_id: score.player_id,
@@ -1256,7 +1593,7 @@ pub async fn get_game_summary(
         FinalRoundSummary {
             round_number: last_round.round_number,
             cards_dealt: last_round.cards_dealt,
-            trump_suit: last_round.trump_suit.clone(),
+            trump_suit: last_round.trump_suit.map(Trump::from),
             dealer_player_id: last_round.dealer_player_id,
             bids: final_bid_summaries,
             tricks_won: final_score_summaries,
@@ -1282,6 +1619,139 @@ pub async fn get_game_summary(
         .json(game_summary))
 }
 
+#[get("/game/{game_id}/round/{round_number}/verify")]
+pub async fn verify_round_deal(
+    req: HttpRequest,
+    path: web::Path<(String, i32)>,
+    db: web::Data<DatabaseConnection>,
+) -> ActixResult<HttpResponse> {
+    // Any signed-in player may check a revealed deal
+    if get_user(&req).is_none() {
+        return Ok(HttpResponse::Unauthorized()
+            .content_type("application/json")
+            .json(json!({
+                "error": "User not authenticated"
+            })));
+    }
+
+    // Parse game ID from path
+    let (game_id, round_number) = path.into_inner();
+    let game_id = match Uuid::parse_str(&game_id) {
+        Ok(id) => id,
+        Err(_) => {
+            return Ok(HttpResponse::BadRequest()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Invalid game ID format"
+                })));
+        }
+    };
+
+    // Fetch the requested round
+    let round = match game_rounds::Entity::find()
+        .filter(game_rounds::Column::GameId.eq(game_id))
+        .filter(game_rounds::Column::RoundNumber.eq(round_number))
+        .one(&**db)
+        .await
+    {
+        Ok(Some(round)) => round,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Round not found"
+                })));
+        }
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to fetch round",
+                    "details": e.to_string()
+                })));
+        }
+    };
+
+    let Some(commitment) = round.deal_commitment.clone() else {
+        return Ok(HttpResponse::NotFound()
+            .content_type("application/json")
+            .json(json!({
+                "error": "No deal commitment recorded for this round"
+            })));
+    };
+
+    // The seed stays secret until every player has been scored
+    let player_count = match game_players::Entity::find()
+        .filter(game_players::Column::GameId.eq(game_id))
+        .all(&**db)
+        .await
+    {
+        Ok(players) => players.len(),
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to fetch game players",
+                    "details": e.to_string()
+                })));
+        }
+    };
+    let scored_players = match round_scores::Entity::find()
+        .filter(round_scores::Column::RoundId.eq(round.id))
+        .all(&**db)
+        .await
+    {
+        Ok(scores) => scores.len(),
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to fetch round scores",
+                    "details": e.to_string()
+                })));
+        }
+    };
+    let Some(reveal) = deal_reveal(&round, scored_players, player_count) else {
+        return Ok(HttpResponse::BadRequest()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Round has not been scored yet; the deal is still secret"
+            })));
+    };
+
+    let dealt_hands = match load_dealt_hands(&round, &db).await {
+        Ok(hands) => hands,
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to load dealt hands",
+                    "details": e
+                })));
+        }
+    };
+
+    // Both values were checked by deal_reveal, so the seed is always present
+    let seed = round.deal_seed.unwrap_or_default();
+    let result = verify_deal(
+        &commitment,
+        seed,
+        &reveal.salt,
+        round.cards_dealt,
+        &dealt_hands,
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(json!({
+            "round_number": round.round_number,
+            "deal_commitment": commitment,
+            "deal_reveal": reveal,
+            "verified": result.is_ok(),
+            "error": result.err()
+        })))
+}
+
 #[delete("/game/{game_id}")]
 pub async fn delete_game(
     req: HttpRequest,
@@ -1312,11 +1782,18 @@ pub async fn delete_game(
         }
     };
 
+    // Lock the game, so it is only deleted as the player last saw it
+    let expected = expected_version(&req, None);
+    let (txn, _) = match begin_at_version(&db, game_id, &expected).await {
+        Ok(locked) => locked,
+        Err(response) => return Ok(response),
+    };
+
     // Check if user is a participant in the game
     let user_in_game = match game_players::Entity::find()
         .filter(game_players::Column::GameId.eq(game_id))
         .filter(game_players::Column::UserId.eq(user.id))
-        .one(&**db)
+        .one(&txn)
         .await
     {
         Ok(Some(_)) => true,
@@ -1349,7 +1826,11 @@ pub async fn delete_game(
     // - round_scores
     // - round_hands
 
-    match games::Entity::delete_by_id(game_id).exec(&**db).await {
+    let deleted = match games::Entity::delete_by_id(game_id).exec(&txn).await {
+        Ok(_) => txn.commit().await,
+        Err(e) => Err(e),
+    };
+    match deleted {
         Ok(_) => Ok(HttpResponse::Ok()
             .content_type("application/json")
             .json(json!({
@@ -1364,3 +1845,64 @@ pub async fn delete_game(
             }))),
     }
 }
+
+/// Read the version a player chose their action at
+///
+/// Taken from the `If-Match` header and the request body's `expected_version`;
+/// when both are sent, both must match.
+fn expected_version(req: &HttpRequest, field: Option<i32>) -> ExpectedVersion {
+    ExpectedVersion {
+        if_match: req
+            .headers()
+            .get(header::IF_MATCH)
+            .map(|value| value.to_str().unwrap_or_default().to_string()),
+        version: field,
+    }
+}
+
+/// Begin a transaction on a game, if it is still at the version expected
+///
+/// The game row stays locked until the transaction ends, so nothing else can
+/// change the game between the check and the action. Answers with the error
+/// response when the game can't be locked or has moved on.
+async fn begin_at_version(
+    db: &DatabaseConnection,
+    game_id: Uuid,
+    expected: &ExpectedVersion,
+) -> Result<(DatabaseTransaction, games::Model), HttpResponse> {
+    let txn = match db.begin().await {
+        Ok(txn) => txn,
+        Err(e) => {
+            return Err(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to begin transaction",
+                    "details": e.to_string()
+                })));
+        }
+    };
+
+    // Refuse actions chosen at an older version, telling the player where the game is now
+    match version::lock_at_version(game_id, expected, &txn).await {
+        Ok(game) => Ok((txn, game)),
+        Err(VersionError::NotFound) => Err(HttpResponse::NotFound()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Game not found"
+            }))),
+        Err(VersionError::Conflict { current_version }) => Err(HttpResponse::Conflict()
+            .content_type("application/json")
+            .insert_header((header::ETAG, version::etag(current_version)))
+            .json(json!({
+                "error": "Game has changed since this action was chosen",
+                "code": "VERSION_CONFLICT",
+                "current_version": current_version
+            }))),
+        Err(VersionError::Storage(e)) => Err(HttpResponse::InternalServerError()
+            .content_type("application/json")
+            .json(json!({
+                "error": "Failed to fetch game",
+                "details": e
+            }))),
+    }
+}
@@ -0,0 +1,63 @@
+//! JSON request bodies
+//!
+//! Malformed bodies are answered in the API's usual `{"error": ...}` shape,
+//! both for handlers that extract `web::Json` and for those whose body is
+//! optional.
+
+use actix_web::{error::InternalError, web, HttpResponse};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+/// Response for a request body that does not parse
+pub(crate) fn invalid_body(details: impl ToString) -> HttpResponse {
+    HttpResponse::BadRequest()
+        .content_type("application/json")
+        .json(json!({
+            "error": "Invalid request body",
+            "details": details.to_string()
+        }))
+}
+
+/// `web::Json` settings that answer extraction errors with a JSON 400
+pub fn json_config() -> web::JsonConfig {
+    web::JsonConfig::default().error_handler(|err, _req| {
+        let response = invalid_body(&err);
+        InternalError::from_response(err, response).into()
+    })
+}
+
+/// Parse a body the client may leave out
+///
+/// Only an empty body counts as left out; anything else must parse, so a
+/// typo is refused rather than silently ignored.
+pub(crate) fn optional<T: DeserializeOwned>(body: &[u8]) -> serde_json::Result<Option<T>> {
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+    serde_json::from_slice(body).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::create_game_request::CreateGameRequest;
+
+    #[test]
+    fn test_empty_body_is_left_out() {
+        assert!(optional::<CreateGameRequest>(b"").unwrap().is_none());
+        assert!(optional::<CreateGameRequest>(b" \n").unwrap().is_none());
+        assert!(optional::<CreateGameRequest>(b"{}").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_malformed_body_is_refused() {
+        for body in [
+            r#"{"player_count":"5"}"#,
+            r#"{"scoring":"bogus"}"#,
+            r#"{"dealer_hook":1}"#,
+            "not json",
+        ] {
+            assert!(optional::<CreateGameRequest>(body.as_bytes()).is_err());
+        }
+    }
+}
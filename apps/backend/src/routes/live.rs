@@ -0,0 +1,339 @@
+use std::convert::Infallible;
+
+use actix_web::{get, web, HttpRequest, HttpResponse, Result as ActixResult};
+use actix_ws::{Message, Session};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde_json::json;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::entity::{game_players, games};
+use crate::game_management::live::{self, ActionRefused, LiveAction, Viewer};
+use crate::jwt::get_user;
+
+/// Messages an event stream may queue for a slow client
+const STREAM_BUFFER: usize = 32;
+
+/// Watch and play a game over a WebSocket
+///
+/// The current snapshot is sent on connect. After every change the viewer is
+/// sent each new event, then the updated snapshot, all redacted to their own
+/// hand. Actions are sent back as text messages in the bot reply format
+/// (`{"type": "bid", "bid": 2}`), optionally with the `expected_version` they
+/// were chosen at; a rejected one is answered with
+/// `{"type": "error", "error": ...}`.
+#[get("/game/{id}/ws")]
+pub async fn game_socket(
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Payload,
+    db: web::Data<DatabaseConnection>,
+) -> ActixResult<HttpResponse> {
+    let (game_id, viewer) = match watch_game(&req, path, &db).await {
+        Ok(watching) => watching,
+        Err(response) => return Ok(response),
+    };
+
+    let (response, mut session, mut messages) = actix_ws::handle(&req, body)?;
+    // Subscribe before the first read, so no change can slip in between
+    let mut changes = live::subscribe(game_id);
+    let db = db.get_ref().clone();
+    actix_web::rt::spawn(async move {
+        let mut seen = None;
+        if push_updates(game_id, &viewer, &mut seen, &mut session, &db)
+            .await
+            .is_ok()
+        {
+            loop {
+                tokio::select! {
+                    message = messages.recv() => match message {
+                        Some(Ok(Message::Text(text))) => {
+                            let result = match serde_json::from_str::<LiveAction>(&text) {
+                                Ok(action) => live::perform(game_id, viewer.user_id, action, &db).await,
+                                Err(e) => Err(ActionRefused::Failed(format!("Invalid action: {e}"))),
+                            };
+                            if let Err(refused) = result {
+                                let reply = refusal_reply(refused);
+                                if session.text(reply.to_string()).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                        Some(Ok(Message::Ping(bytes))) => {
+                            if session.pong(&bytes).await.is_err() {
+                                break;
+                            }
+                        }
+                        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                        Some(Ok(_)) => {}
+                    },
+                    change = changes.recv() => match change {
+                        // Missed wake-ups are covered by reading the log again
+                        Ok(()) | Err(RecvError::Lagged(_)) => {
+                            if push_updates(game_id, &viewer, &mut seen, &mut session, &db)
+                                .await
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                        Err(RecvError::Closed) => break,
+                    },
+                }
+            }
+        }
+        let _ = session.close(None).await;
+    });
+
+    Ok(response)
+}
+
+/// Answer to a socket action that was not carried out
+///
+/// A stale action gets the same conflict body as the HTTP endpoints' 409.
+fn refusal_reply(refused: ActionRefused) -> serde_json::Value {
+    match refused {
+        ActionRefused::Stale { current_version } => json!({
+            "type": "error",
+            "error": "Game has changed since this action was chosen",
+            "code": "VERSION_CONFLICT",
+            "status": 409,
+            "current_version": current_version
+        }),
+        ActionRefused::Failed(error) => json!({ "type": "error", "error": error }),
+    }
+}
+
+/// Send a viewer everything that happened since they last looked
+///
+/// Fails when the updates could not be read or the connection is gone.
+async fn push_updates(
+    game_id: Uuid,
+    viewer: &Viewer,
+    seen: &mut Option<i32>,
+    session: &mut Session,
+    db: &DatabaseConnection,
+) -> Result<(), ()> {
+    let (updates, latest) = match live::updates_since(game_id, viewer, *seen, db).await {
+        Ok(read) => read,
+        Err(e) => {
+            warn!("Failed to read updates for game {game_id}: {e}");
+            return Err(());
+        }
+    };
+    *seen = Some(latest);
+
+    for update in updates {
+        let text = match serde_json::to_string(&update) {
+            Ok(text) => text,
+            Err(e) => {
+                warn!("Failed to encode update for game {game_id}: {e}");
+                return Err(());
+            }
+        };
+        if session.text(text).await.is_err() {
+            return Err(());
+        }
+    }
+    Ok(())
+}
+
+/// Stream a game's events as Server-Sent Events
+///
+/// A new stream starts with the current snapshot; a client reconnecting with
+/// `Last-Event-ID` is sent every event logged after it instead. Each event's
+/// id is its sequence in the game's log, and the stream sends a heartbeat
+/// comment whenever it has been idle for `live::heartbeat_interval()`.
+#[get("/game/{id}/events")]
+pub async fn game_events(
+    req: HttpRequest,
+    path: web::Path<String>,
+    db: web::Data<DatabaseConnection>,
+) -> ActixResult<HttpResponse> {
+    let (game_id, viewer) = match watch_game(&req, path, &db).await {
+        Ok(watching) => watching,
+        Err(response) => return Ok(response),
+    };
+
+    // A reconnecting client resumes after the last event it received
+    let resume_after = match req.headers().get("Last-Event-ID") {
+        Some(value) => match value.to_str().ok().and_then(|id| id.trim().parse().ok()) {
+            Some(after) => Some(after),
+            None => {
+                return Ok(HttpResponse::BadRequest()
+                    .content_type("application/json")
+                    .json(json!({
+                        "error": "Last-Event-ID must be an event sequence number"
+                    })));
+            }
+        },
+        None => None,
+    };
+
+    // Subscribe before the first read, so no change can slip in between
+    let mut changes = live::subscribe(game_id);
+    let (sender, receiver) = mpsc::channel::<Result<web::Bytes, Infallible>>(STREAM_BUFFER);
+    let db = db.get_ref().clone();
+    actix_web::rt::spawn(async move {
+        let first = match resume_after {
+            Some(after) => live::events_since(game_id, &viewer, after, &db).await,
+            None => live::updates_since(game_id, &viewer, None, &db).await,
+        };
+        let Ok(mut seen) = stream_updates(game_id, first, &sender).await else {
+            return;
+        };
+
+        let mut heartbeat = tokio::time::interval(live::heartbeat_interval());
+        heartbeat.reset();
+        loop {
+            tokio::select! {
+                change = changes.recv() => match change {
+                    // Missed wake-ups are covered by reading the log again
+                    Ok(()) | Err(RecvError::Lagged(_)) => {
+                        let read = live::events_since(game_id, &viewer, seen, &db).await;
+                        match stream_updates(game_id, read, &sender).await {
+                            Ok(latest) => seen = latest,
+                            Err(()) => break,
+                        }
+                        heartbeat.reset();
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                _ = heartbeat.tick() => {
+                    let comment = web::Bytes::from_static(b": heartbeat\n\n");
+                    if sender.send(Ok(comment)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    let stream = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|message| (message, receiver))
+    });
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        // Stop buffering proxies from holding events back
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(stream))
+}
+
+/// Write updates to an event stream, returning the latest sequence sent
+///
+/// Fails when the updates could not be read or the client has gone.
+async fn stream_updates(
+    game_id: Uuid,
+    read: Result<(Vec<live::GameUpdate>, i32), String>,
+    sender: &mpsc::Sender<Result<web::Bytes, Infallible>>,
+) -> Result<i32, ()> {
+    let (updates, latest) = match read {
+        Ok(read) => read,
+        Err(e) => {
+            warn!("Failed to read updates for game {game_id}: {e}");
+            return Err(());
+        }
+    };
+
+    for update in updates {
+        let message = match update.to_sse() {
+            Ok(message) => message,
+            Err(e) => {
+                warn!("Failed to encode update for game {game_id}: {e}");
+                return Err(());
+            }
+        };
+        if sender.send(Ok(web::Bytes::from(message))).await.is_err() {
+            return Err(());
+        }
+    }
+    Ok(latest)
+}
+
+/// Check that the requesting user plays in the game they want to watch
+///
+/// Applies the same participant check as `get_game_state`, answering with the
+/// error response when it fails.
+async fn watch_game(
+    req: &HttpRequest,
+    path: web::Path<String>,
+    db: &DatabaseConnection,
+) -> Result<(Uuid, Viewer), HttpResponse> {
+    // Extract user from JWT authentication
+    let user = match get_user(req) {
+        Some(user) => user,
+        None => {
+            return Err(HttpResponse::Unauthorized()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "User not authenticated"
+                })));
+        }
+    };
+
+    // Parse game ID from path
+    let game_id = match Uuid::parse_str(&path.into_inner()) {
+        Ok(id) => id,
+        Err(_) => {
+            return Err(HttpResponse::BadRequest()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Invalid game ID format"
+                })));
+        }
+    };
+
+    match games::Entity::find_by_id(game_id).one(db).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(HttpResponse::NotFound()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Game not found"
+                })));
+        }
+        Err(e) => {
+            return Err(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to fetch game",
+                    "details": e.to_string()
+                })));
+        }
+    };
+
+    // Check if user is a participant in this game
+    let player = match game_players::Entity::find()
+        .filter(game_players::Column::GameId.eq(game_id))
+        .filter(game_players::Column::UserId.eq(user.id))
+        .one(db)
+        .await
+    {
+        Ok(Some(player)) => player,
+        Ok(None) => {
+            return Err(HttpResponse::Forbidden()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Access denied. You are not a participant in this game."
+                })));
+        }
+        Err(e) => {
+            return Err(HttpResponse::InternalServerError()
+                .content_type("application/json")
+                .json(json!({
+                    "error": "Failed to check user participation",
+                    "details": e.to_string()
+                })));
+        }
+    };
+    Ok((
+        game_id,
+        Viewer {
+            user_id: user.id,
+            player_id: player.id,
+        },
+    ))
+}
@@ -1 +1,5 @@
+pub mod admin;
+pub mod bots;
 pub mod game;
+pub mod json_body;
+pub mod live;
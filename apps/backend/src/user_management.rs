@@ -38,3 +38,17 @@ pub async fn ensure_user_exists(
         }
     }
 }
+
+/// Check whether a user may use admin-only endpoints
+///
+/// Admins are listed by email in the comma-separated `ADMIN_EMAILS`
+/// environment variable. Nobody is an admin when it is unset.
+pub fn is_admin(user: &User) -> bool {
+    std::env::var("ADMIN_EMAILS")
+        .map(|emails| {
+            emails
+                .split(',')
+                .any(|email| email.trim().eq_ignore_ascii_case(&user.email))
+        })
+        .unwrap_or(false)
+}
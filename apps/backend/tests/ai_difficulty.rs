@@ -0,0 +1,107 @@
+mod common;
+use std::time::Duration;
+
+use backend::entity::game_players;
+use common::{create_user, test_bootstrap, without_ai_delay};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use serde_json::Value;
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn ai_seats_play_at_their_difficulty() -> anyhow::Result<()> {
+    without_ai_delay();
+    std::env::set_var("AI_EXPERT_TIME_MS", "50");
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+
+    let (_, auth) = create_user(&db).await?;
+
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", auth.as_str()))
+        .set_json(serde_json::json!({ "player_count": 5 }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let created: Value = actix_web::test::read_body_json(res).await;
+    let game_id = created["game"]["id"].as_str().unwrap().to_string();
+    let host_id = created["game_players"][0]["id"].clone();
+
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/ready"))
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+
+    // An unknown level is refused rather than played as hard
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/add_ai"))
+        .insert_header(("Authorization", auth.as_str()))
+        .set_json(serde_json::json!({ "difficulty": "impossible" }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    let body: Value = actix_web::test::read_body_json(res).await;
+    assert_eq!(body["error"], "Invalid request body");
+
+    // One AI seat at each level; without a body the seat plays hard
+    for body in [
+        Some(serde_json::json!({ "difficulty": "easy" })),
+        Some(serde_json::json!({ "difficulty": "medium" })),
+        Some(serde_json::json!({ "difficulty": "expert" })),
+        None,
+    ] {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/add_ai"))
+            .insert_header(("Authorization", auth.as_str()));
+        let req = match body {
+            Some(body) => req.set_json(body),
+            None => req,
+        };
+        let res = actix_web::test::call_service(&app, req.to_request()).await;
+        assert!(res.status().is_success());
+    }
+
+    let seats = game_players::Entity::find()
+        .filter(game_players::Column::GameId.eq(game_id.parse::<Uuid>()?))
+        .order_by_asc(game_players::Column::TurnOrder)
+        .all(&db)
+        .await?;
+    let difficulties: Vec<_> = seats.iter().map(|seat| seat.ai_difficulty).collect();
+    assert_eq!(
+        difficulties,
+        [
+            None,
+            Some(game_players::AiDifficulty::Easy),
+            Some(game_players::AiDifficulty::Medium),
+            Some(game_players::AiDifficulty::Expert),
+            Some(game_players::AiDifficulty::Hard),
+        ]
+    );
+
+    // The host deals, so every AI seat bids before the host's turn comes
+    let mut bids = Vec::new();
+    for _ in 0..500 {
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!("/api/game/{game_id}/state"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+        let state: Value = actix_web::test::read_body_json(res).await;
+        if state["current_round"]["current_player_turn"] == host_id {
+            bids = state["current_round"]["bids"].as_array().unwrap().clone();
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(bids.len(), 4);
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+mod common;
+use std::time::Duration;
+
+use backend::entity::game_events;
+use backend::game_management::ai_driver;
+use common::{create_user, test_bootstrap, without_ai_delay};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Follow the suit led if possible, otherwise play the first card held
+fn legal_card(hand: &[Value], current_trick: &Value) -> Value {
+    let led_suit = current_trick["plays"]
+        .as_array()
+        .and_then(|plays| plays.first())
+        .and_then(|play| play["card"].as_str())
+        .and_then(|card| card.chars().last());
+    hand.iter()
+        .find(|card| card.as_str().unwrap().chars().last() == led_suit)
+        .unwrap_or(&hand[0])
+        .clone()
+}
+
+#[actix_web::test]
+async fn ai_seats_act_until_a_human_is_due() -> anyhow::Result<()> {
+    without_ai_delay();
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+
+    let (_, auth) = create_user(&db).await?;
+
+    // One human and two AI seats; the last AI to join starts the game
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", auth.as_str()))
+        .set_json(serde_json::json!({ "player_count": 3 }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let created: Value = actix_web::test::read_body_json(res).await;
+    let game_id = created["game"]["id"].as_str().unwrap().to_string();
+    let host_id = created["game_players"][0]["id"].clone();
+
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/ready"))
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    for _ in 0..2 {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/add_ai"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // Extra drivers, as after a restart, must not make anyone act twice
+    ai_driver::resume_pending(&db).await.unwrap();
+    ai_driver::resume_pending(&db).await.unwrap();
+
+    // The host only acts on their own turns; the AI seats take every other
+    // turn, through the opening round and into the next one
+    let mut host_turns = 0;
+    loop {
+        let mut state = Value::Null;
+        for _ in 0..500 {
+            let req = actix_web::test::TestRequest::get()
+                .uri(&format!("/api/game/{game_id}/state"))
+                .insert_header(("Authorization", auth.as_str()))
+                .to_request();
+            let res = actix_web::test::call_service(&app, req).await;
+            assert!(res.status().is_success());
+            state = actix_web::test::read_body_json(res).await;
+
+            let due = match state["game"]["phase"].as_str().unwrap() {
+                "trump_selection" => &state["trump_chooser_id"],
+                _ => &state["current_round"]["current_player_turn"],
+            };
+            if *due == host_id {
+                break;
+            }
+            state = Value::Null;
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(!state.is_null(), "AI seats stopped before the host's turn");
+
+        let round = &state["current_round"];
+        if round["round_number"] == 2 {
+            // The AI seat after the new dealer has already bid
+            assert_eq!(round["bids"].as_array().unwrap().len(), 1);
+            break;
+        }
+
+        let req = match state["game"]["phase"].as_str().unwrap() {
+            "bidding" => actix_web::test::TestRequest::post()
+                .uri(&format!("/api/game/{game_id}/bid"))
+                .set_json(serde_json::json!({ "bid": 5 })),
+            "trump_selection" => actix_web::test::TestRequest::post()
+                .uri(&format!("/api/game/{game_id}/trump"))
+                .set_json(serde_json::json!({ "trump_suit": "Hearts" })),
+            _ => {
+                let host = state["players"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .find(|player| player["id"] == host_id)
+                    .unwrap();
+                let card = legal_card(host["hand"].as_array().unwrap(), &round["current_trick"]);
+                actix_web::test::TestRequest::post()
+                    .uri(&format!("/api/game/{game_id}/play"))
+                    .set_json(serde_json::json!({ "card": card }))
+            }
+        };
+        let req = req
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+        host_turns += 1;
+    }
+
+    // The host bid once and played each of the 17 cards dealt
+    assert!(host_turns >= 18);
+
+    // Every seat acted exactly once per turn
+    let log = game_events::Entity::find()
+        .filter(game_events::Column::GameId.eq(game_id.parse::<Uuid>()?))
+        .order_by_asc(game_events::Column::Sequence)
+        .all(&db)
+        .await?;
+    let count = |event_type: &str| {
+        log.iter()
+            .filter(|entry| entry.event_type == event_type)
+            .count()
+    };
+    assert_eq!(count("card_played"), 3 * 17);
+    assert_eq!(count("trick_won"), 17);
+    assert_eq!(count("trump_chosen"), 1);
+    assert_eq!(count("round_scored"), 1);
+    assert_eq!(count("bid_placed"), 3 + 1);
+
+    Ok(())
+}
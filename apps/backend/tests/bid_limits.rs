@@ -0,0 +1,104 @@
+mod common;
+use backend::entity::{game_players, game_rounds, users};
+use chrono::Utc;
+use common::{test_bootstrap, test_issue_token};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn bids_cannot_exceed_cards_dealt() -> anyhow::Result<()> {
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+
+    let user_id = Uuid::new_v4();
+    let user = users::ActiveModel {
+        id: Set(user_id),
+        external_id: Set(user_id.to_string()),
+        email: Set(format!("bids-{user_id}@example.com")),
+        name: Set(Some("Bid Tester".to_string())),
+        is_ai: Set(false),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    };
+    let user = user.insert(&db).await?;
+    let token = test_issue_token(&user.external_id, &user.email, 3600);
+    let auth = format!("Bearer {token}");
+
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let created: serde_json::Value = actix_web::test::read_body_json(res).await;
+    let game_id: Uuid = created["game"]["id"].as_str().unwrap().parse()?;
+
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/ready"))
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+
+    for _ in 1..4 {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/add_ai"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // Turn the opening round into a 2 card round dealt by the last seat, so
+    // the host bids first
+    let last_seat = game_players::Entity::find()
+        .filter(game_players::Column::GameId.eq(game_id))
+        .filter(game_players::Column::TurnOrder.eq(3))
+        .one(&db)
+        .await?
+        .unwrap();
+    let round = game_rounds::Entity::find()
+        .filter(game_rounds::Column::GameId.eq(game_id))
+        .order_by_desc(game_rounds::Column::RoundNumber)
+        .one(&db)
+        .await?
+        .unwrap();
+    let mut round: game_rounds::ActiveModel = round.into();
+    round.cards_dealt = Set(2);
+    round.dealer_player_id = Set(Some(last_seat.id));
+    round.update(&db).await?;
+
+    // Bidding more tricks than cards dealt is rejected with the allowed range
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/bid"))
+        .insert_header(("Authorization", auth.as_str()))
+        .set_json(serde_json::json!({ "bid": 9 }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = actix_web::test::read_body_json(res).await;
+    assert_eq!(body["code"], "BID_OUT_OF_RANGE");
+    assert_eq!(body["min_bid"], 0);
+    assert_eq!(body["max_bid"], 2);
+    assert_eq!(body["cards_dealt"], 2);
+    assert_eq!(
+        body["error"],
+        "Bid must be between 0 and 2 when 2 cards are dealt"
+    );
+
+    // A bid of every card dealt is allowed
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/bid"))
+        .insert_header(("Authorization", auth.as_str()))
+        .set_json(serde_json::json!({ "bid": 2 }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+
+    Ok(())
+}
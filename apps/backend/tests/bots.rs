@@ -0,0 +1,315 @@
+mod common;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::dev::{Service, ServiceResponse};
+use backend::game_management::bot_protocol::{reference_reply, DecisionRequest};
+use common::{create_user, test_bootstrap, without_ai_delay};
+use futures_util::{SinkExt, StreamExt};
+use sea_orm::DatabaseConnection;
+use serde_json::Value;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+use uuid::Uuid;
+
+/// Serve bot callbacks on a free port, answering like the reference bot
+///
+/// Each request is counted, and answered after `delay`.
+fn serve_callback_bot(delay: Duration) -> anyhow::Result<(String, Arc<AtomicUsize>)> {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counted = calls.clone();
+    let server = actix_web::HttpServer::new(move || {
+        let calls = counted.clone();
+        actix_web::App::new().route(
+            "/",
+            actix_web::web::post().to(move |request: actix_web::web::Json<DecisionRequest>| {
+                let calls = calls.clone();
+                async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(delay).await;
+                    actix_web::HttpResponse::Ok().json(reference_reply(&request))
+                }
+            }),
+        )
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))?;
+    let url = format!("http://{}/", server.addrs()[0]);
+    actix_web::rt::spawn(server.run());
+    Ok((url, calls))
+}
+
+async fn register_bot<S>(app: &S, auth: &str, body: Value) -> (Uuid, String)
+where
+    S: Service<actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>,
+{
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/bots")
+        .insert_header(("Authorization", auth))
+        .set_json(body)
+        .to_request();
+    let res = actix_web::test::call_service(app, req).await;
+    assert!(res.status().is_success());
+    let registered: Value = actix_web::test::read_body_json(res).await;
+    assert!(registered["bot"].get("token_hash").is_none());
+    (
+        registered["bot"]["id"].as_str().unwrap().parse().unwrap(),
+        registered["token"].as_str().unwrap().to_string(),
+    )
+}
+
+/// Start a three-player game with the bot in seat 1 and a hard AI in seat 2
+///
+/// Returns the game id and the host's player id.
+async fn start_game_with_bot<S>(app: &S, auth: &str, bot_id: Uuid) -> (String, Value)
+where
+    S: Service<actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>,
+{
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", auth))
+        .set_json(serde_json::json!({ "player_count": 3 }))
+        .to_request();
+    let res = actix_web::test::call_service(app, req).await;
+    assert!(res.status().is_success());
+    let created: Value = actix_web::test::read_body_json(res).await;
+    let game_id = created["game"]["id"].as_str().unwrap().to_string();
+    let host_id = created["game_players"][0]["id"].clone();
+
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/ready"))
+        .insert_header(("Authorization", auth))
+        .to_request();
+    let res = actix_web::test::call_service(app, req).await;
+    assert!(res.status().is_success());
+
+    for body in [
+        serde_json::json!({ "bot_id": bot_id }),
+        serde_json::json!({ "difficulty": "hard" }),
+    ] {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/add_ai"))
+            .insert_header(("Authorization", auth))
+            .set_json(body)
+            .to_request();
+        let res = actix_web::test::call_service(app, req).await;
+        assert!(res.status().is_success());
+    }
+    (game_id, host_id)
+}
+
+/// Wait for the host's first bid, returning the game state at that point
+async fn state_before_host<S>(app: &S, auth: &str, game_id: &str, host_id: &Value) -> Value
+where
+    S: Service<actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>,
+{
+    for _ in 0..500 {
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!("/api/game/{game_id}/state"))
+            .insert_header(("Authorization", auth))
+            .to_request();
+        let res = actix_web::test::call_service(app, req).await;
+        assert!(res.status().is_success());
+        let state: Value = actix_web::test::read_body_json(res).await;
+        if state["current_round"]["current_player_turn"] == *host_id {
+            return state;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("Host's turn never came");
+}
+
+/// Bids placed so far, with the bid of the bot seated after the host
+fn bids_with_bot_bid(state: &Value) -> (usize, Value) {
+    let bot = state["players"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|player| player["turn_order"] == 1)
+        .unwrap();
+    let bids = state["current_round"]["bids"].as_array().unwrap();
+    let bot_bid = bids
+        .iter()
+        .find(|bid| bid["player_id"] == bot["id"])
+        .map(|bid| bid["bid"].clone())
+        .unwrap_or_default();
+    (bids.len(), bot_bid)
+}
+
+/// Integration tests share one database pool per process, so the bot scenarios
+/// run one after another in a single test
+#[actix_web::test]
+async fn bots_decide_for_their_seats() -> anyhow::Result<()> {
+    without_ai_delay();
+    std::env::set_var("BOT_DECISION_TIMEOUT_MS", "300");
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+    let (_, auth) = create_user(&db).await?;
+
+    bot_registration_is_validated(&app, &auth).await?;
+    // The test bots listen on this machine
+    std::env::set_var("BOT_CALLBACK_HOSTS", "127.0.0.1");
+    callback_bot_decides_for_its_seat(&app, &auth).await?;
+    slow_bot_falls_back_to_builtin_ai(&app, &auth).await?;
+    websocket_bot_decides_for_its_seat(&app, &auth, &db).await?;
+    Ok(())
+}
+
+async fn callback_bot_decides_for_its_seat<S>(app: &S, auth: &str) -> anyhow::Result<()>
+where
+    S: Service<actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>,
+{
+    let (url, calls) = serve_callback_bot(Duration::ZERO)?;
+    let (bot_id, _) = register_bot(
+        app,
+        auth,
+        serde_json::json!({ "name": "Callback Bot", "callback_url": url }),
+    )
+    .await;
+    let (game_id, host_id) = start_game_with_bot(app, auth, bot_id).await;
+
+    // The reference bot bids the lowest legal bid
+    let state = state_before_host(app, auth, &game_id, &host_id).await;
+    let (bid_count, bot_bid) = bids_with_bot_bid(&state);
+    assert_eq!(bid_count, 2);
+    assert_eq!(bot_bid, 0);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+async fn slow_bot_falls_back_to_builtin_ai<S>(app: &S, auth: &str) -> anyhow::Result<()>
+where
+    S: Service<actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>,
+{
+    let (url, calls) = serve_callback_bot(Duration::from_secs(2))?;
+    let (bot_id, _) = register_bot(
+        app,
+        auth,
+        serde_json::json!({ "name": "Slow Bot", "callback_url": url }),
+    )
+    .await;
+    let (game_id, host_id) = start_game_with_bot(app, auth, bot_id).await;
+
+    // The bot was asked, missed its deadline and the game went on without it
+    let state = state_before_host(app, auth, &game_id, &host_id).await;
+    assert_eq!(bids_with_bot_bid(&state).0, 2);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+async fn websocket_bot_decides_for_its_seat<S>(
+    app: &S,
+    auth: &str,
+    db: &DatabaseConnection,
+) -> anyhow::Result<()>
+where
+    S: Service<actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>,
+{
+    // Bots connect to a real server
+    let served = db.clone();
+    let server = actix_web::HttpServer::new(move || {
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(served.clone()))
+            .configure(backend::configure_routes)
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))?;
+    let address = server.addrs()[0];
+    actix_web::rt::spawn(server.run());
+
+    let (bot_id, token) =
+        register_bot(app, auth, serde_json::json!({ "name": "Socket Bot" })).await;
+
+    // A wrong token is turned away
+    let mut request = format!("ws://{address}/bots/connect").into_client_request()?;
+    request
+        .headers_mut()
+        .insert("Authorization", "Bot not-a-token".parse()?);
+    assert!(tokio_tungstenite::connect_async(request).await.is_err());
+
+    let mut request = format!("ws://{address}/bots/connect").into_client_request()?;
+    request
+        .headers_mut()
+        .insert("Authorization", format!("Bot {token}").parse()?);
+    let (mut socket, _) = tokio_tungstenite::connect_async(request).await?;
+    let answered = Arc::new(AtomicUsize::new(0));
+    let counted = answered.clone();
+    actix_web::rt::spawn(async move {
+        while let Some(Ok(message)) = socket.next().await {
+            let Message::Text(text) = message else {
+                continue;
+            };
+            let request: DecisionRequest = serde_json::from_str(&text).unwrap();
+            let reply = serde_json::to_string(&reference_reply(&request)).unwrap();
+            counted.fetch_add(1, Ordering::SeqCst);
+            if socket.send(Message::text(reply)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let (game_id, host_id) = start_game_with_bot(app, auth, bot_id).await;
+    let state = state_before_host(app, auth, &game_id, &host_id).await;
+    let (bid_count, bot_bid) = bids_with_bot_bid(&state);
+    assert_eq!(bid_count, 2);
+    assert_eq!(bot_bid, 0);
+    assert_eq!(answered.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+async fn bot_registration_is_validated<S>(app: &S, auth: &str) -> anyhow::Result<()>
+where
+    S: Service<actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>,
+{
+    for body in [
+        serde_json::json!({ "name": "  " }),
+        serde_json::json!({ "name": "Bot", "callback_url": "ftp://example.com" }),
+        serde_json::json!({ "name": "Bot", "callback_url": "http://127.0.0.1:9000/" }),
+        serde_json::json!({ "name": "Bot", "callback_url": "http://169.254.169.254/" }),
+        serde_json::json!({ "name": "Bot", "callback_url": "http://[::1]/" }),
+    ] {
+        let req = actix_web::test::TestRequest::post()
+            .uri("/api/bots")
+            .insert_header(("Authorization", auth))
+            .set_json(body)
+            .to_request();
+        let res = actix_web::test::call_service(app, req).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", auth))
+        .to_request();
+    let res = actix_web::test::call_service(app, req).await;
+    assert!(res.status().is_success());
+    let created: Value = actix_web::test::read_body_json(res).await;
+    let game_id = created["game"]["id"].as_str().unwrap().to_string();
+
+    // Only registered bots can be seated, and each only once per game
+    let (bot_id, _) = register_bot(app, auth, serde_json::json!({ "name": "Seated Bot" })).await;
+    for (bot_id, status) in [
+        (Uuid::new_v4(), actix_web::http::StatusCode::NOT_FOUND),
+        (bot_id, actix_web::http::StatusCode::OK),
+        (bot_id, actix_web::http::StatusCode::BAD_REQUEST),
+    ] {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/add_ai"))
+            .insert_header(("Authorization", auth))
+            .set_json(serde_json::json!({ "bot_id": bot_id }))
+            .to_request();
+        let res = actix_web::test::call_service(app, req).await;
+        assert_eq!(res.status(), status);
+    }
+
+    Ok(())
+}
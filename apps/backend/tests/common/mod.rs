@@ -1,10 +1,13 @@
+use backend::entity::users;
+use chrono::Utc;
 use migration::{Migrator, MigratorTrait};
-use sea_orm::{Database, DatabaseConnection};
+use sea_orm::{ActiveModelTrait, Database, DatabaseConnection, Set};
 use std::env;
 use std::sync::Once;
 use tokio::sync::OnceCell;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use uuid::Uuid;
 
 static INIT: Once = Once::new();
 static ENSURE_TEST_DB_ONCE: Once = Once::new();
@@ -26,6 +29,30 @@ pub fn test_issue_token(sub: &str, email: &str, ttl_seconds: i64) -> String {
     backend::jwt::issue_test_token(sub, email, ttl_seconds)
 }
 
+/// Create a player and the `Authorization` header they send
+#[allow(dead_code)] // not every test file signs players in
+pub async fn create_user(db: &DatabaseConnection) -> anyhow::Result<(Uuid, String)> {
+    let user_id = Uuid::new_v4();
+    let user = users::ActiveModel {
+        id: Set(user_id),
+        external_id: Set(user_id.to_string()),
+        email: Set(format!("player-{user_id}@example.com")),
+        name: Set(Some("Test Player".to_string())),
+        is_ai: Set(false),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    };
+    let user = user.insert(db).await?;
+    let token = test_issue_token(&user.external_id, &user.email, 3600);
+    Ok((user_id, format!("Bearer {token}")))
+}
+
+/// Let AI seats act at once rather than pausing between turns
+#[allow(dead_code)] // not every test file seats AI players
+pub fn without_ai_delay() {
+    env::set_var("AI_TURN_DELAY_MS", "0");
+}
+
 /// Test bootstrap that loads .env, ensures *_test database, inits tracing, connects+migrates once
 pub async fn test_bootstrap() -> DatabaseConnection {
     let _ = dotenv::dotenv();
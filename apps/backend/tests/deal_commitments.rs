@@ -0,0 +1,179 @@
+mod common;
+use backend::entity::{
+    game_players, game_rounds, round_hands, round_scores, round_tricks, trick_plays, users,
+};
+use backend::game_management::fairness::deal_commitment;
+use chrono::Utc;
+use common::{test_bootstrap, test_issue_token};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, Order, QueryFilter, QueryOrder, Set};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn deals_are_committed_then_revealed() -> anyhow::Result<()> {
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+
+    let user_id = Uuid::new_v4();
+    let user = users::ActiveModel {
+        id: Set(user_id),
+        external_id: Set(user_id.to_string()),
+        email: Set(format!("fair-{user_id}@example.com")),
+        name: Set(Some("Fairness Tester".to_string())),
+        is_ai: Set(false),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    };
+    let user = user.insert(&db).await?;
+    let token = test_issue_token(&user.external_id, &user.email, 3600);
+    let auth = format!("Bearer {token}");
+
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let created: serde_json::Value = actix_web::test::read_body_json(res).await;
+    let game_id: Uuid = created["game"]["id"].as_str().unwrap().parse()?;
+
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/ready"))
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    for _ in 1..4 {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/add_ai"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // The commitment is published with the round, the seed is not
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("/api/game/{game_id}/state"))
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let state: serde_json::Value = actix_web::test::read_body_json(res).await;
+    let commitment = state["current_round"]["deal_commitment"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(commitment.len(), 64);
+    assert!(state["current_round"]["deal_reveal"].is_null());
+
+    let verify_uri = format!("/api/game/{game_id}/round/1/verify");
+    let req = actix_web::test::TestRequest::get()
+        .uri(&verify_uri)
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+    // Play one trick directly: played cards leave round_hands
+    let round = game_rounds::Entity::find()
+        .filter(game_rounds::Column::GameId.eq(game_id))
+        .one(&db)
+        .await?
+        .unwrap();
+    let players = game_players::Entity::find()
+        .filter(game_players::Column::GameId.eq(game_id))
+        .order_by(game_players::Column::TurnOrder, Order::Asc)
+        .all(&db)
+        .await?;
+    let trick_id = Uuid::new_v4();
+    round_tricks::ActiveModel {
+        id: Set(trick_id),
+        round_id: Set(round.id),
+        trick_number: Set(1),
+        winner_player_id: Set(Some(players[0].id)),
+        created_at: Set(Utc::now().into()),
+    }
+    .insert(&db)
+    .await?;
+    for (play_order, player) in players.iter().enumerate() {
+        let card = round_hands::Entity::find()
+            .filter(round_hands::Column::RoundId.eq(round.id))
+            .filter(round_hands::Column::PlayerId.eq(player.id))
+            .one(&db)
+            .await?
+            .unwrap();
+        trick_plays::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            trick_id: Set(trick_id),
+            player_id: Set(player.id),
+            card: Set(card.card.clone()),
+            play_order: Set(play_order as i32),
+        }
+        .insert(&db)
+        .await?;
+        round_hands::Entity::delete_by_id(card.id).exec(&db).await?;
+    }
+
+    // Scoring every player reveals the seed and salt
+    for player in &players {
+        round_scores::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            round_id: Set(round.id),
+            player_id: Set(player.id),
+            tricks_won: Set(0),
+        }
+        .insert(&db)
+        .await?;
+    }
+
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("/api/game/{game_id}/state"))
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    let state: serde_json::Value = actix_web::test::read_body_json(res).await;
+    let reveal = &state["current_round"]["deal_reveal"];
+    let seed: i64 = reveal["seed"].as_str().unwrap().parse()?;
+    let salt = reveal["salt"].as_str().unwrap();
+    assert_eq!(deal_commitment(seed, salt), commitment);
+
+    let req = actix_web::test::TestRequest::get()
+        .uri(&verify_uri)
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let verified: serde_json::Value = actix_web::test::read_body_json(res).await;
+    assert_eq!(verified["verified"], true, "{verified}");
+    assert_eq!(verified["deal_commitment"], commitment);
+
+    // A stacked deck no longer matches the revealed seed
+    let moved = round_hands::Entity::find()
+        .filter(round_hands::Column::RoundId.eq(round.id))
+        .filter(round_hands::Column::PlayerId.eq(players[1].id))
+        .one(&db)
+        .await?
+        .unwrap();
+    let mut moved: round_hands::ActiveModel = moved.into();
+    moved.player_id = Set(players[0].id);
+    moved.update(&db).await?;
+
+    let req = actix_web::test::TestRequest::get()
+        .uri(&verify_uri)
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    let verified: serde_json::Value = actix_web::test::read_body_json(res).await;
+    assert_eq!(verified["verified"], false);
+    assert_eq!(
+        verified["error"],
+        "Hand dealt to seat 0 does not match the revealed seed"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+mod common;
+use backend::game_management::bidding::{deal_hands, deck_for_seed, round_deal_seed};
+use common::{create_user, test_bootstrap, test_issue_token};
+
+const ADMIN_EMAIL: &str = "deal-admin@example.com";
+const PINNED_SEED: i64 = 20250301;
+
+fn sorted_cards(cards: &serde_json::Value) -> Vec<String> {
+    let mut cards: Vec<String> = cards
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|card| card.as_str().unwrap().to_string())
+        .collect();
+    cards.sort();
+    cards
+}
+
+#[actix_web::test]
+async fn pinned_seeds_deal_reproducibly() -> anyhow::Result<()> {
+    std::env::set_var("ADMIN_EMAILS", ADMIN_EMAIL);
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+
+    let admin_token = test_issue_token("deal-admin", ADMIN_EMAIL, 3600);
+    let admin_auth = format!("Bearer {admin_token}");
+    let (user_id, auth) = create_user(&db).await?;
+
+    // Only admins may pin the seed
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", auth.as_str()))
+        .set_json(serde_json::json!({ "deal_seed": PINNED_SEED }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), actix_web::http::StatusCode::FORBIDDEN);
+
+    // Two admin games pinned to the same seed deal the same opening hands
+    let mut opening_hands = Vec::new();
+    for _ in 0..2 {
+        let req = actix_web::test::TestRequest::post()
+            .uri("/api/create_game")
+            .insert_header(("Authorization", admin_auth.as_str()))
+            .set_json(serde_json::json!({ "deal_seed": PINNED_SEED }))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+        let created: serde_json::Value = actix_web::test::read_body_json(res).await;
+        let game_id = created["game"]["id"].as_str().unwrap().to_string();
+
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/ready"))
+            .insert_header(("Authorization", admin_auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+
+        for _ in 1..4 {
+            let req = actix_web::test::TestRequest::post()
+                .uri(&format!("/api/game/{game_id}/add_ai"))
+                .insert_header(("Authorization", admin_auth.as_str()))
+                .to_request();
+            let res = actix_web::test::call_service(&app, req).await;
+            assert!(res.status().is_success());
+        }
+
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!("/api/game/{game_id}/state"))
+            .insert_header(("Authorization", admin_auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+        let state: serde_json::Value = actix_web::test::read_body_json(res).await;
+        let host = state["players"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|player| player["turn_order"] == 0)
+            .unwrap();
+        let host_hand = sorted_cards(&host["hand"]);
+
+        // The admin endpoint regenerates the exact deal
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!("/api/admin/game/{game_id}/round/1/deal"))
+            .insert_header(("Authorization", admin_auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+        let deal: serde_json::Value = actix_web::test::read_body_json(res).await;
+        let seed = round_deal_seed(PINNED_SEED, 1);
+        assert_eq!(deal["deal_seed"], seed);
+        assert_eq!(deal["hands"].as_array().unwrap().len(), 4);
+        assert_eq!(sorted_cards(&deal["hands"][0]["hand"]), host_hand);
+
+        let expected = deal_hands(&deck_for_seed(seed), 4, 13).unwrap();
+        let mut expected_host: Vec<String> = expected[0].iter().map(|c| c.to_string()).collect();
+        expected_host.sort();
+        assert_eq!(host_hand, expected_host);
+
+        // Regenerating a deal is admin-only
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!("/api/admin/game/{game_id}/round/1/deal"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::FORBIDDEN);
+
+        opening_hands.push(host_hand);
+    }
+    assert_eq!(opening_hands[0], opening_hands[1]);
+
+    // Unpinned games still record a seed that regenerates the deal
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let created: serde_json::Value = actix_web::test::read_body_json(res).await;
+    let game_id = created["game"]["id"].as_str().unwrap().to_string();
+
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/ready"))
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    for _ in 1..4 {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/add_ai"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("/api/game/{game_id}/state"))
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    let state: serde_json::Value = actix_web::test::read_body_json(res).await;
+    let host = state["players"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|player| player["user_id"] == user_id.to_string())
+        .unwrap();
+
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("/api/admin/game/{game_id}/round/1/deal"))
+        .insert_header(("Authorization", admin_auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let deal: serde_json::Value = actix_web::test::read_body_json(res).await;
+    assert!(deal["deal_seed"].is_i64());
+    assert_eq!(
+        sorted_cards(&deal["hands"][0]["hand"]),
+        sorted_cards(&host["hand"])
+    );
+
+    Ok(())
+}
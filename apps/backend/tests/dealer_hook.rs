@@ -0,0 +1,92 @@
+mod common;
+use common::{create_user, test_bootstrap};
+
+#[actix_web::test]
+async fn dealer_hook_rejects_forbidden_last_bid() -> anyhow::Result<()> {
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+
+    // Four human players, in turn order
+    let mut auths = Vec::new();
+    for _ in 0..4 {
+        auths.push(create_user(&db).await?.1);
+    }
+
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", auths[0].as_str()))
+        .set_json(serde_json::json!({ "dealer_hook": true }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let created: serde_json::Value = actix_web::test::read_body_json(res).await;
+    let game_id = created["game"]["id"].as_str().unwrap().to_string();
+
+    for auth in &auths[1..] {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/join_game?game_id={game_id}"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    for auth in &auths {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/ready"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // The host deals the opening round, so the other three bid first,
+    // 3 each of the 13 cards dealt
+    for auth in &auths[1..] {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/bid"))
+            .insert_header(("Authorization", auth.as_str()))
+            .set_json(serde_json::json!({ "bid": 3 }))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // The snapshot tells the dealer, bidding last, which bid is greyed out
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("/api/game/{game_id}/state"))
+        .insert_header(("Authorization", auths[0].as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let state: serde_json::Value = actix_web::test::read_body_json(res).await;
+    assert_eq!(state["current_round"]["forbidden_bid"], 4);
+
+    // Bidding 4 would make the total equal the cards dealt
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/bid"))
+        .insert_header(("Authorization", auths[0].as_str()))
+        .set_json(serde_json::json!({ "bid": 4 }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = actix_web::test::read_body_json(res).await;
+    assert_eq!(body["code"], "HOOK_RULE_VIOLATION");
+    assert_eq!(body["forbidden_bid"], 4);
+
+    // Any other bid is accepted
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/bid"))
+        .insert_header(("Authorization", auths[0].as_str()))
+        .set_json(serde_json::json!({ "bid": 5 }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+
+    Ok(())
+}
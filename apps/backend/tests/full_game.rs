@@ -0,0 +1,187 @@
+mod common;
+use backend::entity::games;
+use backend::game_management::rules::RuleSet;
+use common::{create_user, test_bootstrap};
+use sea_orm::EntityTrait;
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Follow the suit led if possible, otherwise play the first card held
+fn legal_card(hand: &[Value], current_trick: &Value) -> Value {
+    let led_suit = current_trick["plays"]
+        .as_array()
+        .and_then(|plays| plays.first())
+        .and_then(|play| play["card"].as_str())
+        .and_then(|card| card.chars().last());
+    hand.iter()
+        .find(|card| card.as_str().unwrap().chars().last() == led_suit)
+        .unwrap_or(&hand[0])
+        .clone()
+}
+
+#[actix_web::test]
+async fn standard_game_plays_through_to_completion() -> anyhow::Result<()> {
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+
+    let mut users = Vec::new();
+    for _ in 0..4 {
+        users.push(create_user(&db).await?);
+    }
+
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", users[0].1.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let created: Value = actix_web::test::read_body_json(res).await;
+    let game_id = created["game"]["id"].as_str().unwrap().to_string();
+
+    for (_, auth) in &users[1..] {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/join_game?game_id={game_id}"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+    for (_, auth) in &users {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/ready"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // Whoever is due to act makes a legal move until the game is over
+    let game_uuid: Uuid = game_id.parse()?;
+    let mut rounds_seen = Vec::new();
+    loop {
+        let game = games::Entity::find_by_id(game_uuid)
+            .one(&db)
+            .await?
+            .unwrap();
+        if game.state == games::GameState::Completed {
+            break;
+        }
+
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!("/api/game/{game_id}/state"))
+            .insert_header(("Authorization", users[0].1.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+        let state: Value = actix_web::test::read_body_json(res).await;
+
+        let round = &state["current_round"];
+        let round_number = round["round_number"].as_i64().unwrap();
+        if rounds_seen.last() != Some(&round_number) {
+            rounds_seen.push(round_number);
+        }
+
+        let phase = state["game"]["phase"].as_str().unwrap();
+        let player_id = match phase {
+            "trump_selection" => state["trump_chooser_id"].as_str().unwrap(),
+            _ => round["current_player_turn"].as_str().unwrap(),
+        };
+        let player = state["players"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|player| player["id"] == player_id)
+            .unwrap();
+        let auth = &users
+            .iter()
+            .find(|(id, _)| player["user_id"] == id.to_string())
+            .unwrap()
+            .1;
+
+        let req = match phase {
+            "bidding" => actix_web::test::TestRequest::post()
+                .uri(&format!("/api/game/{game_id}/bid"))
+                .set_json(serde_json::json!({ "bid": 1 })),
+            "trump_selection" => actix_web::test::TestRequest::post()
+                .uri(&format!("/api/game/{game_id}/trump"))
+                .set_json(serde_json::json!({ "trump_suit": "Spades" })),
+            _ => {
+                let hand = player["hand"].as_array().unwrap();
+                let card = legal_card(hand, &round["current_trick"]);
+                actix_web::test::TestRequest::post()
+                    .uri(&format!("/api/game/{game_id}/play"))
+                    .set_json(serde_json::json!({ "card": card }))
+            }
+        };
+        let req = req
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+    assert_eq!(rounds_seen, (1..=26).collect::<Vec<_>>());
+
+    let game = games::Entity::find_by_id(game_uuid)
+        .one(&db)
+        .await?
+        .unwrap();
+    assert!(game.completed_at.is_some());
+    assert_eq!(game.current_turn, None);
+
+    // Every round was scored, dealt to schedule, with the deal moving left
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("/api/game/{game_id}/summary"))
+        .insert_header(("Authorization", users[0].1.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let summary: Value = actix_web::test::read_body_json(res).await;
+
+    let rounds = summary["rounds"].as_array().unwrap();
+    let cards_dealt: Vec<i32> = rounds
+        .iter()
+        .map(|round| round["cards_dealt"].as_i64().unwrap() as i32)
+        .collect();
+    assert_eq!(cards_dealt, RuleSet::standard().round_schedule);
+
+    let seat_ids: Vec<&Value> = summary["players"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|player| &player["id"])
+        .collect();
+    let mut totals = vec![0; 4];
+    for (index, round) in rounds.iter().enumerate() {
+        assert_eq!(&round["dealer_player_id"], seat_ids[index % 4]);
+
+        let results = round["player_results"].as_array().unwrap();
+        assert_eq!(results.len(), 4);
+        let tricks: i64 = results
+            .iter()
+            .map(|result| result["tricks_won"].as_i64().unwrap())
+            .sum();
+        assert_eq!(tricks, round["cards_dealt"].as_i64().unwrap());
+
+        for result in results {
+            let seat = seat_ids
+                .iter()
+                .position(|id| **id == result["player_id"])
+                .unwrap();
+            totals[seat] += result["points"].as_i64().unwrap();
+        }
+    }
+    let final_scores: Vec<i64> = summary["players"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|player| player["final_score"].as_i64().unwrap())
+        .collect();
+    assert_eq!(final_scores, totals);
+
+    Ok(())
+}
@@ -0,0 +1,194 @@
+mod common;
+use backend::entity::game_events;
+use common::{create_user, test_bootstrap, test_issue_token};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use serde_json::Value;
+use uuid::Uuid;
+
+const ADMIN_EMAIL: &str = "events-admin@example.com";
+
+/// Drop the log timestamps and put unordered lists in a fixed order
+fn comparable(mut snapshot: Value) -> Value {
+    for field in ["created_at", "updated_at", "started_at"] {
+        snapshot["game"][field] = Value::Null;
+    }
+    let players = snapshot["players"].as_array_mut().unwrap();
+    players.sort_by_key(|player| player["turn_order"].as_i64());
+    for player in players {
+        if let Some(hand) = player["hand"].as_array_mut() {
+            hand.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+        }
+    }
+    if let Some(bids) = snapshot["current_round"]["bids"].as_array_mut() {
+        bids.sort_by(|a, b| a["player_id"].as_str().cmp(&b["player_id"].as_str()));
+    }
+    snapshot
+}
+
+#[actix_web::test]
+async fn event_log_replays_to_live_snapshot() -> anyhow::Result<()> {
+    std::env::set_var("ADMIN_EMAILS", ADMIN_EMAIL);
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+
+    let admin_token = test_issue_token("events-admin", ADMIN_EMAIL, 3600);
+    let admin_auth = format!("Bearer {admin_token}");
+
+    // Seven human players, so the opening round deals seven cards each
+    let mut users = Vec::new();
+    for _ in 0..7 {
+        users.push(create_user(&db).await?);
+    }
+    let auth_for = |user_id: &str| {
+        users
+            .iter()
+            .find(|(id, _)| id.to_string() == user_id)
+            .map(|(_, auth)| auth.clone())
+            .unwrap()
+    };
+
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", users[0].1.as_str()))
+        .set_json(serde_json::json!({ "player_count": 7 }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let created: Value = actix_web::test::read_body_json(res).await;
+    let game_id = created["game"]["id"].as_str().unwrap().to_string();
+
+    for (_, auth) in &users[1..] {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/join_game?game_id={game_id}"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+    for (_, auth) in &users {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/ready"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // Play the opening round and the first bid of the next, checking the
+    // replayed snapshot against the live one after every action
+    loop {
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!("/api/game/{game_id}/state"))
+            .insert_header(("Authorization", users[0].1.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+        let state: Value = actix_web::test::read_body_json(res).await;
+
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!("/api/admin/game/{game_id}/replay"))
+            .insert_header(("Authorization", admin_auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+        let replayed: Value = actix_web::test::read_body_json(res).await;
+        assert_eq!(comparable(replayed), comparable(state.clone()));
+
+        let round = &state["current_round"];
+        if round["round_number"] == 2 && !round["bids"].as_array().unwrap().is_empty() {
+            break;
+        }
+
+        let player_id = match state["game"]["phase"].as_str().unwrap() {
+            "trump_selection" => state["trump_chooser_id"].as_str().unwrap(),
+            _ => round["current_player_turn"].as_str().unwrap(),
+        };
+        let player = state["players"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|player| player["id"] == player_id)
+            .unwrap();
+        let auth = auth_for(player["user_id"].as_str().unwrap());
+
+        match state["game"]["phase"].as_str().unwrap() {
+            "bidding" => {
+                let req = actix_web::test::TestRequest::post()
+                    .uri(&format!("/api/game/{game_id}/bid"))
+                    .insert_header(("Authorization", auth.as_str()))
+                    .set_json(serde_json::json!({ "bid": 1 }))
+                    .to_request();
+                let res = actix_web::test::call_service(&app, req).await;
+                assert!(res.status().is_success());
+            }
+            "trump_selection" => {
+                let req = actix_web::test::TestRequest::post()
+                    .uri(&format!("/api/game/{game_id}/trump"))
+                    .insert_header(("Authorization", auth.as_str()))
+                    .set_json(serde_json::json!({ "trump_suit": "Hearts" }))
+                    .to_request();
+                let res = actix_web::test::call_service(&app, req).await;
+                assert!(res.status().is_success());
+            }
+            _ => {
+                // Try the hand in order until a card follows suit
+                let mut played = false;
+                for card in player["hand"].as_array().unwrap() {
+                    let req = actix_web::test::TestRequest::post()
+                        .uri(&format!("/api/game/{game_id}/play"))
+                        .insert_header(("Authorization", auth.as_str()))
+                        .set_json(serde_json::json!({ "card": card }))
+                        .to_request();
+                    let res = actix_web::test::call_service(&app, req).await;
+                    if res.status().is_success() {
+                        played = true;
+                        break;
+                    }
+                }
+                assert!(played);
+            }
+        }
+    }
+
+    // The log is numbered without gaps and records every step in order
+    let game_id: Uuid = game_id.parse()?;
+    let log = game_events::Entity::find()
+        .filter(game_events::Column::GameId.eq(game_id))
+        .order_by_asc(game_events::Column::Sequence)
+        .all(&db)
+        .await?;
+    let sequences: Vec<i32> = log.iter().map(|entry| entry.sequence).collect();
+    assert_eq!(sequences, (1..=log.len() as i32).collect::<Vec<_>>());
+
+    let count = |event_type: &str| {
+        log.iter()
+            .filter(|entry| entry.event_type == event_type)
+            .count()
+    };
+    let types: Vec<&str> = log.iter().map(|entry| entry.event_type.as_str()).collect();
+    assert_eq!(types[..7], ["player_joined"; 7]);
+    assert_eq!(types[7..14], ["player_ready"; 7]);
+    assert_eq!(types[14..16], ["game_started", "round_dealt"]);
+    assert_eq!(count("bid_placed"), 8);
+    assert_eq!(count("trump_chosen"), 1);
+    assert_eq!(count("card_played"), 49);
+    assert_eq!(count("trick_won"), 7);
+    assert_eq!(count("round_scored"), 1);
+    assert_eq!(count("round_dealt"), 2);
+    assert_eq!(types.last(), Some(&"bid_placed"));
+
+    // Replaying the log is admin-only
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("/api/admin/game/{game_id}/replay"))
+        .insert_header(("Authorization", users[0].1.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), actix_web::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+mod common;
+use std::time::{Duration, Instant};
+
+use common::{create_user, test_bootstrap, without_ai_delay};
+use serde_json::Value;
+
+#[actix_web::test]
+async fn game_state_is_versioned_for_etags_and_long_polling() -> anyhow::Result<()> {
+    without_ai_delay();
+    std::env::set_var("LONG_POLL_TIMEOUT_MS", "300");
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+    let (_, auth) = create_user(&db).await?;
+    let (_, stranger) = create_user(&db).await?;
+
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", auth.as_str()))
+        .set_json(serde_json::json!({ "player_count": 3 }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let created: Value = actix_web::test::read_body_json(res).await;
+    let game_id = created["game"]["id"].as_str().unwrap().to_string();
+    let state_uri = format!("/api/game/{game_id}/state");
+
+    // The state carries its version, both in the body and as its ETag
+    let req = actix_web::test::TestRequest::get()
+        .uri(&state_uri)
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let etag = res.headers().get("ETag").unwrap().to_str()?.to_string();
+    let state: Value = actix_web::test::read_body_json(res).await;
+    let version = state["game"]["version"].as_i64().unwrap();
+    assert_eq!(etag, format!("\"{version}\""));
+
+    // An unchanged game is not sent again
+    let req = actix_web::test::TestRequest::get()
+        .uri(&state_uri)
+        .insert_header(("Authorization", auth.as_str()))
+        .insert_header(("If-None-Match", etag.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), actix_web::http::StatusCode::NOT_MODIFIED);
+    assert_eq!(res.headers().get("ETag").unwrap().to_str()?, etag);
+
+    // A long-poll nothing happens to times out unmodified
+    let started = Instant::now();
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("{state_uri}?wait_for_version={version}"))
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), actix_web::http::StatusCode::NOT_MODIFIED);
+    assert!(started.elapsed() >= Duration::from_millis(300));
+
+    // Strangers are turned away without waiting
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("{state_uri}?wait_for_version={version}"))
+        .insert_header(("Authorization", stranger.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), actix_web::http::StatusCode::FORBIDDEN);
+
+    // A waiting long-poll is answered as soon as the game changes
+    std::env::set_var("LONG_POLL_TIMEOUT_MS", "10000");
+    let started = Instant::now();
+    let poll = actix_web::test::TestRequest::get()
+        .uri(&format!("{state_uri}?wait_for_version={version}"))
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let ready = async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/ready"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        actix_web::test::call_service(&app, req).await
+    };
+    let (res, readied) = tokio::join!(actix_web::test::call_service(&app, poll), ready);
+    assert!(readied.status().is_success());
+    assert!(res.status().is_success());
+    assert!(started.elapsed() < Duration::from_secs(10));
+    let etag = res.headers().get("ETag").unwrap().to_str()?.to_string();
+    let state: Value = actix_web::test::read_body_json(res).await;
+    let readied_version = state["game"]["version"].as_i64().unwrap();
+    assert!(readied_version > version);
+    assert_eq!(etag, format!("\"{readied_version}\""));
+    assert_eq!(state["players"][0]["is_ready"], true);
+
+    // The old ETag no longer matches
+    let req = actix_web::test::TestRequest::get()
+        .uri(&state_uri)
+        .insert_header(("Authorization", auth.as_str()))
+        .insert_header(("If-None-Match", format!("\"{version}\"")))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+
+    Ok(())
+}
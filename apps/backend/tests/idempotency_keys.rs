@@ -0,0 +1,279 @@
+mod common;
+use std::collections::HashMap;
+
+use actix_web::http::StatusCode;
+use backend::entity::idempotency_keys;
+use chrono::Utc;
+use common::{create_user, test_bootstrap, without_ai_delay};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use serde_json::Value;
+
+/// A card the player whose turn it is may play
+fn legal_card(state: &Value, player_id: &Value) -> String {
+    let hand: Vec<String> = state["players"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|player| &player["id"] == player_id)
+        .and_then(|player| serde_json::from_value(player["hand"].clone()).ok())
+        .unwrap();
+    let lead = state["current_round"]["current_trick"]["plays"]
+        .as_array()
+        .and_then(|plays| plays.first())
+        .and_then(|play| play["card"].as_str())
+        .map(|card| card[card.len() - 1..].to_string());
+    lead.and_then(|suit| hand.iter().find(|card| card.ends_with(&suit)).cloned())
+        .unwrap_or_else(|| hand[0].clone())
+}
+
+#[actix_web::test]
+async fn retried_requests_replay_the_original_response() -> anyhow::Result<()> {
+    without_ai_delay();
+    std::env::set_var("LONG_POLL_TIMEOUT_MS", "2000");
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+    let (host_id, host) = create_user(&db).await?;
+    let (guest_id, guest) = create_user(&db).await?;
+
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", host.as_str()))
+        .set_json(serde_json::json!({ "player_count": 3 }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let created: Value = actix_web::test::read_body_json(res).await;
+    let game_id = created["game"]["id"].as_str().unwrap().to_string();
+    let mut auths = HashMap::new();
+    auths.insert(created["game_players"][0]["id"].clone(), host.clone());
+
+    // A retried join is answered as the first one was, and seats the guest once
+    let join = |key: &str| {
+        actix_web::test::TestRequest::post()
+            .uri(&format!("/api/join_game?game_id={game_id}"))
+            .insert_header(("Authorization", guest.as_str()))
+            .insert_header(("Idempotency-Key", key))
+            .to_request()
+    };
+    let res = actix_web::test::call_service(&app, join("join-1")).await;
+    assert!(res.status().is_success());
+    assert!(res.headers().get("Idempotent-Replayed").is_none());
+    let joined: Value = actix_web::test::read_body_json(res).await;
+    let res = actix_web::test::call_service(&app, join("join-1")).await;
+    assert!(res.status().is_success());
+    assert_eq!(res.headers().get("Idempotent-Replayed").unwrap(), "true");
+    let replayed: Value = actix_web::test::read_body_json(res).await;
+    assert_eq!(replayed, joined);
+    auths.insert(joined["game_player"]["id"].clone(), guest.clone());
+
+    // A new key runs the request again, which now fails as usual
+    let res = actix_web::test::call_service(&app, join("join-2")).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let body: Value = actix_web::test::read_body_json(res).await;
+    assert_eq!(body["error"], "User is already in this game");
+
+    // Reusing a key for another request is refused; keys are kept per user
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/ready"))
+        .insert_header(("Authorization", guest.as_str()))
+        .insert_header(("Idempotency-Key", "join-1"))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/ready"))
+        .insert_header(("Authorization", host.as_str()))
+        .insert_header(("Idempotency-Key", "join-1"))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/ready"))
+        .insert_header(("Authorization", host.as_str()))
+        .insert_header(("Idempotency-Key", "not a key"))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    for (uri, auth) in [
+        (format!("/api/game/{game_id}/ready"), &guest),
+        (format!("/api/game/{game_id}/add_ai"), &host),
+    ] {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&uri)
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // Retry a bid and then a card play, each as soon as a player is due
+    let mut version = 0;
+    let mut retried = Vec::new();
+    while retried.len() < 2 {
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!(
+                "/api/game/{game_id}/state?wait_for_version={version}"
+            ))
+            .insert_header(("Authorization", host.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        if res.status() == StatusCode::NOT_MODIFIED {
+            continue;
+        }
+        let state: Value = actix_web::test::read_body_json(res).await;
+        version = state["game"]["version"].as_i64().unwrap();
+        let player_id = match state["game"]["phase"].as_str().unwrap() {
+            "trump_selection" => state["trump_chooser_id"].clone(),
+            _ => state["current_round"]["current_player_turn"].clone(),
+        };
+        let Some(auth) = auths.get(&player_id) else {
+            continue;
+        };
+        // Hands are only shown to their owner
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!("/api/game/{game_id}/state"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        let state: Value = actix_web::test::read_body_json(res).await;
+        if state["game"]["version"] != version {
+            continue;
+        }
+
+        let (uri, body) = match (state["game"]["phase"].as_str().unwrap(), retried.len()) {
+            ("bidding", _) => {
+                let bid = match state["current_round"]["forbidden_bid"].as_i64() {
+                    Some(0) => 1,
+                    _ => 0,
+                };
+                ("bid", serde_json::json!({ "bid": bid }))
+            }
+            ("trump_selection", _) => ("trump", serde_json::json!({ "trump_suit": "Spades" })),
+            ("playing", 1) => (
+                "play",
+                serde_json::json!({ "card": legal_card(&state, &player_id) }),
+            ),
+            _ => continue,
+        };
+        let key = format!("{uri}-{version}");
+        let send = || {
+            actix_web::test::TestRequest::post()
+                .uri(&format!("/api/game/{game_id}/{uri}"))
+                .insert_header(("Authorization", auth.as_str()))
+                .insert_header(("Idempotency-Key", key.as_str()))
+                .set_json(body.clone())
+                .to_request()
+        };
+        let res = actix_web::test::call_service(&app, send()).await;
+        let status = res.status();
+        let first: Value = actix_web::test::read_body_json(res).await;
+        assert!(status.is_success(), "{uri} failed: {first}");
+        if uri == "trump" || (uri == "bid" && retried.len() == 1) {
+            // Only move the game along to the first trick
+            continue;
+        }
+
+        let res = actix_web::test::call_service(&app, send()).await;
+        assert!(res.status().is_success());
+        assert_eq!(res.headers().get("Idempotent-Replayed").unwrap(), "true");
+        let replayed: Value = actix_web::test::read_body_json(res).await;
+        assert_eq!(replayed, first);
+        retried.push((auth.clone(), uri, key, body));
+    }
+
+    // Each retry ran once: one bid and one card from the players who sent them
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("/api/game/{game_id}/state"))
+        .insert_header(("Authorization", host.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    let state: Value = actix_web::test::read_body_json(res).await;
+    assert_eq!(state["current_round"]["bids"].as_array().unwrap().len(), 3);
+
+    // Expired keys are swept, after which a retry is handled again
+    let stored = idempotency_keys::Entity::find()
+        .filter(idempotency_keys::Column::UserId.is_in([host_id, guest_id]))
+        .all(&db)
+        .await?;
+    assert!(stored.len() >= 4);
+    std::env::set_var("IDEMPOTENCY_KEY_TTL_MS", "0");
+    let purged = backend::idempotency::purge_expired(&db)
+        .await
+        .map_err(anyhow::Error::msg)?;
+    assert!(purged >= stored.len() as u64);
+    std::env::remove_var("IDEMPOTENCY_KEY_TTL_MS");
+    let stored = idempotency_keys::Entity::find()
+        .filter(idempotency_keys::Column::UserId.is_in([host_id, guest_id]))
+        .all(&db)
+        .await?;
+    assert!(stored.is_empty());
+
+    let (auth, uri, key, body) = &retried[0];
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/{uri}"))
+        .insert_header(("Authorization", auth.as_str()))
+        .insert_header(("Idempotency-Key", key.as_str()))
+        .set_json(body.clone())
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert!(res.headers().get("Idempotent-Replayed").is_none());
+
+    // A key whose request never answered holds retries off only for its lease
+    let payload = serde_json::json!({ "player_count": 3 });
+    let abandoned = |age: chrono::Duration| idempotency_keys::ActiveModel {
+        user_id: Set(host_id),
+        key: Set("abandoned".to_string()),
+        request_hash: Set(backend::idempotency::request_hash(
+            "POST",
+            "/api/create_game",
+            &serde_json::to_vec(&payload).unwrap(),
+        )),
+        response_status: Set(None),
+        response_body: Set(None),
+        created_at: Set((Utc::now() - age).into()),
+    };
+    let retry = || {
+        actix_web::test::TestRequest::post()
+            .uri("/api/create_game")
+            .insert_header(("Authorization", host.as_str()))
+            .insert_header(("Idempotency-Key", "abandoned"))
+            .set_json(payload.clone())
+            .to_request()
+    };
+    abandoned(chrono::Duration::zero()).insert(&db).await?;
+    let res = actix_web::test::call_service(&app, retry()).await;
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    idempotency_keys::Entity::delete_many()
+        .filter(idempotency_keys::Column::UserId.eq(host_id))
+        .filter(idempotency_keys::Column::Key.eq("abandoned"))
+        .exec(&db)
+        .await?;
+
+    // Once the lease is up the retry is handled, and its answer kept for the next
+    std::env::set_var("IDEMPOTENCY_LEASE_MS", "1000");
+    abandoned(chrono::Duration::seconds(2)).insert(&db).await?;
+    let res = actix_web::test::call_service(&app, retry()).await;
+    assert!(res.status().is_success());
+    assert!(res.headers().get("Idempotent-Replayed").is_none());
+    let handled: Value = actix_web::test::read_body_json(res).await;
+    std::env::remove_var("IDEMPOTENCY_LEASE_MS");
+    let stored = idempotency_keys::Entity::find_by_id((host_id, "abandoned".to_string()))
+        .one(&db)
+        .await?
+        .expect("the retry's answer is kept");
+    assert_eq!(stored.response_status, Some(200));
+
+    let res = actix_web::test::call_service(&app, retry()).await;
+    assert_eq!(res.headers().get("Idempotent-Replayed").unwrap(), "true");
+    let replayed: Value = actix_web::test::read_body_json(res).await;
+    assert_eq!(replayed, handled);
+
+    Ok(())
+}
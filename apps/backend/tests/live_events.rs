@@ -0,0 +1,214 @@
+mod common;
+use std::pin::Pin;
+use std::time::Duration;
+
+use actix_web::body::MessageBody;
+use common::{create_user, test_bootstrap, without_ai_delay};
+use serde_json::Value;
+
+/// A message read off an event stream; heartbeats have no id or name
+#[derive(Debug)]
+struct StreamMessage {
+    id: Option<i64>,
+    event: Option<String>,
+    data: Value,
+}
+
+/// Reads messages off a streaming response body
+struct EventStream<B> {
+    body: Pin<Box<B>>,
+    buffer: String,
+}
+
+impl<B: MessageBody> EventStream<B> {
+    fn new(body: B) -> Self {
+        Self {
+            body: Box::pin(body),
+            buffer: String::new(),
+        }
+    }
+
+    async fn next(&mut self) -> StreamMessage {
+        loop {
+            if let Some(end) = self.buffer.find("\n\n") {
+                let message: String = self.buffer.drain(..end + 2).collect();
+                let mut parsed = StreamMessage {
+                    id: None,
+                    event: None,
+                    data: Value::Null,
+                };
+                for line in message.lines() {
+                    if let Some(id) = line.strip_prefix("id: ") {
+                        parsed.id = Some(id.parse().unwrap());
+                    } else if let Some(event) = line.strip_prefix("event: ") {
+                        parsed.event = Some(event.to_string());
+                    } else if let Some(data) = line.strip_prefix("data: ") {
+                        parsed.data = serde_json::from_str(data).unwrap();
+                    }
+                }
+                return parsed;
+            }
+
+            let chunk = tokio::time::timeout(
+                Duration::from_secs(10),
+                futures_util::future::poll_fn(|cx| self.body.as_mut().poll_next(cx)),
+            )
+            .await
+            .expect("No message arrived")
+            .expect("Stream ended");
+            let Ok(chunk) = chunk else {
+                panic!("Stream failed");
+            };
+            self.buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+    }
+
+    /// Skip to the next message that is not a heartbeat
+    async fn next_event(&mut self) -> StreamMessage {
+        loop {
+            let message = self.next().await;
+            if message.event.is_some() {
+                return message;
+            }
+        }
+    }
+}
+
+#[actix_web::test]
+async fn game_events_stream_resumes_after_last_event_id() -> anyhow::Result<()> {
+    without_ai_delay();
+    std::env::set_var("SSE_HEARTBEAT_MS", "200");
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+    let (_, auth) = create_user(&db).await?;
+    let (_, stranger) = create_user(&db).await?;
+
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", auth.as_str()))
+        .set_json(serde_json::json!({ "player_count": 3 }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let created: Value = actix_web::test::read_body_json(res).await;
+    let game_id = created["game"]["id"].as_str().unwrap().to_string();
+    let host_id = created["game_players"][0]["id"].clone();
+    let events_uri = format!("/api/game/{game_id}/events");
+
+    // Only the game's players may follow it, and resume ids must be sequences
+    for (auth, last_event_id, status) in [
+        (&stranger, None, actix_web::http::StatusCode::FORBIDDEN),
+        (
+            &auth,
+            Some("latest"),
+            actix_web::http::StatusCode::BAD_REQUEST,
+        ),
+    ] {
+        let mut req = actix_web::test::TestRequest::get()
+            .uri(&events_uri)
+            .insert_header(("Authorization", auth.as_str()));
+        if let Some(last_event_id) = last_event_id {
+            req = req.insert_header(("Last-Event-ID", last_event_id));
+        }
+        let res = actix_web::test::call_service(&app, req.to_request()).await;
+        assert_eq!(res.status(), status);
+    }
+
+    // A new stream starts with the current snapshot
+    let req = actix_web::test::TestRequest::get()
+        .uri(&events_uri)
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    assert_eq!(
+        res.headers().get("Content-Type").unwrap(),
+        "text/event-stream"
+    );
+    let mut stream = EventStream::new(res.into_body());
+    let snapshot = stream.next_event().await;
+    assert_eq!(snapshot.event.as_deref(), Some("snapshot"));
+    assert_eq!(snapshot.data["game"]["state"], "waiting");
+    let mut last_id = snapshot.id.unwrap();
+
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/ready"))
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    for _ in 0..2 {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/add_ai"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // Typed events follow with consecutive ids, until both AI seats have bid
+    let mut bids = Vec::new();
+    while bids.len() < 2 {
+        let message = stream.next_event().await;
+        assert_eq!(message.id, Some(last_id + 1));
+        last_id += 1;
+        let event = message.event.clone().unwrap();
+        assert_eq!(message.data["type"], event);
+        if event == "round_dealt" {
+            assert_eq!(message.data["hands"].as_array().unwrap().len(), 1);
+            assert_eq!(message.data["hands"][0][0], host_id);
+        }
+        if event == "bid_placed" {
+            bids.push(message);
+        }
+    }
+
+    // The host is due to bid, so the idle stream sends heartbeats
+    let heartbeat = stream.next().await;
+    assert!(heartbeat.id.is_none() && heartbeat.event.is_none());
+
+    // Reconnecting after the first bid replays only what came after it
+    let req = actix_web::test::TestRequest::get()
+        .uri(&events_uri)
+        .insert_header(("Authorization", auth.as_str()))
+        .insert_header(("Last-Event-ID", bids[0].id.unwrap().to_string()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let mut resumed = EventStream::new(res.into_body());
+    let replayed = resumed.next_event().await;
+    assert_eq!(replayed.id, bids[1].id);
+    assert_eq!(replayed.data, bids[1].data);
+
+    // New events reach every open stream
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("/api/game/{game_id}/state"))
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    let state: Value = actix_web::test::read_body_json(res).await;
+    let bid = match state["current_round"]["forbidden_bid"].as_i64() {
+        Some(0) => 1,
+        _ => 0,
+    };
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/bid"))
+        .insert_header(("Authorization", auth.as_str()))
+        .set_json(serde_json::json!({ "bid": bid }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    for stream in [&mut stream, &mut resumed] {
+        let message = stream.next_event().await;
+        assert_eq!(message.id, Some(last_id + 1));
+        assert_eq!(message.event.as_deref(), Some("bid_placed"));
+        assert_eq!(message.data["player_id"], host_id);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+mod common;
+use std::time::Duration;
+
+use common::{create_user, test_bootstrap, without_ai_delay};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+type GameSocket = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Read the next update pushed on a game socket
+async fn next_update(socket: &mut GameSocket) -> Value {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(10), socket.next())
+            .await
+            .expect("No update arrived")
+            .expect("Socket closed")
+            .unwrap();
+        if let Message::Text(text) = message {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+#[actix_web::test]
+async fn game_updates_are_pushed_and_actions_accepted_over_websocket() -> anyhow::Result<()> {
+    without_ai_delay();
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+    let served = db.clone();
+    let server = actix_web::HttpServer::new(move || {
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(served.clone()))
+            .configure(backend::configure_routes)
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))?;
+    let address = server.addrs()[0];
+    actix_web::rt::spawn(server.run());
+
+    let (_, auth) = create_user(&db).await?;
+    let (_, stranger) = create_user(&db).await?;
+
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", auth.as_str()))
+        .set_json(serde_json::json!({ "player_count": 3 }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let created: Value = actix_web::test::read_body_json(res).await;
+    let game_id = created["game"]["id"].as_str().unwrap().to_string();
+    let host_id = created["game_players"][0]["id"].clone();
+
+    // Only the game's players may watch it
+    let url = format!("ws://{address}/api/game/{game_id}/ws");
+    let mut request = url.as_str().into_client_request()?;
+    request
+        .headers_mut()
+        .insert("Authorization", stranger.parse()?);
+    assert!(tokio_tungstenite::connect_async(request).await.is_err());
+
+    let mut request = url.as_str().into_client_request()?;
+    request.headers_mut().insert("Authorization", auth.parse()?);
+    let (mut socket, _) = tokio_tungstenite::connect_async(request).await?;
+
+    // The current snapshot is sent on connect
+    let update = next_update(&mut socket).await;
+    assert_eq!(update["type"], "snapshot");
+    assert_eq!(update["snapshot"]["game"]["state"], "waiting");
+
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/game/{game_id}/ready"))
+        .insert_header(("Authorization", auth.as_str()))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    for _ in 0..2 {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/add_ai"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // Events arrive in log order until the host is due to bid
+    let mut events = Vec::new();
+    let snapshot = loop {
+        let update = next_update(&mut socket).await;
+        match update["type"].as_str().unwrap() {
+            "event" => events.push(update),
+            _ => {
+                let snapshot = update["snapshot"].clone();
+                if snapshot["current_round"]["current_player_turn"] == host_id {
+                    break snapshot;
+                }
+            }
+        }
+    };
+    let sequences: Vec<i64> = events
+        .iter()
+        .map(|update| update["sequence"].as_i64().unwrap())
+        .collect();
+    assert!(sequences.windows(2).all(|pair| pair[1] == pair[0] + 1));
+    let of_type = |event_type: &str| {
+        events
+            .iter()
+            .filter(|update| update["event"]["type"] == event_type)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(of_type("game_started").len(), 1);
+    assert_eq!(of_type("bid_placed").len(), 2);
+
+    // Only the host's own cards are ever sent to them
+    let dealt = &of_type("round_dealt")[0]["event"];
+    assert_eq!(dealt["deal_seed"], 0);
+    assert_eq!(dealt["hands"].as_array().unwrap().len(), 1);
+    assert_eq!(dealt["hands"][0][0], host_id);
+    for player in snapshot["players"].as_array().unwrap() {
+        assert_eq!(player["hand"].is_null(), player["id"] != host_id);
+    }
+
+    // Rejected actions are answered on the socket
+    socket
+        .send(Message::text(r#"{"type": "bid", "bid": 99}"#))
+        .await?;
+    let update = next_update(&mut socket).await;
+    assert_eq!(update["type"], "error");
+    socket.send(Message::text("not an action")).await?;
+    let update = next_update(&mut socket).await;
+    assert_eq!(update["type"], "error");
+
+    // An action chosen at an older version is refused with the current one
+    let version = snapshot["game"]["version"].as_i64().unwrap();
+    let bid = match snapshot["current_round"]["forbidden_bid"].as_i64() {
+        Some(0) => 1,
+        _ => 0,
+    };
+    socket
+        .send(Message::text(
+            serde_json::json!({ "type": "bid", "bid": bid, "expected_version": version - 1 })
+                .to_string(),
+        ))
+        .await?;
+    let update = next_update(&mut socket).await;
+    assert_eq!(update["type"], "error");
+    assert_eq!(update["code"], "VERSION_CONFLICT");
+    assert_eq!(update["current_version"], version);
+
+    socket
+        .send(Message::text(
+            serde_json::json!({ "type": "bid", "bid": bid, "expected_version": version })
+                .to_string(),
+        ))
+        .await?;
+    let update = next_update(&mut socket).await;
+    assert_eq!(update["type"], "event");
+    assert_eq!(update["event"]["type"], "bid_placed");
+    assert_eq!(update["event"]["player_id"], host_id);
+    assert_eq!(update["event"]["bid"], bid);
+
+    Ok(())
+}
@@ -0,0 +1,199 @@
+mod common;
+use backend::game_management::cards::{Card, Trump};
+use backend::game_management::rules::{RuleSet, DECK_SIZE, MAX_PLAYERS, MIN_PLAYERS};
+use backend::game_management::tricks::{determine_trick_winner, get_next_trick_turn};
+use chrono::Utc;
+use common::{test_bootstrap, test_issue_token};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use sea_orm::{ActiveModelTrait, Set};
+use uuid::Uuid;
+
+/// Play every round of a game at the given table size using the pure domain
+/// helpers: deal from the schedule, follow suit where possible, and hand the
+/// lead to each trick winner. Returns the total number of tricks played.
+fn play_full_game(player_count: usize, seed: u64) -> i32 {
+    let rules = RuleSet::for_players(player_count).unwrap();
+    let seats: Vec<Uuid> = (0..player_count).map(|_| Uuid::new_v4()).collect();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut total_tricks = 0;
+
+    for round_number in 1..=rules.total_rounds() {
+        let cards_dealt = rules.cards_dealt(round_number).unwrap();
+        assert!(rules.is_valid_bid(cards_dealt));
+
+        let mut deck = Card::full_deck();
+        deck.shuffle(&mut rng);
+        let mut hands: Vec<Vec<Card>> = deck
+            .chunks(cards_dealt as usize)
+            .take(player_count)
+            .map(|hand| hand.to_vec())
+            .collect();
+        assert_eq!(hands.len(), player_count);
+
+        let trump = Trump::ALL[(round_number as usize - 1) % Trump::ALL.len()];
+        let dealer = rules.dealer_index_for_round(round_number);
+        let mut leader = rules.next_player_index(dealer);
+        let mut tricks_won = vec![0; player_count];
+
+        for _ in 0..cards_dealt {
+            let mut plays: Vec<(Card, Uuid)> = Vec::with_capacity(player_count);
+            let mut seat = leader;
+
+            for _ in 0..player_count {
+                let hand = &mut hands[seat];
+                let index = match plays.first() {
+                    Some((lead, _)) => hand
+                        .iter()
+                        .position(|card| card.suit == lead.suit)
+                        .unwrap_or(0),
+                    None => 0,
+                };
+                plays.push((hand.remove(index), seats[seat]));
+                seat = get_next_trick_turn(seat as i32, player_count) as usize;
+            }
+
+            let winner = determine_trick_winner(&plays, trump).unwrap();
+            leader = seats.iter().position(|&id| id == winner).unwrap();
+            tricks_won[leader] += 1;
+        }
+
+        assert!(hands.iter().all(|hand| hand.is_empty()));
+        assert_eq!(tricks_won.iter().sum::<i32>(), cards_dealt);
+        total_tricks += cards_dealt;
+    }
+
+    total_tricks
+}
+
+#[test]
+fn full_games_at_every_table_size() {
+    for player_count in MIN_PLAYERS..=MAX_PLAYERS {
+        let rules = RuleSet::for_players(player_count).unwrap();
+        let expected: i32 = rules.round_schedule.iter().sum();
+
+        for seed in 0..3 {
+            assert_eq!(play_full_game(player_count, seed), expected);
+        }
+    }
+}
+
+#[actix_web::test]
+async fn games_start_at_every_table_size() -> anyhow::Result<()> {
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+
+    for player_count in MIN_PLAYERS..=MAX_PLAYERS {
+        // Create a human host for this table
+        let user_id = Uuid::new_v4();
+        let user = backend::entity::users::ActiveModel {
+            id: Set(user_id),
+            external_id: Set(user_id.to_string()),
+            email: Set(format!("seats-{user_id}@example.com")),
+            name: Set(Some("Seat Tester".to_string())),
+            is_ai: Set(false),
+            created_at: Set(Utc::now().into()),
+            updated_at: Set(Utc::now().into()),
+        };
+        let user = user.insert(&db).await?;
+        let token = test_issue_token(&user.external_id, &user.email, 3600);
+        let auth = format!("Bearer {token}");
+
+        // Create the game with the requested number of seats
+        let req = actix_web::test::TestRequest::post()
+            .uri("/api/create_game")
+            .insert_header(("Authorization", auth.as_str()))
+            .set_json(serde_json::json!({ "player_count": player_count }))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+        let created: serde_json::Value = actix_web::test::read_body_json(res).await;
+        let game_id = created["game"]["id"].as_str().unwrap().to_string();
+
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/ready"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+
+        // Fill the remaining seats with AI players
+        for _ in 1..player_count {
+            let req = actix_web::test::TestRequest::post()
+                .uri(&format!("/api/game/{game_id}/add_ai"))
+                .insert_header(("Authorization", auth.as_str()))
+                .to_request();
+            let res = actix_web::test::call_service(&app, req).await;
+            assert!(res.status().is_success());
+        }
+
+        // The table is full, so no further seats can be taken
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/add_ai"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!("/api/game/{game_id}/state"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+        let state: serde_json::Value = actix_web::test::read_body_json(res).await;
+
+        let max_cards = (DECK_SIZE / player_count) as u64;
+        assert_eq!(state["game"]["state"], "started");
+        assert_eq!(state["max_players"], player_count);
+        assert_eq!(state["player_count"], player_count);
+        assert_eq!(state["current_round"]["cards_dealt"], max_cards);
+
+        let host = state["players"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|player| player["user_id"] == user_id.to_string())
+            .unwrap();
+        assert_eq!(host["hand"].as_array().unwrap().len() as u64, max_cards);
+    }
+
+    // Table sizes outside 3 to 7 are rejected
+    let token = test_issue_token("seats-invalid", "seats-invalid@example.com", 3600);
+    let auth = format!("Bearer {token}");
+    for player_count in [2, 8] {
+        let req = actix_web::test::TestRequest::post()
+            .uri("/api/create_game")
+            .insert_header(("Authorization", auth.as_str()))
+            .set_json(serde_json::json!({ "player_count": player_count }))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+
+    // So are options that don't parse, rather than falling back to defaults
+    for body in [
+        r#"{"player_count": "5"}"#,
+        r#"{"scoring": "bogus"}"#,
+        r#"{"dealer_hook": 1}"#,
+    ] {
+        let req = actix_web::test::TestRequest::post()
+            .uri("/api/create_game")
+            .insert_header(("Authorization", auth.as_str()))
+            .insert_header(("Content-Type", "application/json"))
+            .set_payload(body)
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = actix_web::test::read_body_json(res).await;
+        assert_eq!(body["error"], "Invalid request body");
+    }
+
+    Ok(())
+}
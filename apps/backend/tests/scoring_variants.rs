@@ -0,0 +1,133 @@
+mod common;
+use backend::entity::{game_players, game_rounds, games, round_bids, round_scores, users};
+use backend::game_management::scoring::ScoringVariant;
+use chrono::Utc;
+use common::{test_bootstrap, test_issue_token};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use uuid::Uuid;
+
+/// (bid, tricks_won) for the host in each of two recorded rounds
+const HOST_ROUNDS: [(i32, i32); 2] = [(3, 3), (2, 4)];
+
+#[actix_web::test]
+async fn summary_follows_scoring_variant() -> anyhow::Result<()> {
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+
+    for variant in ScoringVariant::ALL {
+        let user_id = Uuid::new_v4();
+        let user = users::ActiveModel {
+            id: Set(user_id),
+            external_id: Set(user_id.to_string()),
+            email: Set(format!("scoring-{user_id}@example.com")),
+            name: Set(Some("Scoring Tester".to_string())),
+            is_ai: Set(false),
+            created_at: Set(Utc::now().into()),
+            updated_at: Set(Utc::now().into()),
+        };
+        let user = user.insert(&db).await?;
+        let token = test_issue_token(&user.external_id, &user.email, 3600);
+        let auth = format!("Bearer {token}");
+
+        // Create a game with this scoring variant
+        let req = actix_web::test::TestRequest::post()
+            .uri("/api/create_game")
+            .insert_header(("Authorization", auth.as_str()))
+            .set_json(serde_json::json!({ "scoring": variant }))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+        let created: serde_json::Value = actix_web::test::read_body_json(res).await;
+        let game_id: Uuid = created["game"]["id"].as_str().unwrap().parse()?;
+        assert_eq!(
+            created["game"]["rule_set"]["scoring"],
+            serde_json::json!(variant)
+        );
+
+        let host = game_players::Entity::find()
+            .filter(game_players::Column::GameId.eq(game_id))
+            .one(&db)
+            .await?
+            .unwrap();
+
+        // Record finished rounds directly and mark the game completed
+        for (index, (bid, tricks_won)) in HOST_ROUNDS.into_iter().enumerate() {
+            let round_id = Uuid::new_v4();
+            game_rounds::ActiveModel {
+                id: Set(round_id),
+                game_id: Set(game_id),
+                round_number: Set(index as i32 + 1),
+                dealer_player_id: Set(None),
+                trump_suit: Set(None),
+                cards_dealt: Set(13),
+                created_at: Set(Utc::now().into()),
+                deal_seed: Set(None),
+                deal_salt: Set(None),
+                deal_commitment: Set(None),
+            }
+            .insert(&db)
+            .await?;
+            round_bids::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                round_id: Set(round_id),
+                player_id: Set(host.id),
+                bid: Set(bid),
+            }
+            .insert(&db)
+            .await?;
+            round_scores::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                round_id: Set(round_id),
+                player_id: Set(host.id),
+                tricks_won: Set(tricks_won),
+            }
+            .insert(&db)
+            .await?;
+        }
+
+        let game = games::Entity::find_by_id(game_id).one(&db).await?.unwrap();
+        let mut game: games::ActiveModel = game.into();
+        game.state = Set(games::GameState::Completed);
+        game.completed_at = Set(Some(Utc::now().into()));
+        game.update(&db).await?;
+
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!("/api/game/{game_id}/summary"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+        let summary: serde_json::Value = actix_web::test::read_body_json(res).await;
+
+        let rule = variant.rule();
+        let mut expected_total = 0;
+        for (round, (bid, tricks_won)) in summary["rounds"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .zip(HOST_ROUNDS)
+        {
+            let result = &round["player_results"][0];
+            let points = rule.round_points(tricks_won, bid);
+            assert_eq!(result["points"], points, "{variant:?}");
+            assert_eq!(
+                result["bonus"],
+                rule.earns_bonus(tricks_won, bid),
+                "{variant:?}"
+            );
+            expected_total += points;
+        }
+
+        assert_eq!(
+            summary["players"][0]["final_score"], expected_total,
+            "{variant:?}"
+        );
+    }
+
+    Ok(())
+}
@@ -30,14 +30,18 @@ async fn smoke_workflow() -> anyhow::Result<()> {
 
     let user = user.insert(&db).await?;
 
+    // Let this user pin the deal seed so every run is dealt the same hands
+    std::env::set_var("ADMIN_EMAILS", &user.email);
+
     // 2) Mint JWT
     let token = test_issue_token(&user.external_id, &user.email, 3600);
     let auth = format!("Bearer {token}");
 
-    // 3) Create game
+    // 3) Create game with a pinned deal seed
     let req = actix_web::test::TestRequest::post()
         .uri("/api/create_game")
         .insert_header(("Authorization", auth.as_str()))
+        .set_json(serde_json::json!({ "deal_seed": 7 }))
         .to_request();
     let res = actix_web::test::call_service(&app, req).await;
     assert!(res.status().is_success());
@@ -125,7 +129,7 @@ async fn smoke_workflow() -> anyhow::Result<()> {
     // 6) Submit trump (highest bid wins; tie -> first-highest in turn order)
     // This might need to be adjusted based on your actual game flow
     let trump_data = serde_json::json!({
-        "trump_suit": "hearts"
+        "trump_suit": "Hearts"
     });
 
     let req = actix_web::test::TestRequest::post()
@@ -0,0 +1,211 @@
+mod common;
+use std::collections::HashMap;
+
+use actix_web::http::StatusCode;
+use common::{create_user, test_bootstrap, without_ai_delay};
+use serde_json::Value;
+
+#[actix_web::test]
+async fn stale_actions_are_refused_and_seats_never_collide() -> anyhow::Result<()> {
+    without_ai_delay();
+    std::env::set_var("LONG_POLL_TIMEOUT_MS", "2000");
+    let db = test_bootstrap().await;
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(actix_web::web::Data::new(db.clone()))
+            .configure(backend::configure_routes),
+    )
+    .await;
+    let (_, host) = create_user(&db).await?;
+    let (_, first) = create_user(&db).await?;
+    let (_, second) = create_user(&db).await?;
+
+    let req = actix_web::test::TestRequest::post()
+        .uri("/api/create_game")
+        .insert_header(("Authorization", host.as_str()))
+        .set_json(serde_json::json!({ "player_count": 4 }))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let created: Value = actix_web::test::read_body_json(res).await;
+    let game_id = created["game"]["id"].as_str().unwrap().to_string();
+    let mut auths = HashMap::new();
+    auths.insert(created["game_players"][0]["id"].clone(), host.clone());
+
+    let state = |auth: String| {
+        actix_web::test::TestRequest::get()
+            .uri(&format!("/api/game/{game_id}/state"))
+            .insert_header(("Authorization", auth))
+            .to_request()
+    };
+    let res = actix_web::test::call_service(&app, state(host.clone())).await;
+    let seen: Value = actix_web::test::read_body_json(res).await;
+    let version = seen["game"]["version"].as_i64().unwrap();
+
+    // A join chosen at an older version is refused with the current one
+    let req = actix_web::test::TestRequest::post()
+        .uri(&format!("/api/join_game?game_id={game_id}"))
+        .insert_header(("Authorization", first.as_str()))
+        .insert_header(("If-Match", format!("\"{}\"", version + 5)))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    assert_eq!(
+        res.headers().get("ETag").unwrap().to_str()?,
+        format!("\"{version}\"")
+    );
+    let body: Value = actix_web::test::read_body_json(res).await;
+    assert_eq!(body["code"], "VERSION_CONFLICT");
+    assert_eq!(body["current_version"], version);
+
+    // Users joining at once are given different seats
+    let join = |auth: &str| {
+        actix_web::test::TestRequest::post()
+            .uri(&format!("/api/join_game?game_id={game_id}"))
+            .insert_header(("Authorization", auth))
+            .to_request()
+    };
+    let (joined_first, joined_second) = tokio::join!(
+        actix_web::test::call_service(&app, join(&first)),
+        actix_web::test::call_service(&app, join(&second)),
+    );
+    let mut turn_orders = Vec::new();
+    for (res, auth) in [(joined_first, &first), (joined_second, &second)] {
+        assert!(res.status().is_success());
+        let joined: Value = actix_web::test::read_body_json(res).await;
+        turn_orders.push(joined["turn_order"].as_i64().unwrap());
+        auths.insert(joined["game_player"]["id"].clone(), auth.clone());
+    }
+    turn_orders.sort();
+    assert_eq!(turn_orders, vec![1, 2]);
+
+    // Only one AI seat fits when two are added at once
+    let add_ai = || {
+        actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/add_ai"))
+            .insert_header(("Authorization", host.as_str()))
+            .to_request()
+    };
+    let (added_first, added_second) = tokio::join!(
+        actix_web::test::call_service(&app, add_ai()),
+        actix_web::test::call_service(&app, add_ai()),
+    );
+    let mut statuses = vec![added_first.status(), added_second.status()];
+    statuses.sort();
+    assert_eq!(statuses, vec![StatusCode::OK, StatusCode::BAD_REQUEST]);
+
+    // Bodies that don't parse are refused in the usual error shape
+    for (uri, body) in [
+        ("ready", serde_json::json!({ "expected_version": "latest" })),
+        ("trump", serde_json::json!({ "trump_suit": "Purple" })),
+        ("play", serde_json::json!({ "card": "ZZ" })),
+    ] {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/{uri}"))
+            .insert_header(("Authorization", host.as_str()))
+            .set_json(body)
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let body: Value = actix_web::test::read_body_json(res).await;
+        assert_eq!(body["error"], "Invalid request body");
+    }
+
+    // Readying up with the body field works at the current version only
+    let res = actix_web::test::call_service(&app, state(host.clone())).await;
+    let seen: Value = actix_web::test::read_body_json(res).await;
+    let version = seen["game"]["version"].as_i64().unwrap();
+    for (expected_version, status) in [
+        (version - 1, StatusCode::CONFLICT),
+        (version, StatusCode::OK),
+    ] {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/ready"))
+            .insert_header(("Authorization", host.as_str()))
+            .set_json(serde_json::json!({ "expected_version": expected_version }))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert_eq!(res.status(), status);
+    }
+    for auth in [&first, &second] {
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/ready"))
+            .insert_header(("Authorization", auth.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // Wait for a human to be due to bid
+    let mut version = version;
+    let (seen, bidder) = loop {
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!(
+                "/api/game/{game_id}/state?wait_for_version={version}"
+            ))
+            .insert_header(("Authorization", host.as_str()))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        if res.status() == StatusCode::NOT_MODIFIED {
+            continue;
+        }
+        let seen: Value = actix_web::test::read_body_json(res).await;
+        version = seen["game"]["version"].as_i64().unwrap();
+        if let Some(auth) = auths.get(&seen["current_round"]["current_player_turn"]) {
+            if seen["game"]["phase"] == "bidding" {
+                break (seen, auth.clone());
+            }
+        }
+    };
+
+    // A double-clicked bid is only placed once
+    let bid = match seen["current_round"]["forbidden_bid"].as_i64() {
+        Some(0) => 1,
+        _ => 0,
+    };
+    let place_bid = || {
+        actix_web::test::TestRequest::post()
+            .uri(&format!("/api/game/{game_id}/bid"))
+            .insert_header(("Authorization", bidder.as_str()))
+            .set_json(serde_json::json!({ "bid": bid, "expected_version": version }))
+            .to_request()
+    };
+    let (placed_first, placed_second) = tokio::join!(
+        actix_web::test::call_service(&app, place_bid()),
+        actix_web::test::call_service(&app, place_bid()),
+    );
+    let mut statuses = vec![placed_first.status(), placed_second.status()];
+    statuses.sort();
+    assert_eq!(statuses, vec![StatusCode::OK, StatusCode::CONFLICT]);
+
+    // Trump, play and delete are checked against the version just the same
+    for (method, uri, body) in [
+        (
+            "POST",
+            format!("/api/game/{game_id}/trump"),
+            serde_json::json!({ "trump_suit": "Hearts" }),
+        ),
+        (
+            "POST",
+            format!("/api/game/{game_id}/play"),
+            serde_json::json!({ "card": "AS" }),
+        ),
+        ("DELETE", format!("/api/game/{game_id}"), Value::Null),
+    ] {
+        let req = match method {
+            "POST" => actix_web::test::TestRequest::post().set_json(body),
+            _ => actix_web::test::TestRequest::delete(),
+        };
+        let req = req
+            .uri(&uri)
+            .insert_header(("Authorization", host.as_str()))
+            .insert_header(("If-Match", format!("\"{version}\"")))
+            .to_request();
+        let res = actix_web::test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+    }
+    let res = actix_web::test::call_service(&app, state(host.clone())).await;
+    assert!(res.status().is_success());
+
+    Ok(())
+}